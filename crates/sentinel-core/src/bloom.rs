@@ -0,0 +1,135 @@
+//! A simple Bloom filter for approximate set-membership reconciliation.
+//!
+//! Used by `sentinel-discover`'s stale-host detection to avoid pulling every
+//! stored host's properties just to subtract the IPs seen in the current
+//! scan. Bloom membership has no false negatives, so a host that really was
+//! scanned is never wrongly treated as stale; the small false-positive rate
+//! just means a handful of genuinely-stale hosts stay un-flagged for one
+//! extra scan cycle, which is acceptable for staleness detection.
+
+use std::hash::{Hash, Hasher};
+
+use std::collections::hash_map::DefaultHasher;
+
+/// A fixed-size Bloom filter over `&str` items, sized for a target
+/// false-positive rate at construction time.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: usize,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Build a filter sized for `expected_items` insertions at no worse than
+    /// `target_fpr` false-positive probability (e.g. `0.01` for 1%).
+    ///
+    /// Uses the standard optimal-sizing formulas:
+    /// `m = ceil(-n * ln(p) / ln(2)^2)`, `k = round((m/n) * ln(2))`.
+    pub fn with_fpr(expected_items: usize, target_fpr: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let p = target_fpr.clamp(f64::MIN_POSITIVE, 0.5);
+
+        let num_bits = (-(n * p.ln()) / std::f64::consts::LN_2.powi(2)).ceil() as usize;
+        let num_bits = num_bits.max(64);
+        let num_hashes = (((num_bits as f64 / n) * std::f64::consts::LN_2).round() as u32).max(1);
+
+        Self {
+            bits: vec![0u64; num_bits.div_ceil(64)],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    /// Number of bits backing this filter.
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Number of hash functions used per item.
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// Insert an item into the filter.
+    pub fn insert(&mut self, item: &str) {
+        for bit in self.bit_positions(item) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// True if `item` may be in the set (possible false positive); false
+    /// means `item` is definitely not in the set.
+    pub fn contains(&self, item: &str) -> bool {
+        self.bit_positions(item)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Derive `num_hashes` bit positions for `item` via Kirsch-Mitzenmacher
+    /// double hashing: `h_i = h1 + i * h2 (mod num_bits)`, avoiding the cost
+    /// of `num_hashes` independent hash functions.
+    fn bit_positions(&self, item: &str) -> impl Iterator<Item = usize> + '_ {
+        let h1 = hash_with_seed(item, 0);
+        let h2 = hash_with_seed(item, 1);
+        (0..self.num_hashes).map(move |i| {
+            let combined = h1.wrapping_add((i as u64).wrapping_mul(h2));
+            (combined % self.num_bits as u64) as usize
+        })
+    }
+}
+
+fn hash_with_seed(item: &str, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_after_insert() {
+        let mut filter = BloomFilter::with_fpr(100, 0.01);
+        filter.insert("10.0.1.1");
+        assert!(filter.contains("10.0.1.1"));
+    }
+
+    #[test]
+    fn absent_item_usually_not_contained() {
+        let mut filter = BloomFilter::with_fpr(100, 0.01);
+        for i in 0..100 {
+            filter.insert(&format!("10.0.1.{i}"));
+        }
+        // An item from a disjoint, differently-shaped keyspace should not
+        // collide with a well-sized low-FPR filter.
+        assert!(!filter.contains("never-inserted-marker-key"));
+    }
+
+    #[test]
+    fn no_false_negatives_across_many_items() {
+        let items: Vec<String> = (0..1000).map(|i| format!("192.168.{}.1", i % 256)).collect();
+        let mut filter = BloomFilter::with_fpr(items.len(), 0.01);
+        for item in &items {
+            filter.insert(item);
+        }
+        for item in &items {
+            assert!(filter.contains(item), "false negative for {item}");
+        }
+    }
+
+    #[test]
+    fn sizing_grows_with_expected_items() {
+        let small = BloomFilter::with_fpr(10, 0.01);
+        let large = BloomFilter::with_fpr(10_000, 0.01);
+        assert!(large.num_bits() > small.num_bits());
+    }
+
+    #[test]
+    fn tighter_fpr_uses_more_bits() {
+        let loose = BloomFilter::with_fpr(1000, 0.1);
+        let tight = BloomFilter::with_fpr(1000, 0.001);
+        assert!(tight.num_bits() > loose.num_bits());
+    }
+}
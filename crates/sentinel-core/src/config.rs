@@ -2,11 +2,23 @@
 //!
 //! Configuration is loaded from (in priority order):
 //! 1. Environment variables (SENTINEL_ prefix)
-//! 2. Config file (sentinel.toml)
-//! 3. Defaults
+//! 2. Profile-specific config file (sentinel.<profile>.toml), profile from
+//!    SENTINEL_PROFILE (default "dev")
+//! 3. Base config file (sentinel.toml)
+//! 4. Defaults
+//!
+//! Each layer only needs to specify the keys it changes -- layers are
+//! deep-merged, so a profile file can override a single nested field (e.g.
+//! `neo4j.uri`) without repeating the rest of the base file.
+
+use std::path::Path;
+use std::sync::Arc;
 
 use config::{Config, ConfigError, Environment, File};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::Deserialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
 /// Top-level configuration for a Sentinel service.
 #[derive(Debug, Clone, Deserialize)]
@@ -18,6 +30,13 @@ pub struct SentinelConfig {
     pub redis: RedisConfig,
     pub auth: AuthConfig,
     pub llm: LlmConfig,
+    #[serde(default)]
+    pub otel: OtelConfig,
+    /// The resolved profile this config was loaded for (see
+    /// [`load_from`](Self::load_from)), surfaced here purely so callers can
+    /// log which profile a running service picked up.
+    #[serde(default = "default_profile")]
+    pub profile: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -83,10 +102,46 @@ pub struct AuthConfig {
 pub struct LlmConfig {
     #[serde(default = "default_llm_provider")]
     pub provider: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
     pub api_key: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     pub model: Option<String>,
 }
 
+/// Treats an empty-string override (e.g. an unset `SENTINEL_LLM__API_KEY`
+/// in a shared `.env` template) as clearing the field rather than setting
+/// it to `Some("")`, so a profile layer can positively unset a base value.
+fn empty_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let opt = Option::<String>::deserialize(deserializer)?;
+    Ok(opt.filter(|s| !s.is_empty()))
+}
+
+/// OpenTelemetry export settings, shared by every service's `tracing`
+/// setup (see `sentinel_core::otel::init_tracing`). Disabled by default so
+/// a service with no collector configured doesn't block on export.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OtelConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_otel_endpoint")]
+    pub endpoint: String,
+    #[serde(default = "default_otel_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: default_otel_endpoint(),
+            sample_ratio: default_otel_sample_ratio(),
+        }
+    }
+}
+
 impl SentinelConfig {
     /// Load configuration from file + environment variables.
     ///
@@ -98,18 +153,146 @@ impl SentinelConfig {
     }
 
     /// Load configuration from a named file prefix + environment variables.
+    ///
+    /// Layers, from lowest to highest priority: the base `<file_prefix>.toml`,
+    /// a profile-specific `<file_prefix>.<profile>.toml` (profile from
+    /// `SENTINEL_PROFILE`, default `dev`), then `SENTINEL_` environment
+    /// variables. Each layer deep-merges into the previous, so a profile
+    /// file only needs the keys it changes.
     pub fn load_from(file_prefix: &str) -> Result<Self, ConfigError> {
+        let profile = std::env::var("SENTINEL_PROFILE").unwrap_or_else(|_| default_profile());
+
         let config = Config::builder()
             .add_source(File::with_name(file_prefix).required(false))
+            .add_source(File::with_name(&format!("{file_prefix}.{profile}")).required(false))
             .add_source(
                 Environment::with_prefix("SENTINEL")
                     .separator("__")
                     .try_parsing(true),
             )
+            .set_default("profile", profile)?
             .build()?;
 
         config.try_deserialize()
     }
+
+    /// Validate invariants a config must hold before it's safe to use:
+    /// a non-empty `jwt_secret`, parseable connection URIs, and in-range
+    /// ports. [`watch`](Self::watch) runs this on every reload so a broken
+    /// edit to `sentinel.toml` never replaces a known-good config; callers
+    /// of [`load`](Self::load)/[`load_from`](Self::load_from) that want the
+    /// same guarantee at startup should call it explicitly.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.auth.jwt_secret.trim().is_empty() {
+            return Err(ConfigError::Message(
+                "auth.jwt_secret must not be empty".to_string(),
+            ));
+        }
+        if !self.neo4j.uri.contains("://") {
+            return Err(ConfigError::Message(format!(
+                "neo4j.uri is not a valid URI: {}",
+                self.neo4j.uri
+            )));
+        }
+        if !self.redis.url.contains("://") {
+            return Err(ConfigError::Message(format!(
+                "redis.url is not a valid URI: {}",
+                self.redis.url
+            )));
+        }
+        if self.api.port == 0 {
+            return Err(ConfigError::Message("api.port must be nonzero".to_string()));
+        }
+        if self.postgres.port == 0 {
+            return Err(ConfigError::Message(
+                "postgres.port must be nonzero".to_string(),
+            ));
+        }
+        if self.clickhouse.port == 0 {
+            return Err(ConfigError::Message(
+                "clickhouse.port must be nonzero".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.otel.sample_ratio) {
+            return Err(ConfigError::Message(format!(
+                "otel.sample_ratio must be between 0.0 and 1.0: {}",
+                self.otel.sample_ratio
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Watch `file_prefix` (see [`load_from`](Self::load_from)) for changes
+    /// and publish each validated reload through the returned watch
+    /// channel. Reload is transactional: the new config is parsed and
+    /// [`validate`](Self::validate)d in full before it replaces the
+    /// previous `Arc`, so a bad edit to the config file logs a warning and
+    /// leaves subscribers on the last-known-good config rather than
+    /// crashing the service or publishing something broken.
+    ///
+    /// Not every section is actually safe to pick up live: `llm` and
+    /// `api.log_level` are hot-swappable, but `neo4j`/`postgres` connection
+    /// parameters need their connection pools rebuilt to take effect, so a
+    /// consumer that only cares about the former should diff just those
+    /// fields on each reload rather than assume every change applies
+    /// immediately.
+    pub fn watch(
+        file_prefix: &str,
+    ) -> Result<(watch::Receiver<Arc<SentinelConfig>>, JoinHandle<()>), ConfigError> {
+        let initial = Self::load_from(file_prefix)?;
+        initial.validate()?;
+
+        let (tx, rx) = watch::channel(Arc::new(initial));
+        let file_prefix = file_prefix.to_string();
+
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher: RecommendedWatcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let _ = event_tx.send(res);
+            })
+            .map_err(|e| ConfigError::Message(format!("failed to start config watcher: {e}")))?;
+
+        // Watch the containing directory, not the file itself -- many
+        // editors save by writing a new file and renaming it over the old
+        // one, which a file-level watch can miss.
+        let watch_dir = Path::new(&file_prefix)
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| Path::new(".").to_path_buf());
+        watcher
+            .watch(&watch_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| ConfigError::Message(format!("failed to watch {watch_dir:?}: {e}")))?;
+
+        let handle = tokio::spawn(async move {
+            let _watcher = watcher; // keep alive for the task's lifetime
+            while let Some(event) = event_rx.recv().await {
+                if let Err(e) = &event {
+                    tracing::warn!(error = %e, "Configuration file watcher error");
+                    continue;
+                }
+
+                match Self::load_from(&file_prefix).and_then(|cfg| {
+                    cfg.validate()?;
+                    Ok(cfg)
+                }) {
+                    Ok(new_config) => {
+                        tracing::info!(file_prefix = %file_prefix, "Configuration reloaded");
+                        let _ = tx.send(Arc::new(new_config));
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            "Configuration reload failed, keeping previous config"
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok((rx, handle))
+    }
 }
 
 impl PostgresConfig {
@@ -172,3 +355,128 @@ fn default_jwt_algorithm() -> String {
 fn default_llm_provider() -> String {
     "anthropic".to_string()
 }
+fn default_otel_endpoint() -> String {
+    "http://localhost:4317".to_string()
+}
+fn default_otel_sample_ratio() -> f64 {
+    1.0
+}
+fn default_profile() -> String {
+    "dev".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> SentinelConfig {
+        SentinelConfig {
+            api: ApiConfig {
+                host: default_api_host(),
+                port: default_api_port(),
+                log_level: default_log_level(),
+            },
+            neo4j: Neo4jConfig {
+                uri: default_neo4j_uri(),
+                user: default_neo4j_user(),
+                password: "password".to_string(),
+            },
+            postgres: PostgresConfig {
+                host: default_pg_host(),
+                port: default_pg_port(),
+                db: default_pg_db(),
+                user: default_pg_user(),
+                password: "password".to_string(),
+            },
+            clickhouse: ClickhouseConfig {
+                host: default_ch_host(),
+                port: default_ch_port(),
+                db: default_ch_db(),
+                user: default_ch_user(),
+                password: String::new(),
+            },
+            redis: RedisConfig {
+                url: default_redis_url(),
+            },
+            auth: AuthConfig {
+                jwt_secret: "a-real-secret".to_string(),
+                jwt_algorithm: default_jwt_algorithm(),
+            },
+            llm: LlmConfig {
+                provider: default_llm_provider(),
+                api_key: None,
+                model: None,
+            },
+            otel: OtelConfig::default(),
+            profile: default_profile(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_well_formed_config() {
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_empty_jwt_secret() {
+        let mut config = valid_config();
+        config.auth.jwt_secret = "   ".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_schemeless_neo4j_uri() {
+        let mut config = valid_config();
+        config.neo4j.uri = "localhost:7687".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_schemeless_redis_url() {
+        let mut config = valid_config();
+        config.redis.url = "localhost:6379".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_zero_ports() {
+        let mut config = valid_config();
+        config.api.port = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = valid_config();
+        config.postgres.port = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = valid_config();
+        config.clickhouse.port = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_sample_ratio() {
+        let mut config = valid_config();
+        config.otel.sample_ratio = 1.5;
+        assert!(config.validate().is_err());
+
+        let mut config = valid_config();
+        config.otel.sample_ratio = -0.1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn empty_string_override_clears_optional_field() {
+        let json = serde_json::json!({ "provider": "anthropic", "api_key": "", "model": "" });
+        let llm: LlmConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(llm.api_key, None);
+        assert_eq!(llm.model, None);
+    }
+
+    #[test]
+    fn non_empty_override_is_preserved() {
+        let json = serde_json::json!({ "provider": "anthropic", "api_key": "sk-real", "model": "claude" });
+        let llm: LlmConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(llm.api_key, Some("sk-real".to_string()));
+        assert_eq!(llm.model, Some("claude".to_string()));
+    }
+}
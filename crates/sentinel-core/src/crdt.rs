@@ -0,0 +1,203 @@
+//! Conflict resolution for mutable fields written by concurrent sources.
+//!
+//! Nodes discovered by more than one concurrent writer (e.g. overlapping
+//! scanner shards) need a deterministic merge rule rather than "last
+//! write wins by arrival order," which silently drops whichever update
+//! loses the race to commit. Each mutable field group instead carries a
+//! [`FieldVersion`]: a `(wallclock_ms, writer_id)` pair. The later
+//! wallclock wins; ties are broken by comparing `writer_id` so every
+//! writer resolves the conflict to the same outcome. Resolution is
+//! commutative and idempotent: replaying the same `(value, version)`
+//! pair never moves the winner, since `dominates` is reflexive.
+
+use serde::{Deserialize, Serialize};
+
+/// The version attached to one mutable field group of a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldVersion {
+    pub wallclock_ms: i64,
+    pub writer_id: String,
+}
+
+impl FieldVersion {
+    pub fn new(wallclock_ms: i64, writer_id: impl Into<String>) -> Self {
+        Self {
+            wallclock_ms,
+            writer_id: writer_id.into(),
+        }
+    }
+
+    /// The version a field group starts at before it's ever been written
+    /// through the CRDT path (e.g. a node created by a migration or a
+    /// caller that doesn't carry version information). Any real version
+    /// dominates it.
+    pub fn genesis() -> Self {
+        Self {
+            wallclock_ms: i64::MIN,
+            writer_id: String::new(),
+        }
+    }
+
+    /// True if `self` should win a conflict against `other`.
+    pub fn dominates(&self, other: &Self) -> bool {
+        (self.wallclock_ms, &self.writer_id) >= (other.wallclock_ms, &other.writer_id)
+    }
+
+    /// Read a field group's version back out of stored node properties
+    /// (e.g. a `NodeRecord::properties` read from Neo4j), falling back to
+    /// [`FieldVersion::genesis`] for nodes written before this field
+    /// group carried version metadata.
+    pub fn from_stored(properties: &serde_json::Value, field: &str) -> Self {
+        let wallclock_ms = properties
+            .get(format!("{field}_version_ms"))
+            .and_then(|v| v.as_i64());
+        let writer_id = properties
+            .get(format!("{field}_version_writer"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        match (wallclock_ms, writer_id) {
+            (Some(wallclock_ms), Some(writer_id)) => Self {
+                wallclock_ms,
+                writer_id,
+            },
+            _ => Self::genesis(),
+        }
+    }
+}
+
+/// Resolve one versioned field group: compare `incoming` against whatever
+/// is currently `stored` (`None` if the node didn't previously exist),
+/// and return whichever value/version wins along with the writer_id that
+/// won it, for reporting back to callers.
+pub fn resolve_field<T>(
+    incoming: T,
+    incoming_version: &FieldVersion,
+    stored: Option<(T, FieldVersion)>,
+) -> (T, FieldVersion, String) {
+    match stored {
+        Some((stored_value, stored_version)) if !incoming_version.dominates(&stored_version) => {
+            let writer_id = stored_version.writer_id.clone();
+            (stored_value, stored_version, writer_id)
+        }
+        _ => (
+            incoming,
+            incoming_version.clone(),
+            incoming_version.writer_id.clone(),
+        ),
+    }
+}
+
+/// Per-field-group CRDT versions for a [`crate::types::Host`]'s mutable
+/// attributes. Each group resolves independently so, for example, a fresher
+/// `hostname` write doesn't get clobbered by a stale `os` write that
+/// happens to persist later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostFieldVersions {
+    pub hostname: FieldVersion,
+    pub os: FieldVersion,
+    pub mac_address: FieldVersion,
+    pub tags: FieldVersion,
+}
+
+impl HostFieldVersions {
+    /// Stamp every field group with the same version — the state of a
+    /// freshly observed value, before resolving against any existing
+    /// stored state.
+    pub fn stamped(version: FieldVersion) -> Self {
+        Self {
+            hostname: version.clone(),
+            os: version.clone(),
+            mac_address: version.clone(),
+            tags: version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_wallclock_dominates() {
+        let a = FieldVersion::new(100, "writer-a");
+        let b = FieldVersion::new(200, "writer-b");
+        assert!(b.dominates(&a));
+        assert!(!a.dominates(&b));
+    }
+
+    #[test]
+    fn tie_breaks_on_writer_id() {
+        let a = FieldVersion::new(100, "writer-a");
+        let b = FieldVersion::new(100, "writer-b");
+        assert!(b.dominates(&a));
+        assert!(!a.dominates(&b));
+    }
+
+    #[test]
+    fn dominates_is_reflexive_for_idempotent_replay() {
+        let v = FieldVersion::new(100, "writer-a");
+        assert!(v.dominates(&v));
+    }
+
+    #[test]
+    fn genesis_is_dominated_by_any_real_version() {
+        let v = FieldVersion::new(0, "writer-a");
+        assert!(v.dominates(&FieldVersion::genesis()));
+    }
+
+    #[test]
+    fn resolve_field_prefers_dominant_incoming() {
+        let stored = (1u16, FieldVersion::new(100, "writer-a"));
+        let incoming_version = FieldVersion::new(200, "writer-b");
+        let (value, version, winner) = resolve_field(2u16, &incoming_version, Some(stored));
+        assert_eq!(value, 2);
+        assert_eq!(version, incoming_version);
+        assert_eq!(winner, "writer-b");
+    }
+
+    #[test]
+    fn resolve_field_keeps_stored_when_incoming_is_stale() {
+        let stored_version = FieldVersion::new(200, "writer-a");
+        let stored = (1u16, stored_version.clone());
+        let incoming_version = FieldVersion::new(100, "writer-b");
+        let (value, version, winner) = resolve_field(2u16, &incoming_version, Some(stored));
+        assert_eq!(value, 1);
+        assert_eq!(version, stored_version);
+        assert_eq!(winner, "writer-a");
+    }
+
+    #[test]
+    fn resolve_field_with_no_stored_value_takes_incoming() {
+        let incoming_version = FieldVersion::new(100, "writer-a");
+        let (value, version, winner) = resolve_field(2u16, &incoming_version, None);
+        assert_eq!(value, 2);
+        assert_eq!(version, incoming_version);
+        assert_eq!(winner, "writer-a");
+    }
+
+    #[test]
+    fn from_stored_reads_back_a_persisted_version() {
+        let stored = serde_json::json!({
+            "hostname_version_ms": 1700000000000i64,
+            "hostname_version_writer": "writer-a",
+        });
+        let version = FieldVersion::from_stored(&stored, "hostname");
+        assert_eq!(version, FieldVersion::new(1700000000000, "writer-a"));
+    }
+
+    #[test]
+    fn from_stored_falls_back_to_genesis_when_absent() {
+        let stored = serde_json::json!({ "hostname": "legacy-host" });
+        assert_eq!(FieldVersion::from_stored(&stored, "hostname"), FieldVersion::genesis());
+    }
+
+    #[test]
+    fn replaying_identical_version_is_idempotent() {
+        let version = FieldVersion::new(100, "writer-a");
+        let stored = (5u16, version.clone());
+        let (value, resolved_version, _) = resolve_field(5u16, &version, Some(stored));
+        assert_eq!(value, 5);
+        assert_eq!(resolved_version, version);
+    }
+}
@@ -28,6 +28,9 @@ pub enum SentinelError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("Certificate parse error: {0}")]
+    Certificate(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
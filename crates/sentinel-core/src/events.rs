@@ -33,6 +33,13 @@ pub struct SentinelEvent {
     pub timestamp: DateTime<Utc>,
     pub source: EventSource,
     pub payload: EventPayload,
+    /// Monotonically increasing, per-tenant sequence number assigned by
+    /// the publishing node. Lets consumers detect gaps in delivery order
+    /// under at-least-once or eventually-consistent transports (see
+    /// `crate::transport::GossipTransport`). Zero for events constructed
+    /// directly via [`SentinelEvent::new`] and never re-stamped.
+    #[serde(default)]
+    pub seq: u64,
 }
 
 impl SentinelEvent {
@@ -43,8 +50,16 @@ impl SentinelEvent {
             timestamp: Utc::now(),
             source,
             payload,
+            seq: 0,
         }
     }
+
+    /// Attach a sequence number, e.g. one assigned by a transport at
+    /// publish time.
+    pub fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = seq;
+        self
+    }
 }
 
 /// Which service emitted the event.
@@ -94,6 +109,14 @@ pub enum EventPayload {
         cvss_score: Option<f64>,
         exploitable: bool,
     },
+    /// A service was confirmed reachable from outside its NAT via a
+    /// reflexive-address (STUN) probe.
+    ExternalExposureDetected {
+        node_id: NodeId,
+        external_ip: String,
+        external_port: u16,
+        service_port: u16,
+    },
 
     // ── Scan lifecycle events ─────────────────────────────────
     /// A scan operation started.
@@ -151,6 +174,17 @@ pub enum EventPayload {
         intent: String,
         action_count: u32,
     },
+
+    // ── Coordination events ────────────────────────────────────
+    /// A `sentinel-discover` worker's periodic liveness gossip, used by
+    /// peers to maintain their node table for rendezvous-hashed scan
+    /// bucket ownership (see `sentinel_discover::coordination`).
+    ScannerHeartbeat {
+        worker_id: String,
+        assigned_buckets: Vec<String>,
+        capacity: u32,
+        wallclock: DateTime<Utc>,
+    },
 }
 
 #[cfg(test)]
@@ -7,9 +7,13 @@
 //! - Configuration management
 //! - Common error types
 
+pub mod bloom;
 pub mod config;
+pub mod crdt;
 pub mod error;
 pub mod events;
+pub mod otel;
+pub mod transport;
 pub mod types;
 
 pub use error::SentinelError;
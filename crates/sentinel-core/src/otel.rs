@@ -0,0 +1,51 @@
+//! Tracing/telemetry bootstrap shared by every service binary.
+//!
+//! Every service should call [`init_tracing`] once at startup instead of
+//! building its own `tracing_subscriber` registry, so that turning on
+//! `otel.enabled` in `sentinel.toml` is enough to bridge that service's
+//! spans to an OTLP collector without touching its `main.rs`.
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::trace::Sampler;
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::OtelConfig;
+
+/// Install the global `tracing` subscriber for `service_name`.
+///
+/// Always installs an `EnvFilter` + JSON `fmt` layer (the prior behavior of
+/// every `main.rs`). When `config.enabled`, additionally installs a
+/// `tracing-opentelemetry` layer that batches spans to `config.endpoint`
+/// over OTLP, sampled at `config.sample_ratio`. Call this at most once per
+/// process; a second call will panic, same as calling `fmt().init()` twice.
+pub fn init_tracing(service_name: &str, config: &OtelConfig) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let fmt_layer = tracing_subscriber::fmt::layer().json();
+    let registry = tracing_subscriber::registry().with(filter).with(fmt_layer);
+
+    if !config.enabled {
+        registry.init();
+        return Ok(());
+    }
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.endpoint),
+        )
+        .with_trace_config(
+            opentelemetry_sdk::trace::config()
+                .with_sampler(Sampler::TraceIdRatioBased(config.sample_ratio))
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    service_name.to_string(),
+                )])),
+        )
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+    registry.with(tracing_opentelemetry::layer().with_tracer(tracer)).init();
+    Ok(())
+}
@@ -0,0 +1,305 @@
+//! Peer-to-peer event dissemination, as an alternative to a central broker.
+//!
+//! [`EventTransport`] is the publish/subscribe surface every transport
+//! implements, whether broker-backed (Redis Streams now, Kafka later --
+//! see `crate::events`) or broker-less. [`GossipTransport`] is the
+//! broker-less implementation: every node keeps a versioned store of
+//! events it has seen, and reconciles with peers via classic push + pull
+//! anti-entropy -- each round it pushes its most recent events to a few
+//! peers, and answers peers' pull requests (a Bloom filter of the
+//! `EventId`s they already hold, see `crate::bloom`) with whatever's
+//! missing from that filter.
+//!
+//! This module implements the gossip bookkeeping only -- round-robin peer
+//! selection, digest construction, last-version-wins merge -- not the
+//! socket/framing code that would carry those messages between nodes,
+//! matching the scope of `sentinel_discover::coordination`'s heartbeat
+//! gossip (decoded payloads in, decisions out; no transport of its own).
+
+use std::collections::HashMap;
+
+use crate::bloom::BloomFilter;
+use crate::events::{EventId, SentinelEvent};
+use crate::types::TenantId;
+
+/// Target false-positive rate for a [`GossipTransport`]'s pull digests.
+const GOSSIP_DIGEST_FPR: f64 = 0.01;
+
+/// The publish/subscribe surface every event transport implements,
+/// regardless of whether it's broker-backed or broker-less.
+pub trait EventTransport {
+    /// Publish a locally-produced event for delivery to other services.
+    fn publish(&mut self, event: SentinelEvent);
+
+    /// Drain events that have arrived since the last call and haven't yet
+    /// been delivered to this node's subscribers.
+    fn poll(&mut self) -> Vec<SentinelEvent>;
+}
+
+/// A round's push payload: which peers to send to, and what to send them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GossipPush {
+    pub targets: Vec<String>,
+    pub events: Vec<SentinelEvent>,
+}
+
+/// Broker-less, eventually-consistent event dissemination via push + pull
+/// anti-entropy gossip.
+///
+/// Bounds bandwidth per round via `fanout` (how many peers to push to) and
+/// `max_round_events` (how many events to push or answer a pull with).
+pub struct GossipTransport {
+    node_id: String,
+    fanout: usize,
+    max_round_events: usize,
+    store: HashMap<EventId, SentinelEvent>,
+    undelivered: Vec<EventId>,
+    next_seq: HashMap<TenantId, u64>,
+}
+
+impl GossipTransport {
+    pub fn new(node_id: impl Into<String>, fanout: usize, max_round_events: usize) -> Self {
+        Self {
+            node_id: node_id.into(),
+            fanout,
+            max_round_events,
+            store: HashMap::new(),
+            undelivered: Vec::new(),
+            next_seq: HashMap::new(),
+        }
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Number of distinct events currently held, regardless of delivery state.
+    pub fn held_count(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Whether this node already holds `id`.
+    pub fn holds(&self, id: &EventId) -> bool {
+        self.store.contains_key(id)
+    }
+
+    /// Build this round's push: up to `fanout` of `peers`, selected
+    /// deterministically from `node_id` and `round` so repeated calls with
+    /// the same inputs are reproducible, paired with this node's most
+    /// recent `max_round_events` events.
+    pub fn push_round(&self, peers: &[String], round: u64) -> GossipPush {
+        let targets = select_peers(peers, self.fanout, round, &self.node_id);
+
+        let mut events: Vec<SentinelEvent> = self.store.values().cloned().collect();
+        events.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        events.truncate(self.max_round_events);
+
+        GossipPush { targets, events }
+    }
+
+    /// Build this node's pull request: a Bloom filter of every `EventId` it
+    /// already holds. A peer answers it with [`answer_pull`](Self::answer_pull).
+    pub fn pull_digest(&self) -> BloomFilter {
+        let mut filter = BloomFilter::with_fpr(self.store.len().max(1), GOSSIP_DIGEST_FPR);
+        for id in self.store.keys() {
+            filter.insert(&id.0.to_string());
+        }
+        filter
+    }
+
+    /// Answer a peer's pull request: up to `max_round_events` held events
+    /// whose ID isn't in their digest, most recent first.
+    pub fn answer_pull(&self, their_digest: &BloomFilter) -> Vec<SentinelEvent> {
+        let mut missing: Vec<SentinelEvent> = self
+            .store
+            .values()
+            .filter(|e| !their_digest.contains(&e.id.0.to_string()))
+            .cloned()
+            .collect();
+        missing.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        missing.truncate(self.max_round_events);
+        missing
+    }
+
+    /// Merge an event -- ours or a peer's -- into the store.
+    /// Last-version-wins on `EventId` collision, where "later" means a
+    /// strictly higher per-tenant `seq`; a duplicate or stale re-push is
+    /// dropped rather than overwriting the held copy.
+    pub fn ingest(&mut self, event: SentinelEvent) {
+        let id = event.id.clone();
+        let is_newer = match self.store.get(&id) {
+            Some(existing) => event.seq > existing.seq,
+            None => true,
+        };
+        if is_newer {
+            self.store.insert(id.clone(), event);
+            self.undelivered.push(id);
+        }
+    }
+}
+
+impl EventTransport for GossipTransport {
+    /// Assigns the next per-tenant sequence number before ingesting, since
+    /// this node is the event's origin. Events arriving from peers go
+    /// through [`GossipTransport::ingest`] directly and keep whichever
+    /// sequence number their origin assigned.
+    fn publish(&mut self, event: SentinelEvent) {
+        let seq = self.next_seq.entry(event.tenant_id.clone()).or_insert(0);
+        *seq += 1;
+        self.ingest(event.with_seq(*seq));
+    }
+
+    fn poll(&mut self) -> Vec<SentinelEvent> {
+        std::mem::take(&mut self.undelivered)
+            .into_iter()
+            .filter_map(|id| self.store.get(&id).cloned())
+            .collect()
+    }
+}
+
+/// Deterministically pick up to `fanout` of `peers` for `round`, using a
+/// rendezvous-style hash seeded by `node_id` so repeated calls with the
+/// same inputs are reproducible while selection still varies round to
+/// round. Mirrors `sentinel_discover::coordination`'s ownership hashing.
+fn select_peers(peers: &[String], fanout: usize, round: u64, node_id: &str) -> Vec<String> {
+    if peers.len() <= fanout {
+        return peers.to_vec();
+    }
+
+    let mut scored: Vec<(u64, &String)> = peers
+        .iter()
+        .map(|p| (round_weight(node_id, p, round), p))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored
+        .into_iter()
+        .take(fanout)
+        .map(|(_, p)| p.clone())
+        .collect()
+}
+
+fn round_weight(node_id: &str, peer: &str, round: u64) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    node_id.hash(&mut hasher);
+    peer.hash(&mut hasher);
+    round.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventPayload, EventSource};
+    use uuid::Uuid;
+
+    fn sample_event(tenant_id: TenantId) -> SentinelEvent {
+        SentinelEvent::new(
+            tenant_id,
+            EventSource::Discover,
+            EventPayload::NodeDiscovered {
+                node_id: crate::types::NodeId::new(),
+                node_type: "Host".to_string(),
+                label: "test-host".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn publish_assigns_increasing_per_tenant_seq() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut transport = GossipTransport::new("node-a", 3, 10);
+
+        transport.publish(sample_event(tenant.clone()));
+        transport.publish(sample_event(tenant.clone()));
+
+        let delivered = transport.poll();
+        let mut seqs: Vec<u64> = delivered.iter().map(|e| e.seq).collect();
+        seqs.sort_unstable();
+        assert_eq!(seqs, vec![1, 2]);
+    }
+
+    #[test]
+    fn poll_drains_and_does_not_redeliver() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut transport = GossipTransport::new("node-a", 3, 10);
+        transport.publish(sample_event(tenant));
+
+        assert_eq!(transport.poll().len(), 1);
+        assert!(transport.poll().is_empty());
+    }
+
+    #[test]
+    fn ingest_keeps_higher_seq_on_collision() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut transport = GossipTransport::new("node-a", 3, 10);
+
+        let base = sample_event(tenant);
+        let stale = base.clone().with_seq(1);
+        let fresh = base.clone().with_seq(5);
+
+        transport.ingest(fresh.clone());
+        transport.ingest(stale);
+
+        assert_eq!(transport.poll().into_iter().next().unwrap().seq, 5);
+        assert_eq!(transport.held_count(), 1);
+    }
+
+    #[test]
+    fn holds_reports_membership() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut transport = GossipTransport::new("node-a", 3, 10);
+        let event = sample_event(tenant);
+        let id = event.id.clone();
+
+        assert!(!transport.holds(&id));
+        transport.ingest(event);
+        assert!(transport.holds(&id));
+    }
+
+    #[test]
+    fn pull_digest_reconciles_missing_events() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut node_a = GossipTransport::new("node-a", 3, 10);
+        let mut node_b = GossipTransport::new("node-b", 3, 10);
+
+        let shared = sample_event(tenant.clone()).with_seq(1);
+        node_a.ingest(shared.clone());
+        node_b.ingest(shared);
+
+        let only_on_a = sample_event(tenant).with_seq(2);
+        node_a.ingest(only_on_a.clone());
+
+        let b_digest = node_b.pull_digest();
+        let missing = node_a.answer_pull(&b_digest);
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].id, only_on_a.id);
+    }
+
+    #[test]
+    fn push_round_caps_fanout_and_event_count() {
+        let tenant = TenantId(Uuid::new_v4());
+        let mut transport = GossipTransport::new("node-a", 2, 1);
+        transport.ingest(sample_event(tenant.clone()).with_seq(1));
+        transport.ingest(sample_event(tenant).with_seq(2));
+
+        let peers: Vec<String> = (0..5).map(|i| format!("peer-{i}")).collect();
+        let push = transport.push_round(&peers, 0);
+
+        assert_eq!(push.targets.len(), 2);
+        assert_eq!(push.events.len(), 1);
+    }
+
+    #[test]
+    fn push_round_is_deterministic_for_same_round() {
+        let transport = GossipTransport::new("node-a", 2, 10);
+        let peers: Vec<String> = (0..5).map(|i| format!("peer-{i}")).collect();
+
+        let first = transport.push_round(&peers, 7);
+        let second = transport.push_round(&peers, 7);
+        assert_eq!(first.targets, second.targets);
+    }
+}
@@ -3,10 +3,13 @@
 //! These types represent nodes and edges in the network digital twin,
 //! shared across all Sentinel services (Rust, Python, TypeScript).
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::Digest;
 use uuid::Uuid;
 
+use crate::error::SentinelError;
+
 // ── Tenant ────────────────────────────────────────────────────────
 
 /// Every entity in the system belongs to a tenant.
@@ -206,6 +209,59 @@ pub struct Certificate {
     pub last_seen: DateTime<Utc>,
 }
 
+impl Certificate {
+    /// Build a [`Certificate`] node from a raw DER-encoded X.509 certificate.
+    ///
+    /// Subject and issuer are rendered as RFC 4514 distinguished-name
+    /// strings, the serial number as lowercase hex, and the fingerprint as
+    /// the SHA-256 digest of `der` itself. `x509-parser` normalizes both
+    /// UTCTime (2-digit year) and GeneralizedTime (4-digit year) validity
+    /// timestamps before we ever see them, so `not_before`/`not_after`
+    /// don't need to special-case either form here.
+    pub fn from_der(tenant_id: TenantId, der: &[u8]) -> Result<Self, SentinelError> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der)
+            .map_err(|e| SentinelError::Certificate(format!("malformed DER: {e}")))?;
+
+        let validity = cert.validity();
+        let not_before = asn1_time_to_utc(validity.not_before)?;
+        let not_after = asn1_time_to_utc(validity.not_after)?;
+
+        let now = Utc::now();
+        Ok(Self {
+            id: NodeId::new(),
+            tenant_id,
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            serial_number: cert.raw_serial_as_string(),
+            not_before,
+            not_after,
+            fingerprint_sha256: format!("{:x}", sha2::Sha256::digest(der)),
+            first_seen: now,
+            last_seen: now,
+        })
+    }
+
+    /// Build a [`Certificate`] node from a PEM-encoded X.509 certificate,
+    /// decoding the PEM envelope and delegating to [`Certificate::from_der`].
+    pub fn from_pem(tenant_id: TenantId, pem: &[u8]) -> Result<Self, SentinelError> {
+        let (_, pem) = x509_parser::pem::parse_x509_pem(pem)
+            .map_err(|e| SentinelError::Certificate(format!("malformed PEM: {e}")))?;
+        Self::from_der(tenant_id, &pem.contents)
+    }
+}
+
+/// Convert an X.509 validity timestamp to a UTC `DateTime`.
+fn asn1_time_to_utc(t: x509_parser::time::ASN1Time) -> Result<DateTime<Utc>, SentinelError> {
+    Utc.timestamp_opt(t.timestamp(), 0)
+        .single()
+        .ok_or_else(|| {
+            SentinelError::Certificate(format!(
+                "certificate validity timestamp out of range: {}",
+                t.timestamp()
+            ))
+        })
+}
+
 /// An application or container image.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Application {
@@ -338,6 +394,10 @@ pub enum EdgeType {
     HasCertificate,
     BelongsToSubnet,
     BelongsToVpc,
+    /// A `Service` was confirmed reachable from outside its NAT via a
+    /// reflexive-address (STUN-style) probe. See `sentinel-discover`'s
+    /// `exposure` module.
+    ExposedExternally,
 }
 
 /// Properties attached to an edge.
@@ -348,6 +408,12 @@ pub struct EdgeProperties {
     pub encrypted: Option<bool>,
     pub permissions: Vec<String>,
     pub exploitability_score: Option<f64>,
+    /// Public IP a service was confirmed reachable at via a reflexive
+    /// address probe. Set on `ExposedExternally` edges.
+    pub external_ip: Option<String>,
+    /// Public port a service was confirmed reachable at, paired with
+    /// `external_ip`. Set on `ExposedExternally` edges.
+    pub external_port: Option<u16>,
     pub extra: serde_json::Value,
 }
 
@@ -0,0 +1,26 @@
+//! Fuzz target: `parse_nmap_xml` fed arbitrary byte streams must either
+//! return a clean error or a well-formed `NmapRun`, never panic, and
+//! never allocate unbounded memory chasing a malformed document.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sentinel_discover::nmap_xml::parse_nmap_xml;
+
+fuzz_target!(|data: &[u8]| {
+    // A real nmap process can't be tricked into emitting gigabytes of XML
+    // for a handful of input bytes; cap what we even try to parse so a
+    // pathological quadratic-blowup document doesn't turn into an OOM
+    // finding that says more about the fuzzer than the parser.
+    if data.len() > 1_000_000 {
+        return;
+    }
+
+    if let Ok(run) = parse_nmap_xml(data) {
+        for host in &run.hosts {
+            let _ = host.is_up();
+            let _ = host.ipv4();
+            let _ = host.mac();
+        }
+    }
+});
@@ -0,0 +1,237 @@
+//! Bridge from parsed nmap output directly to `NodeRecord`/`EdgeRecord`
+//! collections, bypassing Neo4j entirely so a single scan can feed
+//! `InMemoryGraph::from_subgraph` for ad-hoc or offline pathfinding.
+
+use sentinel_core::types::TenantId;
+use sentinel_graph::queries::{EdgeRecord, NodeRecord};
+use uuid::Uuid;
+
+use crate::diff::SENTINEL_NS;
+use crate::nmap_xml::{NmapRun, NmapScript};
+
+/// Convert a parsed nmap run into graph records. Each up host becomes a
+/// `Host` node; each open port with a detected service becomes a
+/// `Service` node linked by a `RUNS_ON` edge. Vulnerability-detection
+/// scripts (`vulners`, `ssl-*`) populate the edge's
+/// `exploitability_score`; when none fired, the property is left unset
+/// so `extract_exploitability`'s own 0.5 default applies downstream.
+pub fn nmap_run_to_records(nmap_run: &NmapRun, tenant_id: &TenantId) -> (Vec<NodeRecord>, Vec<EdgeRecord>) {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for host in nmap_run.hosts.iter().filter(|h| h.is_up()) {
+        let Some(ip) = host.ipv4() else { continue };
+
+        let host_id = Uuid::new_v5(&SENTINEL_NS, format!("{}:host:{}", tenant_id.0, ip).as_bytes()).to_string();
+
+        nodes.push(NodeRecord {
+            id: host_id.clone(),
+            label: "Host".to_string(),
+            tenant_id: tenant_id.0.to_string(),
+            properties: serde_json::json!({
+                "ip": ip,
+                "hostname": host.hostname(),
+                "os": host.os_name(),
+                "mac_address": host.mac(),
+            }),
+        });
+
+        let Some(ports) = &host.ports else { continue };
+
+        for port in ports.ports.iter().filter(|p| p.state.state == "open") {
+            let Some(svc) = &port.service else { continue };
+
+            let svc_id = Uuid::new_v5(
+                &SENTINEL_NS,
+                format!("{}:service:{}:{}:{}", tenant_id.0, ip, port.port_id, svc.name).as_bytes(),
+            )
+            .to_string();
+
+            nodes.push(NodeRecord {
+                id: svc_id.clone(),
+                label: "Service".to_string(),
+                tenant_id: tenant_id.0.to_string(),
+                properties: serde_json::json!({
+                    "name": svc.name,
+                    "product": svc.product,
+                    "version": svc.version,
+                    "port": port.port_id,
+                    "protocol": port.protocol,
+                    "cpes": svc.cpes,
+                }),
+            });
+
+            edges.push(EdgeRecord {
+                id: Uuid::new_v5(
+                    &SENTINEL_NS,
+                    format!("{}:edge:runs_on:{}:{}", tenant_id.0, ip, port.port_id).as_bytes(),
+                )
+                .to_string(),
+                edge_type: "RUNS_ON".to_string(),
+                source_id: host_id.clone(),
+                target_id: svc_id,
+                properties: serde_json::json!({
+                    "exploitability_score": exploitability_from_scripts(&port.scripts),
+                }),
+            });
+        }
+    }
+
+    (nodes, edges)
+}
+
+/// Derive an edge's `exploitability_score` from NSE vulnerability-detection
+/// script output, taking the highest-weighted signal found. Returns `None`
+/// when no recognized script fired.
+fn exploitability_from_scripts(scripts: &[NmapScript]) -> Option<f64> {
+    scripts
+        .iter()
+        .filter_map(|script| match script.id.as_str() {
+            "vulners" => Some(0.9),
+            id if id.starts_with("ssl-") && is_vulnerable_ssl_output(&script.output) => Some(0.8),
+            _ => None,
+        })
+        .fold(None, |acc: Option<f64>, weight| {
+            Some(acc.map_or(weight, |a| a.max(weight)))
+        })
+}
+
+/// Whether `ssl-*` script output indicates a finding worth scoring, as
+/// opposed to a clean/informational report.
+fn is_vulnerable_ssl_output(output: &str) -> bool {
+    let lower = output.to_lowercase();
+    lower.contains("vulnerable") || lower.contains("weak") || lower.contains("expired")
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::nmap_xml::parse_nmap_xml;
+
+    fn test_tenant() -> TenantId {
+        TenantId(Uuid::nil())
+    }
+
+    #[test]
+    fn test_nmap_run_to_records_basic() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up" reason="syn-ack"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <ports>
+      <port protocol="tcp" portid="80">
+        <state state="open" reason="syn-ack"/>
+        <service name="http" product="nginx" version="1.24"/>
+      </port>
+      <port protocol="tcp" portid="3306">
+        <state state="filtered" reason="no-response"/>
+      </port>
+    </ports>
+  </host>
+  <host>
+    <status state="down" reason="no-response"/>
+    <address addr="10.0.1.2" addrtype="ipv4"/>
+  </host>
+</nmaprun>"#;
+
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let tid = test_tenant();
+        let (nodes, edges) = nmap_run_to_records(&run, &tid);
+
+        // 1 up host + 1 service (the filtered port is skipped, the down host is skipped).
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(edges.len(), 1);
+
+        let host_node = nodes.iter().find(|n| n.label == "Host").unwrap();
+        assert_eq!(host_node.properties.get("ip").and_then(|v| v.as_str()), Some("10.0.1.1"));
+
+        let svc_node = nodes.iter().find(|n| n.label == "Service").unwrap();
+        assert_eq!(svc_node.properties.get("name").and_then(|v| v.as_str()), Some("http"));
+
+        let edge = &edges[0];
+        assert_eq!(edge.edge_type, "RUNS_ON");
+        assert_eq!(edge.source_id, host_node.id);
+        assert_eq!(edge.target_id, svc_node.id);
+        assert!(edge.properties.get("exploitability_score").unwrap().is_null());
+    }
+
+    #[test]
+    fn test_vulners_script_sets_exploitability() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <ports>
+      <port protocol="tcp" portid="443">
+        <state state="open"/>
+        <service name="https" product="nginx"/>
+        <script id="vulners" output="CVE-2023-1234 9.8 https://vulners.com/cve/CVE-2023-1234"/>
+        <script id="ssl-cert" output="Subject: commonName=example.com"/>
+      </port>
+    </ports>
+  </host>
+</nmaprun>"#;
+
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let (_, edges) = nmap_run_to_records(&run, &test_tenant());
+
+        let score = edges[0]
+            .properties
+            .get("exploitability_score")
+            .and_then(|v| v.as_f64());
+        assert_eq!(score, Some(0.9));
+    }
+
+    #[test]
+    fn test_vulnerable_ssl_script_sets_exploitability() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <ports>
+      <port protocol="tcp" portid="443">
+        <state state="open"/>
+        <service name="https"/>
+        <script id="ssl-enum-ciphers" output="least strength: weak"/>
+      </port>
+    </ports>
+  </host>
+</nmaprun>"#;
+
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let (_, edges) = nmap_run_to_records(&run, &test_tenant());
+
+        let score = edges[0]
+            .properties
+            .get("exploitability_score")
+            .and_then(|v| v.as_f64());
+        assert_eq!(score, Some(0.8));
+    }
+
+    #[test]
+    fn test_deterministic_record_ids() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+  </host>
+</nmaprun>"#;
+
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let tid = test_tenant();
+        let (n1, _) = nmap_run_to_records(&run, &tid);
+        let (n2, _) = nmap_run_to_records(&run, &tid);
+
+        assert_eq!(n1[0].id, n2[0].id);
+    }
+}
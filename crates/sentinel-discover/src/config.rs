@@ -1,12 +1,13 @@
 //! Configuration for the sentinel-discover network scanner.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Top-level discover configuration.
 ///
 /// Loaded from `sentinel.toml` `[discover]` section or
-/// `SENTINEL_DISCOVER__` environment variables.
-#[derive(Debug, Clone, Deserialize)]
+/// `SENTINEL_DISCOVER__` environment variables. Can also be generated
+/// interactively via `sentinel-discover init` (see [`crate::init`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoverConfig {
     /// Path to the nmap binary (default: "nmap").
     #[serde(default = "default_nmap_path")]
@@ -35,10 +36,57 @@ pub struct DiscoverConfig {
     /// Maximum concurrent nmap processes.
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent_scans: usize,
+
+    /// Identity of this scanner instance, used as the `writer_id` in
+    /// CRDT field versions so concurrent scanners covering overlapping
+    /// CIDRs converge deterministically (see `sentinel_core::crdt`).
+    /// Defaults to a random ID generated at load time; set explicitly
+    /// when running multiple fixed scanner instances so restarts keep a
+    /// stable identity.
+    #[serde(default = "default_writer_id")]
+    pub writer_id: String,
+
+    /// CIDR prefix length scan buckets are partitioned at for distributed
+    /// worker coordination (default: `/24`). See `crate::coordination`.
+    #[serde(default = "default_bucket_prefix_len")]
+    pub bucket_prefix_len: u8,
+
+    /// How long a peer worker's gossiped heartbeat remains valid before
+    /// it's evicted from the node table and its buckets re-home onto the
+    /// remaining live workers.
+    #[serde(default = "default_heartbeat_ttl_secs")]
+    pub heartbeat_ttl_secs: u64,
+
+    /// Relative scan capacity gossiped in this worker's heartbeat. Purely
+    /// informational today (ownership is hash-based, not load-based); kept
+    /// for future capacity-aware rebalancing.
+    #[serde(default = "default_scan_capacity")]
+    pub scan_capacity: u32,
+
+    /// Public STUN reflectors (`host:port`) used to probe discovered
+    /// services for external (NAT-traversing) reachability. Empty by
+    /// default, which disables the probe entirely. See `crate::exposure`.
+    #[serde(default)]
+    pub stun_reflectors: Vec<String>,
+
+    /// How long to wait for a STUN reflector to answer before trying the
+    /// next one.
+    #[serde(default = "default_stun_timeout_ms")]
+    pub stun_timeout_ms: u64,
+
+    /// How often the background graph-consistency sweep runs (see
+    /// `crate::maintenance`). Only fires when a scan has actually marked
+    /// some region dirty since the last sweep.
+    #[serde(default = "default_maintenance_interval_secs")]
+    pub maintenance_interval_secs: u64,
+
+    /// Maximum nodes to fetch for a consistency sweep.
+    #[serde(default = "default_maintenance_node_limit")]
+    pub maintenance_node_limit: u32,
 }
 
 /// A subnet with its scan schedule.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubnetSchedule {
     /// CIDR target (e.g., "10.0.1.0/24").
     pub cidr: String,
@@ -59,7 +107,7 @@ pub struct SubnetSchedule {
 }
 
 /// Predefined scan profiles mapping to nmap flag sets.
-#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ScanProfile {
     /// Ping sweep only: `-sn`
@@ -102,6 +150,34 @@ fn default_interval() -> u64 {
     3600
 }
 
+fn default_writer_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn default_bucket_prefix_len() -> u8 {
+    24
+}
+
+fn default_heartbeat_ttl_secs() -> u64 {
+    90
+}
+
+fn default_scan_capacity() -> u32 {
+    1
+}
+
+fn default_stun_timeout_ms() -> u64 {
+    500
+}
+
+fn default_maintenance_interval_secs() -> u64 {
+    300
+}
+
+fn default_maintenance_node_limit() -> u32 {
+    50_000
+}
+
 fn default_true() -> bool {
     true
 }
@@ -116,6 +192,14 @@ impl Default for DiscoverConfig {
             stale_threshold_hours: default_stale_hours(),
             engram_dir: default_engram_dir(),
             max_concurrent_scans: default_max_concurrent(),
+            writer_id: default_writer_id(),
+            bucket_prefix_len: default_bucket_prefix_len(),
+            heartbeat_ttl_secs: default_heartbeat_ttl_secs(),
+            scan_capacity: default_scan_capacity(),
+            stun_reflectors: Vec::new(),
+            stun_timeout_ms: default_stun_timeout_ms(),
+            maintenance_interval_secs: default_maintenance_interval_secs(),
+            maintenance_node_limit: default_maintenance_node_limit(),
         }
     }
 }
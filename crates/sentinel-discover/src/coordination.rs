@@ -0,0 +1,288 @@
+//! Distributed scan-ownership coordination via gossiped heartbeats and
+//! rendezvous (HRW) hashing.
+//!
+//! Multiple `sentinel-discover` workers can cover overlapping CIDRs without
+//! double-scanning by partitioning each target CIDR into fixed-size buckets
+//! (by default `/24`s) and deterministically hashing each bucket onto the
+//! set of currently-live workers: whichever worker scores highest for
+//! `(worker_id, bucket)` owns it. Liveness is tracked in an in-memory node
+//! table, refreshed by periodic [`EventPayload::ScannerHeartbeat`] gossip;
+//! a worker that stops heartbeating is evicted once its last heartbeat
+//! exceeds `heartbeat_ttl`, and its buckets re-home onto the remaining live
+//! set automatically on the next ownership check — there's no separate
+//! rebalancing step, ownership is recomputed fresh every time.
+//!
+//! Membership can briefly disagree during churn (a newly-evicted worker may
+//! still be mid-scan, or two workers may not yet agree on the live set), so
+//! ownership here is advisory rather than a hard lock: [`diff::compute_diff`]
+//! just skips hosts outside a worker's owned buckets rather than relying on
+//! ownership being globally consistent at every instant. A duplicate upsert
+//! from an overlapping scan is harmless because [`sentinel_core::crdt`]'s
+//! last-write-wins field resolution makes replaying the same or an older
+//! value a no-op.
+//!
+//! [`diff::compute_diff`]: crate::diff::compute_diff
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+
+use chrono::{DateTime, Duration, Utc};
+use ipnet::IpNet;
+use sentinel_core::events::EventPayload;
+
+/// Identity of one coordinating worker, gossiped in its heartbeats.
+pub type WorkerId = String;
+
+/// Liveness record for one peer, refreshed on every gossiped heartbeat.
+#[derive(Debug, Clone)]
+struct PeerState {
+    capacity: u32,
+    last_heartbeat: DateTime<Utc>,
+}
+
+/// Tracks live peer workers and assigns CIDR buckets to exactly one live
+/// worker via rendezvous hashing.
+pub struct ScanCoordinator {
+    worker_id: WorkerId,
+    capacity: u32,
+    bucket_prefix_len: u8,
+    heartbeat_ttl: Duration,
+    peers: HashMap<WorkerId, PeerState>,
+}
+
+impl ScanCoordinator {
+    pub fn new(worker_id: impl Into<WorkerId>, capacity: u32, bucket_prefix_len: u8, heartbeat_ttl: Duration) -> Self {
+        Self {
+            worker_id: worker_id.into(),
+            capacity,
+            bucket_prefix_len,
+            heartbeat_ttl,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// This worker's own ID, as gossiped in its heartbeats.
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    /// Build this worker's heartbeat payload to gossip on the event stream.
+    pub fn heartbeat_payload(&self, assigned_buckets: Vec<String>) -> EventPayload {
+        EventPayload::ScannerHeartbeat {
+            worker_id: self.worker_id.clone(),
+            assigned_buckets,
+            capacity: self.capacity,
+            wallclock: Utc::now(),
+        }
+    }
+
+    /// Apply a peer's gossiped heartbeat to the node table. Ignores
+    /// heartbeats from this worker's own ID (nothing to learn from them).
+    pub fn ingest_heartbeat(&mut self, payload: &EventPayload) {
+        if let EventPayload::ScannerHeartbeat {
+            worker_id,
+            capacity,
+            wallclock,
+            ..
+        } = payload
+        {
+            if worker_id == &self.worker_id {
+                return;
+            }
+            self.peers.insert(
+                worker_id.clone(),
+                PeerState {
+                    capacity: *capacity,
+                    last_heartbeat: *wallclock,
+                },
+            );
+        }
+    }
+
+    /// Evict peers whose last heartbeat is older than `heartbeat_ttl` as of
+    /// `now`, reclaiming their buckets for the remaining live set.
+    pub fn evict_stale_peers(&mut self, now: DateTime<Utc>) {
+        let ttl = self.heartbeat_ttl;
+        self.peers
+            .retain(|_, peer| now - peer.last_heartbeat <= ttl);
+    }
+
+    /// Number of peers currently considered live, not counting self.
+    pub fn live_peer_count(&self) -> usize {
+        self.peers.len()
+    }
+
+    fn live_workers(&self) -> Vec<WorkerId> {
+        let mut ids: Vec<WorkerId> = self.peers.keys().cloned().collect();
+        ids.push(self.worker_id.clone());
+        ids
+    }
+
+    /// Take a point-in-time, lock-free snapshot of the live worker set and
+    /// this worker's bucket settings, sufficient to answer ownership
+    /// queries without holding the coordinator across an `await` point
+    /// (e.g. while `compute_diff` runs).
+    pub fn snapshot(&self) -> OwnershipSnapshot {
+        OwnershipSnapshot {
+            worker_id: self.worker_id.clone(),
+            bucket_prefix_len: self.bucket_prefix_len,
+            live_workers: self.live_workers(),
+        }
+    }
+
+    /// True if this worker owns `bucket` among the currently live worker
+    /// set, per rendezvous (highest random weight) hashing: every worker
+    /// computes the same winner independently, with no coordination needed
+    /// beyond agreeing on the live set.
+    pub fn owns_bucket(&self, bucket: &str) -> bool {
+        self.snapshot().owns_bucket(bucket)
+    }
+
+    /// True if `ip` falls in a bucket this worker owns.
+    pub fn owns_ip(&self, ip: &str) -> bool {
+        self.snapshot().owns_ip(ip)
+    }
+
+    /// Partition `cidr` into buckets of `bucket_prefix_len` and return the
+    /// bucket keys this worker currently owns (for gossiping as
+    /// `assigned_buckets` in its heartbeat).
+    pub fn owned_buckets(&self, cidr: &IpNet) -> Vec<String> {
+        self.snapshot().owned_buckets(cidr)
+    }
+}
+
+/// A point-in-time, lock-free snapshot of the live worker set and this
+/// worker's bucket settings. See [`ScanCoordinator::snapshot`].
+#[derive(Debug, Clone)]
+pub struct OwnershipSnapshot {
+    worker_id: WorkerId,
+    bucket_prefix_len: u8,
+    live_workers: Vec<WorkerId>,
+}
+
+impl OwnershipSnapshot {
+    /// True if `worker_id` owns `bucket` among this snapshot's live set.
+    pub fn owns_bucket(&self, bucket: &str) -> bool {
+        let winner = self
+            .live_workers
+            .iter()
+            .max_by_key(|id| rendezvous_weight(id, bucket))
+            .expect("live_workers always includes self");
+        winner == &self.worker_id
+    }
+
+    /// True if `ip` falls in a bucket this worker owns. IPs that fail to
+    /// parse (shouldn't happen for scan output) are never silently
+    /// dropped — they're treated as owned.
+    pub fn owns_ip(&self, ip: &str) -> bool {
+        match ip.parse::<IpAddr>() {
+            Ok(addr) => self.owns_bucket(&bucket_key(addr, self.bucket_prefix_len)),
+            Err(_) => true,
+        }
+    }
+
+    /// Partition `cidr` into buckets of `bucket_prefix_len` and return the
+    /// bucket keys this worker currently owns.
+    pub fn owned_buckets(&self, cidr: &IpNet) -> Vec<String> {
+        let Ok(subnets) = cidr.subnets(self.bucket_prefix_len) else {
+            return vec![cidr.trunc().to_string()];
+        };
+        subnets
+            .map(|bucket| bucket.trunc().to_string())
+            .filter(|bucket| self.owns_bucket(bucket))
+            .collect()
+    }
+}
+
+fn bucket_key(ip: IpAddr, prefix_len: u8) -> String {
+    IpNet::new(ip, prefix_len)
+        .map(|net| net.trunc().to_string())
+        .unwrap_or_else(|_| ip.to_string())
+}
+
+fn rendezvous_weight(worker_id: &str, bucket: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    worker_id.hash(&mut hasher);
+    bucket.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinator(worker_id: &str) -> ScanCoordinator {
+        ScanCoordinator::new(worker_id, 1, 24, Duration::seconds(90))
+    }
+
+    #[test]
+    fn solo_worker_owns_every_bucket() {
+        let c = coordinator("worker-a");
+        assert!(c.owns_bucket("10.0.1.0/24"));
+        assert!(c.owns_bucket("10.0.2.0/24"));
+    }
+
+    #[test]
+    fn exactly_one_of_two_workers_owns_each_bucket() {
+        let mut a = coordinator("worker-a");
+        let mut b = coordinator("worker-b");
+        a.ingest_heartbeat(&b.heartbeat_payload(vec![]));
+        b.ingest_heartbeat(&a.heartbeat_payload(vec![]));
+
+        for i in 0..20 {
+            let bucket = format!("10.0.{i}.0/24");
+            assert_ne!(a.owns_bucket(&bucket), b.owns_bucket(&bucket));
+        }
+    }
+
+    #[test]
+    fn stale_peer_is_evicted_and_buckets_are_reclaimed() {
+        let mut a = coordinator("worker-a");
+        let old_heartbeat = EventPayload::ScannerHeartbeat {
+            worker_id: "worker-b".to_string(),
+            assigned_buckets: vec![],
+            capacity: 1,
+            wallclock: Utc::now() - Duration::seconds(200),
+        };
+        a.ingest_heartbeat(&old_heartbeat);
+        assert_eq!(a.live_peer_count(), 1);
+
+        a.evict_stale_peers(Utc::now());
+        assert_eq!(a.live_peer_count(), 0);
+        // With worker-b evicted, worker-a is solo again and owns everything.
+        assert!(a.owns_bucket("10.0.5.0/24"));
+    }
+
+    #[test]
+    fn ingest_ignores_own_heartbeat() {
+        let mut a = coordinator("worker-a");
+        a.ingest_heartbeat(&a.heartbeat_payload(vec![]));
+        assert_eq!(a.live_peer_count(), 0);
+    }
+
+    #[test]
+    fn owned_buckets_partitions_cidr_deterministically() {
+        let cidr: IpNet = "10.0.0.0/22".parse().unwrap();
+        let mut a = coordinator("worker-a");
+        let mut b = coordinator("worker-b");
+        a.ingest_heartbeat(&b.heartbeat_payload(vec![]));
+        b.ingest_heartbeat(&a.heartbeat_payload(vec![]));
+
+        let a_buckets = a.owned_buckets(&cidr);
+        let b_buckets = b.owned_buckets(&cidr);
+
+        // Every /24 in the /22 is owned by exactly one of the two workers.
+        assert_eq!(a_buckets.len() + b_buckets.len(), 4);
+        for bucket in &a_buckets {
+            assert!(!b_buckets.contains(bucket));
+        }
+    }
+
+    #[test]
+    fn owns_ip_matches_owns_bucket_for_its_containing_bucket() {
+        let c = coordinator("solo");
+        assert_eq!(c.owns_ip("10.0.1.42"), c.owns_bucket("10.0.1.0/24"));
+    }
+}
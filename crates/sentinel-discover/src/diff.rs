@@ -6,6 +6,8 @@ use std::net::IpAddr;
 
 use chrono::{DateTime, Utc};
 use ipnet::IpNet;
+use sentinel_core::bloom::BloomFilter;
+use sentinel_core::crdt::{resolve_field, FieldVersion, HostFieldVersions};
 use sentinel_core::types::{
     CloudProvider, Criticality, Edge, EdgeId, EdgeProperties, EdgeType, Host, NodeId, Port,
     PortState, Protocol, Service, ServiceState, TenantId,
@@ -13,27 +15,49 @@ use sentinel_core::types::{
 use sentinel_graph::GraphClient;
 use uuid::Uuid;
 
+use crate::coordination::OwnershipSnapshot;
 use crate::error::Result;
 use crate::nmap_xml::{NmapHost, NmapRun};
 
 /// DNS namespace UUID for deterministic port/service IDs.
-const SENTINEL_NS: Uuid = Uuid::from_bytes([
+pub(crate) const SENTINEL_NS: Uuid = Uuid::from_bytes([
     0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
 ]);
 
 /// A host with its discovered ports, services, and edges.
+///
+/// `host_versions`, `port_versions`, and `service_versions` start out
+/// stamped uniformly with this scan's [`FieldVersion`] by
+/// [`parse_scan_results`]; [`compute_diff`] then resolves each group
+/// against whatever is already stored, so by the time a `DiscoveredHost`
+/// reaches `persist`, its fields and versions already reflect whichever
+/// writer won.
 pub struct DiscoveredHost {
     pub host: Host,
+    pub host_versions: HostFieldVersions,
     pub ports: Vec<Port>,
+    pub port_versions: Vec<FieldVersion>,
     pub services: Vec<Service>,
+    pub service_versions: Vec<FieldVersion>,
     pub edges: Vec<Edge>,
 }
 
+/// Records which writer's value won a CRDT field-group conflict, so
+/// operators can see that concurrent scans converged deterministically
+/// instead of depending on upsert arrival order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecision {
+    pub ip: String,
+    pub field: String,
+    pub winner_writer_id: String,
+}
+
 /// The outcome of diffing scan results against the graph.
 pub struct DiffResult {
     pub new_hosts: Vec<DiscoveredHost>,
     pub changed_hosts: Vec<DiscoveredHost>,
     pub stale_ips: Vec<String>,
+    pub field_decisions: Vec<FieldDecision>,
     pub summary: DiffSummary,
 }
 
@@ -45,17 +69,21 @@ pub struct DiffSummary {
     pub stale_count: u32,
 }
 
-/// Convert raw nmap output into typed sentinel-core entities.
+/// Convert raw nmap output into typed sentinel-core entities, stamping
+/// every mutable field group with a [`FieldVersion`] derived from
+/// `scan_time` and `writer_id` (typically `DiscoverConfig::writer_id`,
+/// identifying this scanner instance).
 pub fn parse_scan_results(
     nmap_run: &NmapRun,
     tenant_id: &TenantId,
     scan_time: DateTime<Utc>,
+    writer_id: &str,
 ) -> Vec<DiscoveredHost> {
     nmap_run
         .hosts
         .iter()
         .filter(|h| h.is_up())
-        .filter_map(|h| convert_nmap_host(h, tenant_id, scan_time))
+        .filter_map(|h| convert_nmap_host(h, tenant_id, scan_time, writer_id))
         .collect()
 }
 
@@ -63,8 +91,10 @@ fn convert_nmap_host(
     nmap_host: &NmapHost,
     tenant_id: &TenantId,
     now: DateTime<Utc>,
+    writer_id: &str,
 ) -> Option<DiscoveredHost> {
     let ip = nmap_host.ipv4()?;
+    let version = FieldVersion::new(now.timestamp_millis(), writer_id);
 
     // Deterministic host ID based on tenant + IP so MERGE is idempotent.
     let host_id = NodeId(Uuid::new_v5(
@@ -90,7 +120,9 @@ fn convert_nmap_host(
     };
 
     let mut ports = Vec::new();
+    let mut port_versions = Vec::new();
     let mut services = Vec::new();
+    let mut service_versions = Vec::new();
     let mut edges = Vec::new();
 
     if let Some(nmap_ports) = &nmap_host.ports {
@@ -110,6 +142,7 @@ fn convert_nmap_host(
                 last_seen: now,
             };
             ports.push(port);
+            port_versions.push(version.clone());
 
             // Host --HAS_PORT--> Port
             edges.push(Edge {
@@ -157,6 +190,7 @@ fn convert_nmap_host(
                     last_seen: now,
                 };
                 services.push(service);
+                service_versions.push(version.clone());
 
                 // Host --EXPOSES--> Service
                 edges.push(Edge {
@@ -182,45 +216,61 @@ fn convert_nmap_host(
 
     Some(DiscoveredHost {
         host,
+        host_versions: HostFieldVersions::stamped(version),
         ports,
+        port_versions,
         services,
+        service_versions,
         edges,
     })
 }
 
 /// Compare discovered hosts against what's currently in Neo4j.
+///
+/// For a host that already exists, each mutable field group is resolved
+/// independently via [`resolve_field`] against this scan's version,
+/// rather than unconditionally overwriting with the new scan's values.
+/// This is what lets two scanners covering overlapping CIDRs run
+/// concurrently without clobbering each other: whichever has the later
+/// `FieldVersion` wins per field group, and replaying the same scan
+/// output is a no-op (the incoming version never dominates itself worse
+/// than a tie).
+///
+/// When `ownership` is `Some`, hosts outside this worker's owned buckets
+/// (see `crate::coordination`) are skipped entirely rather than diffed and
+/// persisted — the worker that owns the bucket is responsible for them.
+/// `ownership` is `None` for single-worker deployments, which diff and
+/// persist every discovered host, matching prior behavior.
 pub async fn compute_diff(
     graph: &GraphClient,
     tenant_id: &TenantId,
     discovered: Vec<DiscoveredHost>,
     scan_target_cidr: &str,
+    ownership: Option<&OwnershipSnapshot>,
 ) -> Result<DiffResult> {
     let mut new_hosts = Vec::new();
     let mut changed_hosts = Vec::new();
+    let mut field_decisions = Vec::new();
     let mut seen_ips: HashSet<String> = HashSet::new();
 
-    for dh in discovered {
+    let owned = discovered.into_iter().filter(|dh| match ownership {
+        Some(o) => o.owns_ip(&dh.host.ip),
+        None => true,
+    });
+
+    for dh in owned {
         seen_ips.insert(dh.host.ip.clone());
 
         let existing = graph
-            .find_node_by_property(tenant_id, "Host", "ip", &dh.host.ip)
+            .find_node_by_property(tenant_id, "Host", "ip", &dh.host.ip, None)
             .await?;
 
         match existing {
             None => new_hosts.push(dh),
             Some(record) => {
-                // Check if properties changed.
-                let props = &record.properties;
-                let hostname_changed =
-                    dh.host.hostname.as_deref() != props.get("hostname").and_then(|v| v.as_str());
-                let os_changed = dh.host.os.as_deref() != props.get("os").and_then(|v| v.as_str());
-
-                if hostname_changed || os_changed {
-                    changed_hosts.push(dh);
-                } else {
-                    // Unchanged — still upsert to update last_seen.
-                    changed_hosts.push(dh);
-                }
+                let (resolved, decisions) = resolve_host_conflict(dh, &record.properties);
+                field_decisions.extend(decisions);
+                changed_hosts.push(resolved);
             }
         }
     }
@@ -239,38 +289,157 @@ pub async fn compute_diff(
         new_hosts,
         changed_hosts,
         stale_ips,
+        field_decisions,
         summary,
     })
 }
 
+/// Resolve one host's field groups against the properties of the node
+/// already stored in Neo4j, returning the merged `DiscoveredHost` (ready
+/// to upsert as-is) and a [`FieldDecision`] per resolved field group.
+fn resolve_host_conflict(
+    mut dh: DiscoveredHost,
+    stored: &serde_json::Value,
+) -> (DiscoveredHost, Vec<FieldDecision>) {
+    let ip = dh.host.ip.clone();
+    let mut decisions = Vec::new();
+
+    let stored_hostname = stored_opt_string(stored, "hostname");
+    let stored_hostname_version = FieldVersion::from_stored(stored, "hostname");
+    let (hostname, hostname_version, winner) = resolve_field(
+        dh.host.hostname.clone(),
+        &dh.host_versions.hostname,
+        Some((stored_hostname, stored_hostname_version)),
+    );
+    dh.host.hostname = hostname;
+    dh.host_versions.hostname = hostname_version;
+    decisions.push(FieldDecision {
+        ip: ip.clone(),
+        field: "hostname".to_string(),
+        winner_writer_id: winner,
+    });
+
+    let stored_os = (
+        stored_opt_string(stored, "os"),
+        stored_opt_string(stored, "os_version"),
+    );
+    let stored_os_version = FieldVersion::from_stored(stored, "os");
+    let (os, os_version, winner) = resolve_field(
+        (dh.host.os.clone(), dh.host.os_version.clone()),
+        &dh.host_versions.os,
+        Some((stored_os, stored_os_version)),
+    );
+    dh.host.os = os.0;
+    dh.host.os_version = os.1;
+    dh.host_versions.os = os_version;
+    decisions.push(FieldDecision {
+        ip: ip.clone(),
+        field: "os".to_string(),
+        winner_writer_id: winner,
+    });
+
+    let stored_mac = stored_opt_string(stored, "mac_address");
+    let stored_mac_version = FieldVersion::from_stored(stored, "mac_address");
+    let (mac_address, mac_version, winner) = resolve_field(
+        dh.host.mac_address.clone(),
+        &dh.host_versions.mac_address,
+        Some((stored_mac, stored_mac_version)),
+    );
+    dh.host.mac_address = mac_address;
+    dh.host_versions.mac_address = mac_version;
+    decisions.push(FieldDecision {
+        ip: ip.clone(),
+        field: "mac_address".to_string(),
+        winner_writer_id: winner,
+    });
+
+    let stored_tags = stored
+        .get("tags")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let stored_tags_version = FieldVersion::from_stored(stored, "tags");
+    let (tags, tags_version, winner) = resolve_field(
+        dh.host.tags.clone(),
+        &dh.host_versions.tags,
+        Some((stored_tags, stored_tags_version)),
+    );
+    dh.host.tags = tags;
+    dh.host_versions.tags = tags_version;
+    decisions.push(FieldDecision {
+        ip,
+        field: "tags".to_string(),
+        winner_writer_id: winner,
+    });
+
+    (dh, decisions)
+}
+
+/// Read an optional string property, treating an empty string the same
+/// as absent (Neo4j stores `Option<String>` as `""` when `None` — see
+/// `opt_string` in `sentinel-graph`'s mutations module).
+fn stored_opt_string(properties: &serde_json::Value, key: &str) -> Option<String> {
+    properties
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+/// Below this many scanned IPs, a `HashSet` lookup against the exact
+/// `list_nodes` payload is already cheap enough that a Bloom filter's
+/// sizing overhead isn't worth it.
+const BLOOM_RECONCILE_MIN_SEEN: usize = 256;
+
+/// Target Bloom filter false-positive rate for stale-host reconciliation.
+/// A false positive just means a genuinely-stale host is skipped this
+/// round and re-checked on the next scan, so 1% is generous.
+const BLOOM_TARGET_FPR: f64 = 0.01;
+
 /// Query graph for existing hosts in the CIDR and return IPs not seen in scan.
+///
+/// For small scans, compares against the exact host list directly. For
+/// larger tenants, builds a [`BloomFilter`] over `seen_ips` and checks each
+/// stored IP against it instead of a `HashSet`: membership has no false
+/// negatives, so no genuinely-seen host is ever wrongly flagged stale, and
+/// the lean [`GraphClient::list_node_ips`] projection avoids transferring
+/// full node properties just to diff IP sets.
 async fn find_stale_ips(
     graph: &GraphClient,
     tenant_id: &TenantId,
     cidr_str: &str,
     seen_ips: &HashSet<String>,
 ) -> Result<Vec<String>> {
-    let all_hosts = graph.list_nodes(tenant_id, "Host", 10_000, 0).await?;
-
     let cidr: Option<IpNet> = cidr_str.parse().ok();
+    let in_cidr = |ip_str: &str| match (&cidr, ip_str.parse::<IpAddr>()) {
+        (Some(net), Ok(ip)) => net.contains(&ip),
+        _ => true,
+    };
 
-    Ok(all_hosts
-        .iter()
-        .filter_map(|record| {
-            let ip_str = record.properties.get("ip")?.as_str()?;
-            if seen_ips.contains(ip_str) {
-                return None;
-            }
-            // Only consider hosts within the scanned CIDR.
-            if let Some(ref net) = cidr {
-                if let Ok(ip) = ip_str.parse::<IpAddr>() {
-                    if !net.contains(&ip) {
-                        return None;
-                    }
-                }
-            }
-            Some(ip_str.to_string())
-        })
+    if seen_ips.len() < BLOOM_RECONCILE_MIN_SEEN {
+        let all_hosts = graph.list_nodes(tenant_id, "Host", 10_000, 0, None).await?;
+        return Ok(all_hosts
+            .iter()
+            .filter_map(|record| {
+                let ip_str = record.properties.get("ip")?.as_str()?;
+                (!seen_ips.contains(ip_str) && in_cidr(ip_str)).then(|| ip_str.to_string())
+            })
+            .collect());
+    }
+
+    let mut filter = BloomFilter::with_fpr(seen_ips.len(), BLOOM_TARGET_FPR);
+    for ip in seen_ips {
+        filter.insert(ip);
+    }
+
+    let all_ips = graph.list_node_ips(tenant_id, "Host", 10_000).await?;
+    Ok(all_ips
+        .into_iter()
+        .filter(|ip_str| !filter.contains(ip_str) && in_cidr(ip_str))
         .collect())
 }
 
@@ -294,6 +463,7 @@ fn parse_port_state(state: &str) -> PortState {
 mod tests {
     use super::*;
     use crate::nmap_xml::parse_nmap_xml;
+    use chrono::TimeZone;
 
     fn test_tenant() -> TenantId {
         TenantId(Uuid::nil())
@@ -324,7 +494,7 @@ mod tests {
         let nmap_run = parse_nmap_xml(xml.as_bytes()).unwrap();
         let tid = test_tenant();
         let now = Utc::now();
-        let results = parse_scan_results(&nmap_run, &tid, now);
+        let results = parse_scan_results(&nmap_run, &tid, now, "writer-a");
 
         assert_eq!(results.len(), 1);
         let host = &results[0];
@@ -352,13 +522,101 @@ mod tests {
 </nmaprun>"#;
 
         let run = parse_nmap_xml(xml.as_bytes()).unwrap();
-        let r1 = parse_scan_results(&run, &tid, now);
-        let r2 = parse_scan_results(&run, &tid, now);
+        let r1 = parse_scan_results(&run, &tid, now, "writer-a");
+        let r2 = parse_scan_results(&run, &tid, now, "writer-a");
 
         // Same input → same host ID.
         assert_eq!(r1[0].host.id, r2[0].host.id);
     }
 
+    #[test]
+    fn test_resolve_host_conflict_prefers_later_writer() {
+        let tid = test_tenant();
+        let earlier = Utc.timestamp_millis_opt(1_000).unwrap();
+        let later = Utc.timestamp_millis_opt(2_000).unwrap();
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <hostnames><hostname name="fresh.local" type="PTR"/></hostnames>
+  </host>
+</nmaprun>"#;
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let incoming = convert_nmap_host(&run.hosts[0], &tid, later, "writer-new").unwrap();
+
+        let stored = serde_json::json!({
+            "hostname": "stale.local",
+            "hostname_version_ms": earlier.timestamp_millis(),
+            "hostname_version_writer": "writer-old",
+        });
+
+        let (resolved, decisions) = resolve_host_conflict(incoming, &stored);
+        assert_eq!(resolved.host.hostname.as_deref(), Some("fresh.local"));
+        assert_eq!(
+            decisions
+                .iter()
+                .find(|d| d.field == "hostname")
+                .unwrap()
+                .winner_writer_id,
+            "writer-new"
+        );
+    }
+
+    #[test]
+    fn test_resolve_host_conflict_keeps_stored_when_incoming_is_stale() {
+        let tid = test_tenant();
+        let earlier = Utc.timestamp_millis_opt(1_000).unwrap();
+        let later = Utc.timestamp_millis_opt(2_000).unwrap();
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <hostnames><hostname name="late-arriving.local" type="PTR"/></hostnames>
+  </host>
+</nmaprun>"#;
+        let run = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let incoming = convert_nmap_host(&run.hosts[0], &tid, earlier, "writer-old").unwrap();
+
+        let stored = serde_json::json!({
+            "hostname": "already-fresher.local",
+            "hostname_version_ms": later.timestamp_millis(),
+            "hostname_version_writer": "writer-new",
+        });
+
+        let (resolved, decisions) = resolve_host_conflict(incoming, &stored);
+        assert_eq!(
+            resolved.host.hostname.as_deref(),
+            Some("already-fresher.local")
+        );
+        assert_eq!(
+            decisions
+                .iter()
+                .find(|d| d.field == "hostname")
+                .unwrap()
+                .winner_writer_id,
+            "writer-new"
+        );
+    }
+
+    #[test]
+    fn test_stored_opt_string_treats_empty_as_none() {
+        let stored = serde_json::json!({ "mac_address": "" });
+        assert_eq!(stored_opt_string(&stored, "mac_address"), None);
+        assert_eq!(stored_opt_string(&stored, "missing"), None);
+
+        let stored = serde_json::json!({ "mac_address": "aa:bb:cc:dd:ee:ff" });
+        assert_eq!(
+            stored_opt_string(&stored, "mac_address"),
+            Some("aa:bb:cc:dd:ee:ff".to_string())
+        );
+    }
+
     #[test]
     fn test_parse_protocol() {
         assert_eq!(parse_protocol("tcp"), Protocol::Tcp);
@@ -21,6 +21,9 @@ pub enum DiscoverError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("STUN reflexive-address probe failed: {0}")]
+    Stun(String),
 }
 
 pub type Result<T> = std::result::Result<T, DiscoverError>;
@@ -0,0 +1,150 @@
+//! Reflexive-address (STUN) probing for external-exposure detection.
+//!
+//! For each open [`Service`] discovered on a host, binds a UDP socket on
+//! the service's port and sends a STUN binding request to a configured
+//! reflector to learn whether (and where) that port is reachable from
+//! outside the host's NAT. This mirrors `scanner.rs`'s relationship to
+//! nmap: this module owns the network I/O, callers just get back edges
+//! and events to persist.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use sentinel_core::types::{Edge, EdgeId, EdgeProperties, EdgeType, Service, TenantId};
+
+use crate::diff::{DiscoveredHost, SENTINEL_NS};
+use crate::error::{DiscoverError, Result};
+use crate::stun;
+
+/// The public endpoint a service was confirmed reachable at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalMapping {
+    pub external_ip: String,
+    pub external_port: u16,
+}
+
+/// Probe a single service's port against a list of STUN reflectors, in
+/// order, returning the first confirmed mapping.
+///
+/// Returns `Ok(None)` if no reflector responds within `probe_timeout` --
+/// that's the expected outcome for a service that genuinely isn't exposed,
+/// not an error.
+pub async fn probe_reflexive_address(
+    bind_port: u16,
+    reflectors: &[String],
+    probe_timeout: Duration,
+) -> Result<Option<ExternalMapping>> {
+    let socket = UdpSocket::bind(("0.0.0.0", bind_port))
+        .await
+        .map_err(|e| DiscoverError::Stun(format!("bind to port {bind_port}: {e}")))?;
+
+    for reflector in reflectors {
+        let Ok(mut addrs) = tokio::net::lookup_host(reflector).await else {
+            continue;
+        };
+        let Some(addr) = addrs.next() else {
+            continue;
+        };
+
+        let transaction_id: [u8; 12] = Uuid::new_v4().as_bytes()[..12].try_into().unwrap();
+        let request = stun::build_binding_request(transaction_id);
+
+        if socket.send_to(&request, addr).await.is_err() {
+            continue;
+        }
+
+        let mut buf = [0u8; 512];
+        let Ok(Ok((n, _))) = timeout(probe_timeout, socket.recv_from(&mut buf)).await else {
+            continue;
+        };
+
+        if let Some(mapped) = stun::parse_binding_response(&buf[..n], transaction_id) {
+            return Ok(Some(ExternalMapping {
+                external_ip: mapped.ip().to_string(),
+                external_port: mapped.port(),
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Build the self-loop `ExposedExternally` edge for a service whose
+/// external mapping was confirmed via [`probe_reflexive_address`].
+///
+/// The graph ontology has no dedicated "external endpoint" node type, so
+/// the mapped `ip:port` is carried in the edge's properties rather than a
+/// separate target node -- the edge loops from the `Service` back to
+/// itself.
+pub fn build_exposure_edge(
+    service: &Service,
+    tenant_id: &TenantId,
+    mapping: &ExternalMapping,
+    now: DateTime<Utc>,
+) -> Edge {
+    Edge {
+        id: EdgeId(Uuid::new_v5(
+            &SENTINEL_NS,
+            format!("{}:edge:exposed_externally:{}", tenant_id.0, service.id.0).as_bytes(),
+        )),
+        tenant_id: tenant_id.clone(),
+        source_id: service.id.clone(),
+        target_id: service.id.clone(),
+        edge_type: EdgeType::ExposedExternally,
+        properties: EdgeProperties {
+            port: Some(service.port),
+            protocol: Some(service.protocol.clone()),
+            external_ip: Some(mapping.external_ip.clone()),
+            external_port: Some(mapping.external_port),
+            ..Default::default()
+        },
+        first_seen: now,
+        last_seen: now,
+    }
+}
+
+/// Probe every open service on `hosts` for external exposure, attaching a
+/// confirmed mapping as an `ExposedExternally` edge on the owning host.
+///
+/// `persist_diff` (see `crate::persist`) upserts these edges alongside the
+/// rest of a host's edges and emits `ExternalExposureDetected` from them,
+/// so this function only needs to attach them -- it doesn't build events
+/// itself.
+///
+/// No-ops without touching the network when `reflectors` is empty, so this
+/// capability is opt-in via `DiscoverConfig::stun_reflectors`.
+pub async fn detect_exposure(
+    hosts: &mut [DiscoveredHost],
+    tenant_id: &TenantId,
+    reflectors: &[String],
+    probe_timeout: Duration,
+    now: DateTime<Utc>,
+) {
+    if reflectors.is_empty() {
+        return;
+    }
+
+    for host in hosts.iter_mut() {
+        for service in &host.services {
+            match probe_reflexive_address(service.port, reflectors, probe_timeout).await {
+                Ok(Some(mapping)) => {
+                    host.edges
+                        .push(build_exposure_edge(service, tenant_id, &mapping, now));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        service_id = ?service.id,
+                        port = service.port,
+                        error = %e,
+                        "STUN exposure probe failed"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Interactive `init` wizard: prompts an operator through building a
+//! [`DiscoverConfig`] and writes it to `sentinel.toml` as a `[discover]`
+//! section, so standing up a new scanner doesn't require hand-editing
+//! TOML.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use ipnet::IpNet;
+use serde::Serialize;
+
+use crate::config::{DiscoverConfig, ScanProfile, SubnetSchedule};
+
+/// Document shape written to `sentinel.toml`: just the `[discover]`
+/// section, so re-running `init` against a file with other sections
+/// (e.g. `[neo4j]`) would still clobber it — callers are expected to run
+/// this against a dedicated or fresh file, consistent with `--force`
+/// guarding overwrites.
+#[derive(Serialize)]
+struct TomlDocument<'a> {
+    discover: &'a DiscoverConfig,
+}
+
+/// Run the wizard and write the resulting config to `path`.
+///
+/// Refuses to overwrite an existing file unless `force` is set. In
+/// `non_interactive` mode, every field is filled with its default
+/// (equivalent to accepting every prompt's default and adding no
+/// subnets), so the command is scriptable.
+pub fn run(path: &Path, non_interactive: bool, force: bool) -> anyhow::Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists; pass --force to overwrite", path.display());
+    }
+
+    let config = if non_interactive {
+        DiscoverConfig::default()
+    } else {
+        prompt_config()?
+    };
+
+    let doc = TomlDocument { discover: &config };
+    let rendered = toml::to_string_pretty(&doc)?;
+    std::fs::write(path, rendered)?;
+
+    println!(
+        "Wrote {} subnet(s) to {}",
+        config.subnets.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn prompt_config() -> anyhow::Result<DiscoverConfig> {
+    let nmap_path = prompt_str("Path to nmap binary", "nmap")?;
+    let tenant_id = prompt_str("Tenant ID (UUID)", "")?;
+    let default_profile = prompt_profile("Default scan profile", ScanProfile::Standard)?;
+    let stale_threshold_hours = prompt_u64("Stale threshold (hours)", 24)?;
+    let engram_dir = prompt_str("Engram storage directory", "./engrams")?;
+    let max_concurrent_scans = prompt_usize("Max concurrent nmap processes", 4)?;
+
+    let mut subnets = Vec::new();
+    println!("\nAdd subnets to scan (leave CIDR blank to finish).");
+    loop {
+        let cidr = prompt_str("Subnet CIDR (e.g. 10.0.1.0/24)", "")?;
+        if cidr.is_empty() {
+            break;
+        }
+        if cidr.parse::<IpNet>().is_err() {
+            println!("  '{cidr}' is not a valid CIDR, try again.");
+            continue;
+        }
+
+        let name = prompt_str("  Name", "")?;
+        let profile = prompt_profile("  Scan profile for this subnet", default_profile)?;
+        let interval_secs = prompt_u64("  Scan interval (seconds)", 3600)?;
+        let enabled = prompt_bool("  Enabled", true)?;
+
+        subnets.push(SubnetSchedule {
+            cidr,
+            name: if name.is_empty() { None } else { Some(name) },
+            profile: Some(profile),
+            interval_secs,
+            enabled,
+        });
+    }
+
+    Ok(DiscoverConfig {
+        nmap_path,
+        tenant_id,
+        default_profile,
+        subnets,
+        stale_threshold_hours,
+        engram_dir,
+        max_concurrent_scans,
+        ..DiscoverConfig::default()
+    })
+}
+
+fn prompt_str(label: &str, default: &str) -> anyhow::Result<String> {
+    let answer = read_line(&format!("{label} [{default}]: "))?;
+    Ok(if answer.is_empty() { default.to_string() } else { answer })
+}
+
+fn prompt_u64(label: &str, default: u64) -> anyhow::Result<u64> {
+    loop {
+        let answer = read_line(&format!("{label} [{default}]: "))?;
+        if answer.is_empty() {
+            return Ok(default);
+        }
+        match answer.parse() {
+            Ok(v) => return Ok(v),
+            Err(_) => println!("  Not a number, try again."),
+        }
+    }
+}
+
+fn prompt_usize(label: &str, default: usize) -> anyhow::Result<usize> {
+    loop {
+        let answer = read_line(&format!("{label} [{default}]: "))?;
+        if answer.is_empty() {
+            return Ok(default);
+        }
+        match answer.parse() {
+            Ok(v) => return Ok(v),
+            Err(_) => println!("  Not a number, try again."),
+        }
+    }
+}
+
+fn prompt_bool(label: &str, default: bool) -> anyhow::Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    loop {
+        let answer = read_line(&format!("{label} [{hint}]: "))?;
+        match answer.to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("  Please answer y or n."),
+        }
+    }
+}
+
+fn prompt_profile(label: &str, default: ScanProfile) -> anyhow::Result<ScanProfile> {
+    let hint = match default {
+        ScanProfile::Quick => "quick",
+        ScanProfile::Standard => "standard",
+        ScanProfile::Deep => "deep",
+    };
+    loop {
+        let answer = read_line(&format!("{label} (quick/standard/deep) [{hint}]: "))?;
+        match answer.to_lowercase().as_str() {
+            "" => return Ok(default),
+            "quick" => return Ok(ScanProfile::Quick),
+            "standard" => return Ok(ScanProfile::Standard),
+            "deep" => return Ok(ScanProfile::Deep),
+            _ => println!("  Choose quick, standard, or deep."),
+        }
+    }
+}
+
+fn read_line(prompt: &str) -> anyhow::Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_interactive_writes_default_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sentinel.toml");
+
+        run(&path, true, false).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let parsed: toml::Value = toml::from_str(&contents).unwrap();
+        let discover = parsed.get("discover").unwrap();
+        assert_eq!(discover.get("nmap_path").unwrap().as_str(), Some("nmap"));
+        assert_eq!(discover.get("default_profile").unwrap().as_str(), Some("standard"));
+        assert!(discover.get("subnets").unwrap().as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn refuses_to_overwrite_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sentinel.toml");
+        std::fs::write(&path, "existing = true\n").unwrap();
+
+        let err = run(&path, true, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+
+        // Original contents are untouched.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "existing = true\n");
+    }
+
+    #[test]
+    fn force_overwrites_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sentinel.toml");
+        std::fs::write(&path, "existing = true\n").unwrap();
+
+        run(&path, true, true).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("[discover]"));
+    }
+}
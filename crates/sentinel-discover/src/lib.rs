@@ -3,11 +3,18 @@
 //! Wraps nmap to scan subnets, detects changes against the Neo4j graph,
 //! and records an Engram audit trail for every scan run.
 
+pub mod bridge;
 pub mod config;
+pub mod coordination;
 pub mod diff;
 pub mod engram;
 pub mod error;
+pub mod exposure;
+pub mod init;
+pub mod maintenance;
 pub mod nmap_xml;
 pub mod persist;
+pub mod priority;
 pub mod scanner;
 pub mod scheduler;
+pub mod stun;
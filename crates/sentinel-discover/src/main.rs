@@ -1,86 +1,126 @@
 //! CLI entry point for the sentinel-discover network scanner.
 
-use clap::Parser;
-use tracing_subscriber::{fmt, EnvFilter};
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
 use uuid::Uuid;
 
+use sentinel_core::config::OtelConfig;
 use sentinel_core::types::TenantId;
 use sentinel_graph::{GraphClient, GraphConfig};
 
 use sentinel_discover::config::{DiscoverConfig, ScanProfile};
+use sentinel_discover::coordination::ScanCoordinator;
 use sentinel_discover::scanner::NmapScanner;
+use sentinel_discover::maintenance::DirtySet;
 use sentinel_discover::scheduler::{run_single_scan, ScanScheduler};
 
 #[derive(Parser)]
 #[command(name = "sentinel-discover")]
 #[command(about = "Network scanner for the Sentinel knowledge graph")]
 struct Cli {
-    /// Target to scan (CIDR notation, e.g., 10.0.1.0/24).
-    #[arg(short, long)]
-    target: Option<String>,
-
-    /// Scan profile: quick, standard, deep.
-    #[arg(short, long, default_value = "standard")]
-    profile: String,
-
-    /// Run a single one-shot scan and exit.
-    #[arg(long)]
-    once: bool,
-
-    /// Run as daemon with scheduled scans.
-    #[arg(long)]
-    daemon: bool,
+    #[command(subcommand)]
+    command: Command,
 
     /// Override tenant ID (otherwise read from config).
-    #[arg(long)]
+    #[arg(long, global = true)]
     tenant_id: Option<String>,
 
     /// Config file prefix (default: sentinel).
-    #[arg(short, long, default_value = "sentinel")]
+    #[arg(short, long, default_value = "sentinel", global = true)]
     config: String,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Run a one-shot or scheduled scan against the Sentinel knowledge graph.
+    Scan {
+        /// Target to scan (CIDR notation, e.g., 10.0.1.0/24).
+        #[arg(short, long)]
+        target: Option<String>,
+
+        /// Scan profile: quick, standard, deep.
+        #[arg(short, long, default_value = "standard")]
+        profile: String,
+
+        /// Run a single one-shot scan and exit.
+        #[arg(long)]
+        once: bool,
+
+        /// Run as daemon with scheduled scans.
+        #[arg(long)]
+        daemon: bool,
+    },
+    /// Interactively generate a `sentinel.toml` `[discover]` config.
+    Init {
+        /// Fill every field with its default instead of prompting (scriptable).
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Overwrite the config file if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-    fmt().with_env_filter(filter).json().init();
-
     let cli = Cli::parse();
-    let discover_config = load_discover_config(&cli.config)?;
-
-    // Connect to Neo4j.
-    let graph_config = load_graph_config(&cli.config);
-    let graph = GraphClient::connect(&graph_config).await?;
-    tracing::info!("Connected to Neo4j");
-
-    // Verify nmap installation.
-    let scanner = NmapScanner::new(&discover_config.nmap_path);
-    let version = scanner.verify_installation().await?;
-    tracing::info!(nmap_version = %version.trim(), "Nmap verified");
-
-    if cli.once {
-        let target = cli
-            .target
-            .as_deref()
-            .ok_or_else(|| anyhow::anyhow!("--target is required in --once mode"))?;
-        let profile = parse_profile(&cli.profile)?;
-        let tenant_id = resolve_tenant_id(&cli, &discover_config)?;
-
-        run_single_scan(
-            &scanner,
-            &graph,
-            &tenant_id,
-            &discover_config,
-            target,
-            &profile,
-        )
-        .await?;
-    } else if cli.daemon {
-        let tenant_id = resolve_tenant_id(&cli, &discover_config)?;
-        let sched = ScanScheduler::new(discover_config, scanner, graph, tenant_id);
-        sched.run().await?;
-    } else {
-        anyhow::bail!("Specify --once (one-shot scan) or --daemon (scheduled scanning)");
+
+    let otel_config = load_otel_config(&cli.config);
+    sentinel_core::otel::init_tracing("sentinel-discover", &otel_config)?;
+
+    match &cli.command {
+        Command::Init { non_interactive, force } => {
+            let path = PathBuf::from(format!("{}.toml", cli.config));
+            sentinel_discover::init::run(&path, *non_interactive, *force)?;
+        }
+        Command::Scan { target, profile, once, daemon } => {
+            let discover_config = load_discover_config(&cli.config)?;
+
+            // Connect to Neo4j.
+            let graph_config = load_graph_config(&cli.config);
+            let graph = GraphClient::connect(&graph_config).await?;
+            tracing::info!("Connected to Neo4j");
+
+            // Verify nmap installation.
+            let scanner = NmapScanner::new(&discover_config.nmap_path);
+            let version = scanner.verify_installation().await?;
+            tracing::info!(nmap_version = %version.trim(), "Nmap verified");
+
+            if *once {
+                let target = target
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("--target is required in --once mode"))?;
+                let profile = parse_profile(profile)?;
+                let tenant_id = resolve_tenant_id(cli.tenant_id.as_deref(), &discover_config)?;
+                let coordinator = std::sync::Mutex::new(ScanCoordinator::new(
+                    discover_config.writer_id.clone(),
+                    discover_config.scan_capacity,
+                    discover_config.bucket_prefix_len,
+                    chrono::Duration::seconds(discover_config.heartbeat_ttl_secs as i64),
+                ));
+                let dirty = DirtySet::new();
+
+                run_single_scan(
+                    &scanner,
+                    &graph,
+                    &tenant_id,
+                    &discover_config,
+                    target,
+                    &profile,
+                    &coordinator,
+                    &dirty,
+                )
+                .await?;
+            } else if *daemon {
+                let tenant_id = resolve_tenant_id(cli.tenant_id.as_deref(), &discover_config)?;
+                let sched = ScanScheduler::new(discover_config, scanner, graph, tenant_id);
+                sched.run().await?;
+            } else {
+                anyhow::bail!("Specify --once (one-shot scan) or --daemon (scheduled scanning)");
+            }
+        }
     }
 
     Ok(())
@@ -95,8 +135,11 @@ fn parse_profile(s: &str) -> anyhow::Result<ScanProfile> {
     }
 }
 
-fn resolve_tenant_id(cli: &Cli, config: &DiscoverConfig) -> anyhow::Result<TenantId> {
-    let raw = cli.tenant_id.as_deref().unwrap_or(&config.tenant_id);
+fn resolve_tenant_id(
+    cli_tenant_id: Option<&str>,
+    config: &DiscoverConfig,
+) -> anyhow::Result<TenantId> {
+    let raw = cli_tenant_id.unwrap_or(&config.tenant_id);
     if raw.is_empty() {
         anyhow::bail!("Tenant ID required: set --tenant-id or discover.tenant_id in config");
     }
@@ -130,7 +173,7 @@ fn load_graph_config(file_prefix: &str) -> GraphConfig {
         )
         .build();
 
-    match cfg {
+    let mut graph_config = match cfg {
         Ok(c) => GraphConfig {
             uri: c
                 .get_string("neo4j.uri")
@@ -141,8 +184,50 @@ fn load_graph_config(file_prefix: &str) -> GraphConfig {
             password: c
                 .get_string("neo4j.password")
                 .unwrap_or_else(|_| "sentinel-dev".to_string()),
+            tls_ca_cert: c.get_string("neo4j.tls_ca_cert_path").ok().map(PathBuf::from),
+            tls_client_cert: c.get_string("neo4j.tls_client_cert_path").ok().map(PathBuf::from),
+            tls_client_key: c.get_string("neo4j.tls_client_key_path").ok().map(PathBuf::from),
+            tls_verify_hostname: c.get_bool("neo4j.tls_verify_hostname").unwrap_or(true),
             ..Default::default()
         },
         Err(_) => GraphConfig::default(),
+    };
+
+    apply_graph_env_overrides(&mut graph_config);
+    graph_config
+}
+
+/// Overlay credential/TLS settings read directly from the process
+/// environment, bypassing `config`/`sentinel.toml` entirely, so these
+/// never need to be written to the config file (or accidentally committed
+/// alongside it).
+fn apply_graph_env_overrides(graph_config: &mut GraphConfig) {
+    if let Ok(password) = std::env::var("SENTINEL_NEO4J_PASSWORD") {
+        graph_config.password = password;
+    }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CA_CERT_PATH") {
+        graph_config.tls_ca_cert = Some(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CLIENT_CERT_PATH") {
+        graph_config.tls_client_cert = Some(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CLIENT_KEY_PATH") {
+        graph_config.tls_client_key = Some(PathBuf::from(path));
+    }
+}
+
+fn load_otel_config(file_prefix: &str) -> OtelConfig {
+    let cfg = config::Config::builder()
+        .add_source(config::File::with_name(file_prefix).required(false))
+        .add_source(
+            config::Environment::with_prefix("SENTINEL")
+                .separator("__")
+                .try_parsing(true),
+        )
+        .build();
+
+    match cfg.and_then(|c| c.get::<OtelConfig>("otel")) {
+        Ok(c) => c,
+        Err(_) => OtelConfig::default(),
     }
 }
@@ -0,0 +1,198 @@
+//! Batched background graph-consistency sweep.
+//!
+//! `run_single_scan` only diffs and persists the region it just scanned; it
+//! has no business paying for a whole-tenant consistency pass on every scan
+//! tick; especially with many subnets on short intervals, that would repeat
+//! expensive graph-wide work far more often than the graph actually changes
+//! in ways worth re-checking. Instead, each scan marks the regions it
+//! touched as dirty in a shared [`DirtySet`], and [`run_maintenance_loop`]
+//! drains that set on its own, separate ticker and runs one consolidated
+//! pass — duplicate edges, dangling edge references, and reachability
+//! cycles (via `sentinel_pathfind::scc`) — over the whole tenant graph,
+//! emitting a single tracing summary rather than validating per-edge on
+//! every persist.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tokio::time::{interval, Duration};
+
+use sentinel_core::types::TenantId;
+use sentinel_graph::queries::{EdgeRecord, NodeRecord};
+use sentinel_graph::GraphClient;
+use sentinel_pathfind::graph::InMemoryGraph;
+use sentinel_pathfind::scc;
+
+/// Shared work set of region identifiers (subnet CIDRs) that have changed
+/// since the last consistency sweep. Cheap to mark dirty from the per-scan
+/// hot path; drained in bulk by the maintenance task.
+#[derive(Default)]
+pub struct DirtySet {
+    regions: Mutex<HashSet<String>>,
+}
+
+impl DirtySet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a region as having changed since the last sweep.
+    pub async fn mark_dirty(&self, region: &str) {
+        self.regions.lock().await.insert(region.to_string());
+    }
+
+    /// Take and clear every region marked dirty so far.
+    async fn drain(&self) -> HashSet<String> {
+        std::mem::take(&mut *self.regions.lock().await)
+    }
+}
+
+/// Summary of one consistency sweep, emitted as a single tracing event
+/// rather than one log line per finding.
+#[derive(Debug, Default)]
+struct ConsistencyReport {
+    dirty_regions: usize,
+    duplicate_edges: usize,
+    dangling_edges: usize,
+    reachability_loops: usize,
+}
+
+/// Run the background consistency sweep until the process shuts down.
+///
+/// Ticks every `interval_secs`; a tick is a no-op (beyond logging) if no
+/// region has been marked dirty since the last one. On a non-empty tick,
+/// fetches the whole tenant graph once and runs every check against that
+/// single snapshot.
+pub async fn run_maintenance_loop(
+    graph: GraphClient,
+    tenant_id: TenantId,
+    dirty: Arc<DirtySet>,
+    interval_secs: u64,
+    node_limit: u32,
+) {
+    let mut ticker = interval(Duration::from_secs(interval_secs));
+
+    loop {
+        ticker.tick().await;
+
+        let dirty_regions = dirty.drain().await;
+        if dirty_regions.is_empty() {
+            continue;
+        }
+
+        let subgraph = match graph.fetch_subgraph(&tenant_id, node_limit, node_limit * 5).await {
+            Ok(subgraph) => subgraph,
+            Err(e) => {
+                tracing::warn!(error = %e, "Consistency sweep failed to fetch tenant graph");
+                continue;
+            }
+        };
+
+        let (duplicate_edges, dangling_edges) = count_duplicate_and_dangling_edges(&subgraph.nodes, &subgraph.edges);
+
+        let in_memory = InMemoryGraph::from_subgraph(subgraph.nodes, subgraph.edges);
+        let reachability_loops = scc::detect_reachability_loops(&in_memory).len();
+
+        let report = ConsistencyReport {
+            dirty_regions: dirty_regions.len(),
+            duplicate_edges,
+            dangling_edges,
+            reachability_loops,
+        };
+
+        tracing::info!(
+            dirty_regions = report.dirty_regions,
+            duplicate_edges = report.duplicate_edges,
+            dangling_edges = report.dangling_edges,
+            reachability_loops = report.reachability_loops,
+            "Graph consistency sweep complete"
+        );
+    }
+}
+
+/// Count edges referencing a node ID not present in `nodes` ("dangling"),
+/// and edges that are exact (source, target, type) duplicates of another
+/// edge. `InMemoryGraph::from_subgraph` silently drops dangling edges when
+/// building its adjacency list, so this check has to run against the raw
+/// subgraph beforehand to catch them at all.
+fn count_duplicate_and_dangling_edges(nodes: &[NodeRecord], edges: &[EdgeRecord]) -> (usize, usize) {
+    let node_ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let mut seen_edges: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let mut dangling_edges = 0;
+
+    for edge in edges {
+        if !node_ids.contains(edge.source_id.as_str()) || !node_ids.contains(edge.target_id.as_str()) {
+            dangling_edges += 1;
+            continue;
+        }
+        *seen_edges
+            .entry((edge.source_id.as_str(), edge.target_id.as_str(), edge.edge_type.as_str()))
+            .or_insert(0) += 1;
+    }
+
+    let duplicate_edges = seen_edges.values().filter(|&&count| count > 1).count();
+    (duplicate_edges, dangling_edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str) -> NodeRecord {
+        NodeRecord {
+            id: id.to_string(),
+            label: "Host".to_string(),
+            tenant_id: "t1".to_string(),
+            properties: serde_json::json!({}),
+        }
+    }
+
+    fn edge(id: &str, source: &str, target: &str, edge_type: &str) -> EdgeRecord {
+        EdgeRecord {
+            id: id.to_string(),
+            edge_type: edge_type.to_string(),
+            source_id: source.to_string(),
+            target_id: target.to_string(),
+            properties: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn counts_zero_for_a_clean_graph() {
+        let nodes = vec![node("a"), node("b")];
+        let edges = vec![edge("e1", "a", "b", "CONNECTS_TO")];
+
+        assert_eq!(count_duplicate_and_dangling_edges(&nodes, &edges), (0, 0));
+    }
+
+    #[test]
+    fn detects_a_dangling_edge() {
+        let nodes = vec![node("a")];
+        let edges = vec![edge("e1", "a", "missing", "CONNECTS_TO")];
+
+        assert_eq!(count_duplicate_and_dangling_edges(&nodes, &edges), (0, 1));
+    }
+
+    #[test]
+    fn detects_a_duplicate_edge() {
+        let nodes = vec![node("a"), node("b")];
+        let edges = vec![
+            edge("e1", "a", "b", "CONNECTS_TO"),
+            edge("e2", "a", "b", "CONNECTS_TO"),
+        ];
+
+        assert_eq!(count_duplicate_and_dangling_edges(&nodes, &edges), (1, 0));
+    }
+
+    #[test]
+    fn does_not_confuse_different_edge_types_between_the_same_nodes() {
+        let nodes = vec![node("a"), node("b")];
+        let edges = vec![
+            edge("e1", "a", "b", "CONNECTS_TO"),
+            edge("e2", "a", "b", "HAS_ACCESS"),
+        ];
+
+        assert_eq!(count_duplicate_and_dangling_edges(&nodes, &edges), (0, 0));
+    }
+}
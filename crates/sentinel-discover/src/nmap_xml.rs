@@ -80,6 +80,18 @@ pub struct NmapPort {
     pub port_id: u16,
     pub state: PortState,
     pub service: Option<NmapService>,
+    /// NSE script results for this port (e.g. `vulners`, `ssl-cert`).
+    #[serde(rename = "script", default)]
+    pub scripts: Vec<NmapScript>,
+}
+
+/// Result of an NSE (Nmap Scripting Engine) script run against a port.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NmapScript {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@output")]
+    pub output: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -100,6 +112,10 @@ pub struct NmapService {
     pub version: Option<String>,
     #[serde(rename = "@extrainfo")]
     pub extra_info: Option<String>,
+    /// CPE identifiers nmap matched for this service, e.g.
+    /// `cpe:/a:openbsd:openssh:9.6`.
+    #[serde(rename = "cpe", default)]
+    pub cpes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -314,6 +330,37 @@ mod tests {
         assert_eq!(result.hosts.len(), 0);
     }
 
+    #[test]
+    fn test_parse_scripts_and_cpes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up" reason="syn-ack"/>
+    <address addr="10.0.1.1" addrtype="ipv4"/>
+    <ports>
+      <port protocol="tcp" portid="22">
+        <state state="open" reason="syn-ack"/>
+        <service name="ssh" product="OpenSSH" version="9.6">
+          <cpe>cpe:/a:openbsd:openssh:9.6</cpe>
+        </service>
+        <script id="vulners" output="CVE-2023-1234  7.5  https://vulners.com/cve/CVE-2023-1234"/>
+      </port>
+    </ports>
+  </host>
+</nmaprun>"#;
+
+        let result = parse_nmap_xml(xml.as_bytes()).unwrap();
+        let port = &result.hosts[0].ports.as_ref().unwrap().ports[0];
+
+        let svc = port.service.as_ref().unwrap();
+        assert_eq!(svc.cpes, vec!["cpe:/a:openbsd:openssh:9.6".to_string()]);
+
+        assert_eq!(port.scripts.len(), 1);
+        assert_eq!(port.scripts[0].id, "vulners");
+        assert!(port.scripts[0].output.contains("CVE-2023-1234"));
+    }
+
     #[test]
     fn test_host_without_hostname() {
         let host = NmapHost {
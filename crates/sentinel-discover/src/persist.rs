@@ -1,7 +1,8 @@
 //! Graph persistence: upsert discovered hosts, services, ports, and edges.
 
 use chrono::{TimeDelta, Utc};
-use sentinel_core::types::{Node, TenantId};
+use sentinel_core::events::{EventPayload, EventSource, SentinelEvent};
+use sentinel_core::types::{EdgeType, TenantId};
 use sentinel_graph::GraphClient;
 
 use crate::diff::{DiffResult, DiscoveredHost};
@@ -38,19 +39,63 @@ pub async fn persist_diff(
         }
     }
 
+    for decision in &diff.field_decisions {
+        tracing::debug!(
+            ip = %decision.ip,
+            field = %decision.field,
+            winner = %decision.winner_writer_id,
+            "CRDT field conflict resolved"
+        );
+    }
+
+    for discovered in diff.new_hosts.iter().chain(&diff.changed_hosts) {
+        for edge in &discovered.edges {
+            if edge.edge_type != EdgeType::ExposedExternally {
+                continue;
+            }
+            let Some(external_ip) = edge.properties.external_ip.clone() else {
+                continue;
+            };
+            let Some(external_port) = edge.properties.external_port else {
+                continue;
+            };
+            let service_port = edge.properties.port.unwrap_or_default();
+
+            tracing::debug!(
+                event = ?SentinelEvent::new(
+                    tenant_id.clone(),
+                    EventSource::Discover,
+                    EventPayload::ExternalExposureDetected {
+                        node_id: edge.source_id.clone(),
+                        external_ip,
+                        external_port,
+                        service_port,
+                    },
+                ),
+                "External exposure detected"
+            );
+        }
+    }
+
     Ok(())
 }
 
 /// Persist a single discovered host with all its ports, services, and edges.
+///
+/// Each node is upserted with the [`DiscoveredHost`]'s already-resolved
+/// CRDT version for its field group — `compute_diff` decided the winner,
+/// so this is a plain write, not a conditional one.
 async fn persist_discovered_host(graph: &GraphClient, discovered: &DiscoveredHost) -> Result<()> {
-    graph.upsert_host(&discovered.host).await?;
+    graph
+        .upsert_host(&discovered.host, &discovered.host_versions)
+        .await?;
 
-    for port in &discovered.ports {
-        graph.upsert_node(&Node::Port(port.clone())).await?;
+    for (port, version) in discovered.ports.iter().zip(&discovered.port_versions) {
+        graph.upsert_port(port, version).await?;
     }
 
-    for svc in &discovered.services {
-        graph.upsert_service(svc).await?;
+    for (svc, version) in discovered.services.iter().zip(&discovered.service_versions) {
+        graph.upsert_service(svc, version).await?;
     }
 
     for edge in &discovered.edges {
@@ -0,0 +1,279 @@
+//! Criticality-weighted scan ordering.
+//!
+//! Orders scan targets with an Efraimidis–Spirakis weighted shuffle so
+//! the most important targets are probed first each cycle, without ever
+//! fully starving the low-priority ones: each candidate draws a random
+//! key `u^(1/w)` for its weight `w` and a uniform `u` in `(0, 1]`, then
+//! candidates are sorted descending by key. This is the same A-Res trick
+//! `sentinel_pathfind::algorithms::sample_paths` uses for weighted path
+//! sampling, applied here to scan order instead of graph walks.
+
+use std::collections::HashSet;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+use ipnet::IpNet;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use sentinel_core::types::{Criticality, TenantId};
+use sentinel_graph::GraphClient;
+
+use crate::config::SubnetSchedule;
+use crate::error::Result;
+
+/// A target competing for scan priority, together with the signals its
+/// weight is derived from.
+#[derive(Debug, Clone)]
+pub struct ScanCandidate<T> {
+    pub item: T,
+    /// Highest known criticality among hosts behind this target. Defaults
+    /// to `Criticality::Info` for a target with no prior scan history.
+    pub criticality: Criticality,
+    /// Oldest `last_seen` among hosts behind this target, or `None` if
+    /// it's never been scanned (treated as maximally stale).
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Whether any host behind this target has a known-exploitable
+    /// vulnerability (see `GraphClient::list_exploitable_host_ips`).
+    pub known_exploitable: bool,
+}
+
+/// Weight for a candidate: criticality and a known-exploitable
+/// vulnerability both multiply the base weight up, and staleness (time
+/// since last scanned, capped at a week) tapers it further upward so
+/// overdue targets don't get stuck at the back of the line forever. The
+/// floor keeps every candidate winnable, just less likely to.
+pub fn candidate_weight<T>(candidate: &ScanCandidate<T>, now: DateTime<Utc>) -> f64 {
+    let criticality_score = match &candidate.criticality {
+        Criticality::Critical => 8.0,
+        Criticality::High => 4.0,
+        Criticality::Medium => 2.0,
+        Criticality::Low => 1.0,
+        Criticality::Info => 0.5,
+    };
+
+    let exploitable_bonus = if candidate.known_exploitable { 2.0 } else { 1.0 };
+
+    let staleness_hours = candidate
+        .last_seen
+        .map(|seen| (now - seen).num_seconds().max(0) as f64 / 3600.0)
+        .unwrap_or(168.0);
+    let recency_factor = 1.0 + staleness_hours.min(168.0) / 168.0;
+
+    (criticality_score * exploitable_bonus * recency_factor).max(1e-6)
+}
+
+/// Order `candidates` via a one-pass weighted random shuffle, returning
+/// just the wrapped items in probe order.
+///
+/// The same `seed` always produces the same order for the same
+/// candidates, so scheduling is reproducible in tests; vary the seed
+/// (e.g. by scan round) in production use.
+pub fn weighted_scan_order<T>(
+    candidates: Vec<ScanCandidate<T>>,
+    now: DateTime<Utc>,
+    seed: u64,
+) -> Vec<T> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut keyed: Vec<(f64, T)> = candidates
+        .into_iter()
+        .map(|c| {
+            let weight = candidate_weight(&c, now);
+            (a_res_key(&mut rng, weight), c.item)
+        })
+        .collect();
+
+    keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
+
+/// A-Res key for a candidate: `u^(1/weight)` for `u` uniform in `(0, 1]`.
+/// Higher weight pushes the key toward 1 (preferred), but any candidate
+/// can win.
+fn a_res_key(rng: &mut StdRng, weight: f64) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>(); // uniform in (0, 1], avoids u=0 blowing up the root
+    u.powf(1.0 / weight)
+}
+
+/// Rank a [`Criticality`] for "most severe wins" comparisons.
+fn criticality_rank(c: &Criticality) -> u8 {
+    match c {
+        Criticality::Critical => 4,
+        Criticality::High => 3,
+        Criticality::Medium => 2,
+        Criticality::Low => 1,
+        Criticality::Info => 0,
+    }
+}
+
+/// Build a [`ScanCandidate`] per subnet from hosts already known to the
+/// graph: the subnet's priority is driven by its most critical host, its
+/// least-recently-seen host, and whether any of its hosts carry a known
+/// exploitable vulnerability (see `GraphClient::list_exploitable_host_ips`).
+/// A subnet with no known hosts yet gets `Criticality::Info` and
+/// `last_seen: None`, so it's still eligible but doesn't crowd out subnets
+/// with a real track record.
+pub async fn gather_subnet_candidates(
+    graph: &GraphClient,
+    tenant_id: &TenantId,
+    subnets: Vec<SubnetSchedule>,
+) -> Result<Vec<ScanCandidate<SubnetSchedule>>> {
+    let hosts = graph.list_nodes(tenant_id, "Host", 10_000, 0, None).await?;
+    let exploitable_ips: HashSet<String> =
+        graph.list_exploitable_host_ips(tenant_id).await?.into_iter().collect();
+
+    let mut candidates = Vec::with_capacity(subnets.len());
+    for subnet in subnets {
+        let cidr: Option<IpNet> = subnet.cidr.parse().ok();
+        let in_cidr = |ip_str: &str| match (&cidr, ip_str.parse::<IpAddr>()) {
+            (Some(net), Ok(ip)) => net.contains(&ip),
+            _ => false,
+        };
+
+        let mut criticality = Criticality::Info;
+        let mut last_seen: Option<DateTime<Utc>> = None;
+        let mut known_exploitable = false;
+
+        for host in &hosts {
+            let Some(ip) = host.properties.get("ip").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if !in_cidr(ip) {
+                continue;
+            }
+
+            if let Some(c) = host
+                .properties
+                .get("criticality")
+                .and_then(|v| v.as_str())
+                .and_then(parse_criticality)
+            {
+                if criticality_rank(&c) > criticality_rank(&criticality) {
+                    criticality = c;
+                }
+            }
+
+            if let Some(seen) = host
+                .properties
+                .get("last_seen")
+                .and_then(|v| v.as_str())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+            {
+                last_seen = Some(last_seen.map_or(seen, |current| current.min(seen)));
+            }
+
+            if exploitable_ips.contains(ip) {
+                known_exploitable = true;
+            }
+        }
+
+        candidates.push(ScanCandidate {
+            item: subnet,
+            criticality,
+            last_seen,
+            known_exploitable,
+        });
+    }
+
+    Ok(candidates)
+}
+
+fn parse_criticality(s: &str) -> Option<Criticality> {
+    match s {
+        "critical" => Some(Criticality::Critical),
+        "high" => Some(Criticality::High),
+        "medium" => Some(Criticality::Medium),
+        "low" => Some(Criticality::Low),
+        "info" => Some(Criticality::Info),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn candidate(
+        item: &str,
+        criticality: Criticality,
+        hours_stale: i64,
+        known_exploitable: bool,
+    ) -> ScanCandidate<String> {
+        ScanCandidate {
+            item: item.to_string(),
+            criticality,
+            last_seen: Some(Utc::now() - chrono::Duration::hours(hours_stale)),
+            known_exploitable,
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_reproducible_order() {
+        let now = Utc.timestamp_millis_opt(0).unwrap();
+        let candidates = vec![
+            candidate("a", Criticality::Low, 1, false),
+            candidate("b", Criticality::Critical, 1, false),
+            candidate("c", Criticality::Medium, 1, true),
+        ];
+
+        let first = weighted_scan_order(candidates.clone(), now, 42);
+        let second = weighted_scan_order(candidates, now, 42);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn never_scanned_candidate_is_treated_as_maximally_stale() {
+        let now = Utc::now();
+        let never_scanned = ScanCandidate::<()> {
+            item: (),
+            criticality: Criticality::Low,
+            last_seen: None,
+            known_exploitable: false,
+        };
+        let just_scanned = ScanCandidate::<()> {
+            item: (),
+            criticality: Criticality::Low,
+            last_seen: Some(now),
+            known_exploitable: false,
+        };
+
+        assert!(candidate_weight(&never_scanned, now) > candidate_weight(&just_scanned, now));
+    }
+
+    #[test]
+    fn higher_criticality_wins_first_place_more_often_without_starving_others() {
+        let now = Utc::now();
+        let mut critical_wins = 0;
+        let trials = 200;
+
+        for seed in 0..trials {
+            let candidates = vec![
+                candidate("critical", Criticality::Critical, 1, false),
+                candidate("low-a", Criticality::Low, 1, false),
+                candidate("low-b", Criticality::Low, 1, false),
+                candidate("low-c", Criticality::Low, 1, false),
+            ];
+            let order = weighted_scan_order(candidates, now, seed);
+            if order[0] == "critical" {
+                critical_wins += 1;
+            }
+        }
+
+        // Heavily favored, but not guaranteed -- proves low-criticality
+        // targets still get a non-zero chance of going first.
+        assert!(critical_wins > trials / 2);
+        assert!(critical_wins < trials);
+    }
+
+    #[test]
+    fn known_exploitable_increases_weight() {
+        let now = Utc::now();
+        let exploitable = candidate("a", Criticality::Medium, 1, true);
+        let not_exploitable = candidate("b", Criticality::Medium, 1, false);
+
+        assert!(candidate_weight(&exploitable, now) > candidate_weight(&not_exploitable, now));
+    }
+}
@@ -3,18 +3,21 @@
 //! Spawns one tokio task per configured subnet, each running periodic scans
 //! at the configured interval. A semaphore limits concurrent nmap processes.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use tokio::sync::Semaphore;
 use tokio::time::{interval, Duration};
 
+use sentinel_core::events::{EventPayload, EventSource, SentinelEvent};
 use sentinel_core::types::TenantId;
 use sentinel_graph::GraphClient;
 
 use crate::config::{DiscoverConfig, ScanProfile, SubnetSchedule};
+use crate::coordination::ScanCoordinator;
 use crate::error::Result;
+use crate::maintenance::{self, DirtySet};
 use crate::scanner::NmapScanner;
-use crate::{diff, engram, persist};
+use crate::{diff, engram, exposure, persist, priority};
 
 /// The scheduler manages periodic scan jobs for multiple subnets.
 pub struct ScanScheduler {
@@ -23,6 +26,8 @@ pub struct ScanScheduler {
     graph: GraphClient,
     tenant_id: TenantId,
     concurrency: Arc<Semaphore>,
+    coordinator: Arc<Mutex<ScanCoordinator>>,
+    dirty: Arc<DirtySet>,
 }
 
 impl ScanScheduler {
@@ -33,40 +38,83 @@ impl ScanScheduler {
         tenant_id: TenantId,
     ) -> Self {
         let concurrency = Arc::new(Semaphore::new(config.max_concurrent_scans));
+        let coordinator = Arc::new(Mutex::new(ScanCoordinator::new(
+            config.writer_id.clone(),
+            config.scan_capacity,
+            config.bucket_prefix_len,
+            chrono::Duration::seconds(config.heartbeat_ttl_secs as i64),
+        )));
         Self {
             config,
             scanner: Arc::new(scanner),
             graph,
             tenant_id,
             concurrency,
+            coordinator,
+            dirty: Arc::new(DirtySet::new()),
         }
     }
 
     /// Run the scheduler, spawning a tokio task per subnet.
     /// Blocks indefinitely until all tasks complete or the runtime shuts down.
+    ///
+    /// Subnets are spawned in criticality-weighted order (see
+    /// `crate::priority`) so the most important, most overdue subnets start
+    /// scanning first each time the scheduler comes up; if the graph can't
+    /// be queried for priority signals, falls back to config order rather
+    /// than failing the whole scheduler.
     pub async fn run(&self) -> Result<()> {
         let mut handles = Vec::new();
 
-        for subnet in &self.config.subnets {
-            if !subnet.enabled {
-                tracing::info!(cidr = %subnet.cidr, "Subnet disabled, skipping");
-                continue;
+        let (enabled, disabled): (Vec<SubnetSchedule>, Vec<SubnetSchedule>) =
+            self.config.subnets.iter().cloned().partition(|s| s.enabled);
+
+        for subnet in &disabled {
+            tracing::info!(cidr = %subnet.cidr, "Subnet disabled, skipping");
+        }
+
+        let now = chrono::Utc::now();
+        let ordered_subnets = match priority::gather_subnet_candidates(
+            &self.graph,
+            &self.tenant_id,
+            enabled.clone(),
+        )
+        .await
+        {
+            Ok(candidates) => priority::weighted_scan_order(candidates, now, now.timestamp_millis() as u64),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to compute scan priority order, falling back to config order");
+                enabled
             }
+        };
 
+        for subnet in ordered_subnets {
             let scanner = self.scanner.clone();
             let graph = self.graph.clone();
             let tenant_id = self.tenant_id.clone();
             let config = self.config.clone();
-            let subnet = subnet.clone();
             let semaphore = self.concurrency.clone();
+            let coordinator = self.coordinator.clone();
+            let dirty = self.dirty.clone();
 
             let handle = tokio::spawn(async move {
-                run_subnet_loop(scanner, graph, tenant_id, config, subnet, semaphore).await;
+                run_subnet_loop(
+                    scanner, graph, tenant_id, config, subnet, semaphore, coordinator, dirty,
+                )
+                .await;
             });
             handles.push(handle);
         }
 
-        tracing::info!(subnet_count = handles.len(), "Scheduler started");
+        handles.push(tokio::spawn(maintenance::run_maintenance_loop(
+            self.graph.clone(),
+            self.tenant_id.clone(),
+            self.dirty.clone(),
+            self.config.maintenance_interval_secs,
+            self.config.maintenance_node_limit,
+        )));
+
+        tracing::info!(subnet_count = handles.len() - 1, "Scheduler started");
 
         for handle in handles {
             if let Err(e) = handle.await {
@@ -79,6 +127,7 @@ impl ScanScheduler {
 }
 
 /// Per-subnet scan loop with configurable interval.
+#[allow(clippy::too_many_arguments)]
 async fn run_subnet_loop(
     scanner: Arc<NmapScanner>,
     graph: GraphClient,
@@ -86,6 +135,8 @@ async fn run_subnet_loop(
     config: DiscoverConfig,
     subnet: SubnetSchedule,
     semaphore: Arc<Semaphore>,
+    coordinator: Arc<Mutex<ScanCoordinator>>,
+    dirty: Arc<DirtySet>,
 ) {
     let profile = subnet
         .profile
@@ -107,6 +158,8 @@ async fn run_subnet_loop(
             &config,
             &subnet.cidr,
             &profile,
+            &coordinator,
+            &dirty,
         )
         .await
         {
@@ -116,6 +169,13 @@ async fn run_subnet_loop(
 }
 
 /// Execute a single scan: nmap → parse → diff → persist → engram.
+///
+/// `coordinator` partitions ownership of `target`'s buckets across
+/// cooperating workers (see `crate::coordination`); pass `None` to diff and
+/// persist every discovered host unconditionally, as a single-worker
+/// deployment does. Marks `target` dirty in `dirty` on a successful persist
+/// so the next maintenance sweep re-checks it (see `crate::maintenance`).
+#[allow(clippy::too_many_arguments)]
 pub async fn run_single_scan(
     scanner: &NmapScanner,
     graph: &GraphClient,
@@ -123,9 +183,30 @@ pub async fn run_single_scan(
     config: &DiscoverConfig,
     target: &str,
     profile: &ScanProfile,
+    coordinator: &Mutex<ScanCoordinator>,
+    dirty: &DirtySet,
 ) -> Result<()> {
     let mut session = engram::start_scan_session(tenant_id.0, target, profile);
 
+    // Gossip this worker's heartbeat and evict any peers we haven't heard
+    // from within the TTL, then snapshot ownership so everything below is
+    // scoped to buckets this worker actually owns.
+    let (ownership, owned_buckets) = {
+        let mut coordinator = coordinator.lock().expect("coordinator lock poisoned");
+        coordinator.evict_stale_peers(chrono::Utc::now());
+        let cidr: Option<ipnet::IpNet> = target.parse().ok();
+        let owned_buckets = cidr
+            .map(|c| coordinator.owned_buckets(&c))
+            .unwrap_or_else(|| vec![target.to_string()]);
+        let heartbeat = coordinator.heartbeat_payload(owned_buckets.clone());
+        tracing::debug!(
+            event = ?SentinelEvent::new(tenant_id.clone(), EventSource::Discover, heartbeat),
+            "Gossiping scanner heartbeat"
+        );
+        (coordinator.snapshot(), owned_buckets)
+    };
+    let scope = owned_buckets.join(",");
+
     // Run nmap.
     let scan_result = match scanner.scan(target, profile).await {
         Ok(r) => r,
@@ -136,15 +217,52 @@ pub async fn run_single_scan(
         }
     };
 
+    tracing::debug!(
+        event = ?SentinelEvent::new(
+            tenant_id.clone(),
+            EventSource::Discover,
+            EventPayload::ScanStarted {
+                scan_id: scan_result.scan_id,
+                scan_type: format!("{profile:?}"),
+                target: scope.clone(),
+            },
+        ),
+        "Scan started"
+    );
+
     // Parse results into sentinel-core types.
     let now = chrono::Utc::now();
-    let discovered = diff::parse_scan_results(&scan_result.nmap_run, tenant_id, now);
+    let discovered =
+        diff::parse_scan_results(&scan_result.nmap_run, tenant_id, now, &config.writer_id);
 
-    // Diff against current graph state.
-    let diff_result = diff::compute_diff(graph, tenant_id, discovered, target).await?;
+    // Diff against current graph state, scoped to owned buckets.
+    let mut diff_result =
+        diff::compute_diff(graph, tenant_id, discovered, target, Some(&ownership)).await?;
+
+    // Probe newly-seen and changed services for external (NAT-traversing)
+    // reachability; a no-op unless `stun_reflectors` is configured. Confirmed
+    // mappings are attached as `ExposedExternally` edges, which `persist_diff`
+    // upserts and emits `ExternalExposureDetected` for below.
+    exposure::detect_exposure(
+        &mut diff_result.new_hosts,
+        tenant_id,
+        &config.stun_reflectors,
+        Duration::from_millis(config.stun_timeout_ms),
+        now,
+    )
+    .await;
+    exposure::detect_exposure(
+        &mut diff_result.changed_hosts,
+        tenant_id,
+        &config.stun_reflectors,
+        Duration::from_millis(config.stun_timeout_ms),
+        now,
+    )
+    .await;
 
     // Persist to Neo4j.
     persist::persist_diff(graph, tenant_id, &diff_result, config.stale_threshold_hours).await?;
+    dirty.mark_dirty(target).await;
 
     // Record in Engram.
     engram::record_scan_results(
@@ -154,9 +272,24 @@ pub async fn run_single_scan(
     );
     engram::finalize_and_store(session, &config.engram_dir);
 
+    tracing::debug!(
+        event = ?SentinelEvent::new(
+            tenant_id.clone(),
+            EventSource::Discover,
+            EventPayload::ScanCompleted {
+                scan_id: scan_result.scan_id,
+                nodes_found: diff_result.summary.new_count,
+                nodes_updated: diff_result.summary.changed_count,
+                nodes_stale: diff_result.summary.stale_count,
+                duration_ms: scan_result.duration.as_millis() as u64,
+            },
+        ),
+        "Scan completed"
+    );
+
     tracing::info!(
         scan_id = %scan_result.scan_id,
-        target = %target,
+        target = %scope,
         new = diff_result.summary.new_count,
         changed = diff_result.summary.changed_count,
         stale = diff_result.summary.stale_count,
@@ -0,0 +1,225 @@
+//! Minimal client-side codec for STUN (RFC 5389) binding requests and
+//! responses — just enough to learn a UDP socket's server-reflexive
+//! (public) address. See [`crate::exposure`] for the I/O that uses this.
+
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+/// STUN magic cookie (RFC 5389 §6).
+const MAGIC_COOKIE: u32 = 0x2112_A442;
+const BINDING_REQUEST: u16 = 0x0001;
+const BINDING_SUCCESS_RESPONSE: u16 = 0x0101;
+const ATTR_MAPPED_ADDRESS: u16 = 0x0001;
+const ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+
+/// Build a STUN binding request with the given 96-bit transaction ID and no
+/// attributes.
+pub fn build_binding_request(transaction_id: [u8; 12]) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(20);
+    msg.extend_from_slice(&BINDING_REQUEST.to_be_bytes());
+    msg.extend_from_slice(&0u16.to_be_bytes()); // message length: no attributes
+    msg.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+    msg.extend_from_slice(&transaction_id);
+    msg
+}
+
+/// Parse a STUN binding response, returning the reflexive address it
+/// reports. Prefers `XOR-MAPPED-ADDRESS` (RFC 5389) over the legacy
+/// `MAPPED-ADDRESS` (RFC 3489) when both are present. IPv4 only. Returns
+/// `None` if `data` isn't a well-formed binding success response matching
+/// `expected_transaction_id`.
+pub fn parse_binding_response(
+    data: &[u8],
+    expected_transaction_id: [u8; 12],
+) -> Option<SocketAddr> {
+    if data.len() < 20 {
+        return None;
+    }
+
+    let message_type = u16::from_be_bytes([data[0], data[1]]);
+    if message_type != BINDING_SUCCESS_RESPONSE {
+        return None;
+    }
+
+    let message_length = u16::from_be_bytes([data[2], data[3]]) as usize;
+    let magic_cookie = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+    if magic_cookie != MAGIC_COOKIE {
+        return None;
+    }
+
+    if data[8..20] != expected_transaction_id {
+        return None;
+    }
+
+    if data.len() < 20 + message_length {
+        return None;
+    }
+
+    let mut offset = 20;
+    let end = 20 + message_length;
+    let mut mapped_address = None;
+    let mut xor_mapped_address = None;
+
+    while offset + 4 <= end {
+        let attr_type = u16::from_be_bytes([data[offset], data[offset + 1]]);
+        let attr_len = u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let value_start = offset + 4;
+        let value_end = value_start + attr_len;
+        if value_end > data.len() {
+            break;
+        }
+        let value = &data[value_start..value_end];
+
+        match attr_type {
+            ATTR_MAPPED_ADDRESS => mapped_address = parse_mapped_address(value),
+            ATTR_XOR_MAPPED_ADDRESS => xor_mapped_address = parse_xor_mapped_address(value),
+            _ => {}
+        }
+
+        // Attributes are padded to a 4-byte boundary.
+        offset = value_end + ((4 - (attr_len % 4)) % 4);
+    }
+
+    xor_mapped_address.or(mapped_address)
+}
+
+fn parse_mapped_address(value: &[u8]) -> Option<SocketAddr> {
+    if value.len() < 8 || value[1] != 0x01 {
+        return None; // only IPv4 family (0x01) is supported
+    }
+    let port = u16::from_be_bytes([value[2], value[3]]);
+    let ip = Ipv4Addr::new(value[4], value[5], value[6], value[7]);
+    Some(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+}
+
+fn parse_xor_mapped_address(value: &[u8]) -> Option<SocketAddr> {
+    if value.len() < 8 || value[1] != 0x01 {
+        return None; // only IPv4 family (0x01) is supported
+    }
+    let cookie = MAGIC_COOKIE.to_be_bytes();
+    let port = u16::from_be_bytes([value[2] ^ cookie[0], value[3] ^ cookie[1]]);
+    let ip = Ipv4Addr::new(
+        value[4] ^ cookie[0],
+        value[5] ^ cookie[1],
+        value[6] ^ cookie[2],
+        value[7] ^ cookie[3],
+    );
+    Some(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_attr(attr_type: u16, value: &[u8]) -> Vec<u8> {
+        let mut attr = Vec::new();
+        attr.extend_from_slice(&attr_type.to_be_bytes());
+        attr.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        attr.extend_from_slice(value);
+        while attr.len() % 4 != 0 {
+            attr.push(0);
+        }
+        attr
+    }
+
+    fn success_response(transaction_id: [u8; 12], attrs: &[u8]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&BINDING_SUCCESS_RESPONSE.to_be_bytes());
+        msg.extend_from_slice(&(attrs.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        msg.extend_from_slice(&transaction_id);
+        msg.extend_from_slice(attrs);
+        msg
+    }
+
+    fn xor_mapped_address_attr(addr: SocketAddrV4) -> Vec<u8> {
+        let cookie = MAGIC_COOKIE.to_be_bytes();
+        let octets = addr.ip().octets();
+        let mut value = vec![0x00, 0x01];
+        value.extend_from_slice(&(addr.port() ^ u16::from_be_bytes([cookie[0], cookie[1]])).to_be_bytes());
+        value.push(octets[0] ^ cookie[0]);
+        value.push(octets[1] ^ cookie[1]);
+        value.push(octets[2] ^ cookie[2]);
+        value.push(octets[3] ^ cookie[3]);
+        encode_attr(ATTR_XOR_MAPPED_ADDRESS, &value)
+    }
+
+    fn mapped_address_attr(addr: SocketAddrV4) -> Vec<u8> {
+        let octets = addr.ip().octets();
+        let mut value = vec![0x00, 0x01];
+        value.extend_from_slice(&addr.port().to_be_bytes());
+        value.extend_from_slice(&octets);
+        encode_attr(ATTR_MAPPED_ADDRESS, &value)
+    }
+
+    #[test]
+    fn build_binding_request_has_expected_header() {
+        let tid = [1u8; 12];
+        let req = build_binding_request(tid);
+        assert_eq!(req.len(), 20);
+        assert_eq!(u16::from_be_bytes([req[0], req[1]]), BINDING_REQUEST);
+        assert_eq!(u16::from_be_bytes([req[2], req[3]]), 0);
+        assert_eq!(
+            u32::from_be_bytes([req[4], req[5], req[6], req[7]]),
+            MAGIC_COOKIE
+        );
+        assert_eq!(&req[8..20], &tid);
+    }
+
+    #[test]
+    fn parses_xor_mapped_address() {
+        let tid = [2u8; 12];
+        let addr: SocketAddrV4 = "203.0.113.7:51234".parse().unwrap();
+        let response = success_response(tid, &xor_mapped_address_attr(addr));
+
+        let parsed = parse_binding_response(&response, tid).unwrap();
+        assert_eq!(parsed, SocketAddr::V4(addr));
+    }
+
+    #[test]
+    fn parses_legacy_mapped_address() {
+        let tid = [3u8; 12];
+        let addr: SocketAddrV4 = "198.51.100.22:8080".parse().unwrap();
+        let response = success_response(tid, &mapped_address_attr(addr));
+
+        let parsed = parse_binding_response(&response, tid).unwrap();
+        assert_eq!(parsed, SocketAddr::V4(addr));
+    }
+
+    #[test]
+    fn prefers_xor_mapped_address_over_legacy() {
+        let tid = [4u8; 12];
+        let xor_addr: SocketAddrV4 = "203.0.113.7:51234".parse().unwrap();
+        let legacy_addr: SocketAddrV4 = "198.51.100.22:8080".parse().unwrap();
+        let mut attrs = mapped_address_attr(legacy_addr);
+        attrs.extend_from_slice(&xor_mapped_address_attr(xor_addr));
+        let response = success_response(tid, &attrs);
+
+        let parsed = parse_binding_response(&response, tid).unwrap();
+        assert_eq!(parsed, SocketAddr::V4(xor_addr));
+    }
+
+    #[test]
+    fn rejects_mismatched_transaction_id() {
+        let addr: SocketAddrV4 = "203.0.113.7:51234".parse().unwrap();
+        let response = success_response([5u8; 12], &xor_mapped_address_attr(addr));
+
+        assert!(parse_binding_response(&response, [6u8; 12]).is_none());
+    }
+
+    #[test]
+    fn rejects_non_success_message_type() {
+        let tid = [7u8; 12];
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&BINDING_REQUEST.to_be_bytes());
+        msg.extend_from_slice(&0u16.to_be_bytes());
+        msg.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        msg.extend_from_slice(&tid);
+
+        assert!(parse_binding_response(&msg, tid).is_none());
+    }
+
+    #[test]
+    fn rejects_truncated_message() {
+        assert!(parse_binding_response(&[0u8; 10], [0u8; 12]).is_none());
+    }
+}
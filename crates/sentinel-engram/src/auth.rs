@@ -0,0 +1,469 @@
+//! Capability-token authorization for scoped engram access.
+//!
+//! `EngramQuery` can already filter by `tenant_id`/`agent_id`, but nothing
+//! stops a caller from simply not setting those filters — any holder of an
+//! `EngramStore` can list everything. This module makes that filtering
+//! enforceable: an issuer signs a compact `Capability` token whose claims
+//! encode a `Scope` (allowed tenant, optional agent glob), a set of
+//! `Permission`s, and a validity window. `AuthorizedEngramStore` wraps any
+//! `EngramStore` and checks the capability on every call, intersecting
+//! `list()` queries with the token's scope rather than trusting the caller.
+//!
+//! Tokens are signed with HMAC-SHA256 over the canonical (JSON) claim
+//! bytes and encoded as `base64(claims).base64(signature)`.
+
+use std::collections::HashSet;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::store::{EngramQuery, EngramStore, StoreError};
+use crate::{Engram, EngramId};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The resource scope a capability token is authorized to act within.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Scope {
+    /// The single tenant this token covers.
+    pub tenant_id: Uuid,
+    /// Optional glob restricting which agents' engrams this scope covers.
+    /// Supports a single trailing `*` wildcard (e.g. `"scanner-*"`);
+    /// `None` means any agent within the tenant.
+    pub agent_glob: Option<String>,
+}
+
+impl Scope {
+    pub fn new(tenant_id: Uuid) -> Self {
+        Self {
+            tenant_id,
+            agent_glob: None,
+        }
+    }
+
+    pub fn with_agent_glob(mut self, glob: impl Into<String>) -> Self {
+        self.agent_glob = Some(glob.into());
+        self
+    }
+
+    /// Whether this scope covers the given tenant/agent combination.
+    pub fn allows(&self, tenant_id: Uuid, agent_id: &str) -> bool {
+        if tenant_id != self.tenant_id {
+            return false;
+        }
+        match &self.agent_glob {
+            Some(glob) => glob_matches(glob, agent_id),
+            None => true,
+        }
+    }
+
+    /// Narrow a caller-supplied query to this scope, rejecting it outright
+    /// if it asks for a tenant or agent the scope doesn't cover.
+    fn intersect(&self, query: &EngramQuery) -> Result<EngramQuery, StoreError> {
+        if let Some(requested_tenant) = query.tenant_id {
+            if requested_tenant != self.tenant_id {
+                return Err(StoreError::Unauthorized);
+            }
+        }
+
+        if let (Some(requested_agent), Some(glob)) = (&query.agent_id, &self.agent_glob) {
+            if !glob_matches(glob, requested_agent) {
+                return Err(StoreError::Unauthorized);
+            }
+        }
+
+        Ok(EngramQuery {
+            tenant_id: Some(self.tenant_id),
+            agent_id: query.agent_id.clone(),
+            session_id: query.session_id,
+            from: query.from,
+            to: query.to,
+        })
+    }
+}
+
+/// Match a glob pattern supporting a single trailing `*` wildcard.
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+/// An action a capability token may permit against an `EngramStore`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Read,
+    List,
+    Write,
+}
+
+/// The signed claims carried by a capability token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Claims {
+    token_id: Uuid,
+    scope: Scope,
+    permissions: HashSet<Permission>,
+    not_before: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl Claims {
+    /// Canonical byte representation signed over. JSON field order is
+    /// stable for a fixed struct layout, so this is deterministic.
+    fn canonical_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("claims are always serializable")
+    }
+}
+
+/// A verified capability: scope, permissions, and validity window, plus
+/// the signature that was checked to produce it. Only `CapabilityAuthority`
+/// can mint these (via `issue`) or reconstruct one from a token string
+/// (via `verify`) — there's no public constructor.
+#[derive(Debug, Clone)]
+pub struct Capability {
+    claims: Claims,
+}
+
+impl Capability {
+    pub fn token_id(&self) -> Uuid {
+        self.claims.token_id
+    }
+
+    pub fn scope(&self) -> &Scope {
+        &self.claims.scope
+    }
+
+    pub fn permissions(&self) -> &HashSet<Permission> {
+        &self.claims.permissions
+    }
+
+    fn ensure_active(&self) -> Result<(), StoreError> {
+        let now = Utc::now();
+        if now < self.claims.not_before || now > self.claims.expires_at {
+            return Err(StoreError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn ensure_permitted(&self, permission: Permission) -> Result<(), StoreError> {
+        if !self.claims.permissions.contains(&permission) {
+            return Err(StoreError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Encode as a compact token string: `base64(claims json).base64(signature)`.
+    fn encode(&self, signature: &[u8]) -> String {
+        let claims_b64 = URL_SAFE_NO_PAD.encode(self.claims.canonical_bytes());
+        let sig_b64 = URL_SAFE_NO_PAD.encode(signature);
+        format!("{claims_b64}.{sig_b64}")
+    }
+}
+
+/// Issues and verifies capability tokens, and tracks revocations by token ID.
+///
+/// Signing uses HMAC-SHA256 over the canonical claim bytes; any party that
+/// also holds `signing_key` can verify tokens independently (e.g. a
+/// read-only replica checking tokens without calling back to the issuer).
+pub struct CapabilityAuthority {
+    signing_key: Vec<u8>,
+    revoked: std::sync::Mutex<HashSet<Uuid>>,
+}
+
+impl CapabilityAuthority {
+    pub fn new(signing_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            signing_key: signing_key.into(),
+            revoked: std::sync::Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts a key of any length")
+    }
+
+    /// Mint a new capability token valid for `ttl` starting now.
+    pub fn issue(&self, scope: Scope, permissions: HashSet<Permission>, ttl: Duration) -> String {
+        let now = Utc::now();
+        let claims = Claims {
+            token_id: Uuid::new_v4(),
+            scope,
+            permissions,
+            not_before: now,
+            expires_at: now + ttl,
+        };
+
+        let mut mac = self.mac();
+        mac.update(&claims.canonical_bytes());
+        let signature = mac.finalize().into_bytes();
+
+        Capability { claims }.encode(&signature)
+    }
+
+    /// Verify a token's signature, expiry window, and revocation status,
+    /// returning the `Capability` it grants.
+    pub fn verify(&self, token: &str) -> Result<Capability, StoreError> {
+        let (claims_b64, sig_b64) = token
+            .split_once('.')
+            .ok_or(StoreError::Unauthorized)?;
+
+        let claims_bytes = URL_SAFE_NO_PAD
+            .decode(claims_b64)
+            .map_err(|_| StoreError::Unauthorized)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(sig_b64)
+            .map_err(|_| StoreError::Unauthorized)?;
+
+        let mut mac = self.mac();
+        mac.update(&claims_bytes);
+        mac.verify_slice(&signature)
+            .map_err(|_| StoreError::Unauthorized)?;
+
+        let claims: Claims =
+            serde_json::from_slice(&claims_bytes).map_err(|_| StoreError::Unauthorized)?;
+
+        if self.revoked.lock().expect("revocation set poisoned").contains(&claims.token_id) {
+            return Err(StoreError::Unauthorized);
+        }
+
+        let capability = Capability { claims };
+        capability.ensure_active()?;
+        Ok(capability)
+    }
+
+    /// Revoke a previously issued token by ID; subsequent `verify` calls for
+    /// it will fail even if it hasn't expired yet.
+    pub fn revoke(&self, token_id: Uuid) {
+        self.revoked.lock().expect("revocation set poisoned").insert(token_id);
+    }
+
+    /// Whether a token ID has been revoked.
+    pub fn is_revoked(&self, token_id: Uuid) -> bool {
+        self.revoked.lock().expect("revocation set poisoned").contains(&token_id)
+    }
+}
+
+/// An `EngramStore` decorator that enforces a `Capability`'s scope and
+/// permissions on every call, re-checking expiry each time so a long-lived
+/// wrapper can't outlive the token it was built from.
+pub struct AuthorizedEngramStore<S: EngramStore> {
+    inner: S,
+    capability: Capability,
+}
+
+impl<S: EngramStore> AuthorizedEngramStore<S> {
+    pub fn new(inner: S, capability: Capability) -> Result<Self, StoreError> {
+        capability.ensure_active()?;
+        Ok(Self { inner, capability })
+    }
+}
+
+impl<S: EngramStore> EngramStore for AuthorizedEngramStore<S> {
+    fn save(&self, engram: &Engram) -> Result<(), StoreError> {
+        self.capability.ensure_active()?;
+        self.capability.ensure_permitted(Permission::Write)?;
+        if !self.capability.scope().allows(engram.tenant_id, &engram.agent_id) {
+            return Err(StoreError::Unauthorized);
+        }
+        self.inner.save(engram)
+    }
+
+    fn get(&self, id: EngramId) -> Result<Engram, StoreError> {
+        self.capability.ensure_active()?;
+        self.capability.ensure_permitted(Permission::Read)?;
+        let engram = self.inner.get(id)?;
+        if !self.capability.scope().allows(engram.tenant_id, &engram.agent_id) {
+            return Err(StoreError::Unauthorized);
+        }
+        Ok(engram)
+    }
+
+    fn list(&self, query: &EngramQuery) -> Result<Vec<Engram>, StoreError> {
+        self.capability.ensure_active()?;
+        self.capability.ensure_permitted(Permission::List)?;
+
+        let scoped_query = self.capability.scope().intersect(query)?;
+        let results = self.inner.list(&scoped_query)?;
+
+        // Belt-and-suspenders: the inner store may not understand agent
+        // globs, so re-check scope on every result rather than trusting
+        // the pushed-down query alone.
+        Ok(results
+            .into_iter()
+            .filter(|e| self.capability.scope().allows(e.tenant_id, &e.agent_id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::EngramSession;
+    use crate::store::GitEngramStore;
+
+    fn permissions(perms: &[Permission]) -> HashSet<Permission> {
+        perms.iter().copied().collect()
+    }
+
+    fn test_engram(tenant_id: Uuid, agent_id: &str) -> Engram {
+        let mut session = EngramSession::new(tenant_id, agent_id, "Test intent");
+        session.add_decision("choice A", "best option", 0.9);
+        session.finalize()
+    }
+
+    #[test]
+    fn verified_capability_roundtrips_scope_and_permissions() {
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let tenant_id = Uuid::new_v4();
+        let token = authority.issue(
+            Scope::new(tenant_id),
+            permissions(&[Permission::Read, Permission::List]),
+            Duration::hours(1),
+        );
+
+        let capability = authority.verify(&token).unwrap();
+        assert_eq!(capability.scope().tenant_id, tenant_id);
+        assert!(capability.permissions().contains(&Permission::Read));
+        assert!(!capability.permissions().contains(&Permission::Write));
+    }
+
+    #[test]
+    fn tampered_token_fails_verification() {
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(
+            Scope::new(Uuid::new_v4()),
+            permissions(&[Permission::Read]),
+            Duration::hours(1),
+        );
+
+        let mut tampered = token.clone();
+        tampered.push('x');
+        assert!(matches!(
+            authority.verify(&tampered),
+            Err(StoreError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn expired_token_fails_verification() {
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(
+            Scope::new(Uuid::new_v4()),
+            permissions(&[Permission::Read]),
+            Duration::seconds(-1),
+        );
+
+        assert!(matches!(
+            authority.verify(&token),
+            Err(StoreError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn revoked_token_fails_verification() {
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(
+            Scope::new(Uuid::new_v4()),
+            permissions(&[Permission::Read]),
+            Duration::hours(1),
+        );
+        let capability = authority.verify(&token).unwrap();
+
+        authority.revoke(capability.token_id());
+        assert!(matches!(
+            authority.verify(&token),
+            Err(StoreError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn authorized_store_rejects_save_outside_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = GitEngramStore::new(dir.path()).unwrap();
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let allowed_tenant = Uuid::new_v4();
+        let token = authority.issue(
+            Scope::new(allowed_tenant),
+            permissions(&[Permission::Write]),
+            Duration::hours(1),
+        );
+        let capability = authority.verify(&token).unwrap();
+        let store = AuthorizedEngramStore::new(inner, capability).unwrap();
+
+        let in_scope = test_engram(allowed_tenant, "agent-a");
+        assert!(store.save(&in_scope).is_ok());
+
+        let out_of_scope = test_engram(Uuid::new_v4(), "agent-a");
+        assert!(matches!(
+            store.save(&out_of_scope),
+            Err(StoreError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn authorized_store_cannot_widen_list_beyond_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_a = Uuid::new_v4();
+        let tenant_b = Uuid::new_v4();
+        inner.save(&test_engram(tenant_a, "agent-a")).unwrap();
+        inner.save(&test_engram(tenant_b, "agent-a")).unwrap();
+
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(
+            Scope::new(tenant_a),
+            permissions(&[Permission::List]),
+            Duration::hours(1),
+        );
+        let capability = authority.verify(&token).unwrap();
+        let store = AuthorizedEngramStore::new(inner, capability).unwrap();
+
+        // Even an unscoped query can only ever see tenant_a's engrams.
+        let results = store.list(&EngramQuery::default()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].tenant_id, tenant_a);
+    }
+
+    #[test]
+    fn authorized_store_enforces_agent_glob() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        inner.save(&test_engram(tenant_id, "scanner-1")).unwrap();
+        inner.save(&test_engram(tenant_id, "hunter-1")).unwrap();
+
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(
+            Scope::new(tenant_id).with_agent_glob("scanner-*"),
+            permissions(&[Permission::List]),
+            Duration::hours(1),
+        );
+        let capability = authority.verify(&token).unwrap();
+        let store = AuthorizedEngramStore::new(inner, capability).unwrap();
+
+        let results = store.list(&EngramQuery::default()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].agent_id, "scanner-1");
+    }
+
+    #[test]
+    fn missing_permission_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let authority = CapabilityAuthority::new(b"test-signing-key".to_vec());
+        let token = authority.issue(Scope::new(tenant_id), permissions(&[Permission::Read]), Duration::hours(1));
+        let capability = authority.verify(&token).unwrap();
+        let store = AuthorizedEngramStore::new(inner, capability).unwrap();
+
+        let result = store.list(&EngramQuery::default());
+        assert!(matches!(result, Err(StoreError::Unauthorized)));
+    }
+}
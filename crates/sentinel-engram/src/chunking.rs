@@ -0,0 +1,479 @@
+//! Content-defined chunking and a content-addressed blob store.
+//!
+//! Engrams embed arbitrary `context` and per-action `details` JSON that
+//! can be large and highly repetitive across a session (the same
+//! environment snapshot or scan output reappearing engram after engram),
+//! yet the plain stores write each engram as one self-contained file with
+//! no sharing between them. `ChunkedEngramStore` externalizes large
+//! values into a content-addressed blob store, deduplicating identical
+//! chunks across engrams; small values stay inline so the common case is
+//! still a single file.
+//!
+//! Chunk boundaries are found with a FastCDC-style rolling hash: a Gear
+//! hash slides byte-by-byte over the value's serialized bytes, and a
+//! boundary is declared when `hash & mask == 0`, subject to hard min/max
+//! chunk sizes so a run of highly compressible or highly random bytes
+//! can't produce pathologically tiny or huge chunks. Each chunk is
+//! content-addressed by its SHA-256 hash and written to
+//! `{root}/chunks/ab/cdef...` only if not already present.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::store::{find_file_recursive, matches_query, EngramQuery, EngramStore, StoreError};
+use crate::{Action, Alternative, Decision, Engram, EngramId};
+
+/// Values whose serialized form is smaller than this stay inline.
+const INLINE_THRESHOLD: usize = 4 * 1024;
+/// Hard lower bound on a content-defined chunk's size.
+const MIN_CHUNK_SIZE: usize = 2 * 1024;
+/// Target average chunk size the boundary mask is tuned for.
+const AVG_CHUNK_SIZE: usize = 8 * 1024;
+/// Hard upper bound on a content-defined chunk's size.
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A mask with `log2(AVG_CHUNK_SIZE)` low bits set: for uniformly
+/// distributed Gear-hash output, `hash & mask == 0` fires with
+/// probability `1 / AVG_CHUNK_SIZE` per byte, giving that average
+/// boundary spacing.
+fn boundary_mask() -> u64 {
+    (AVG_CHUNK_SIZE as u64).next_power_of_two() - 1
+}
+
+/// 256-entry table of fixed pseudo-random 64-bit constants driving the
+/// Gear hash, generated at compile time (via splitmix64) so the chunker
+/// has no external dependency and chunk boundaries are reproducible.
+const GEAR: [u64; 256] = build_gear_table();
+
+const fn build_gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut seed: u64 = 0x9E3779B97F4A7C15;
+    let mut i = 0;
+    while i < 256 {
+        seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        table[i] = z;
+        i += 1;
+    }
+    table
+}
+
+/// Split `data` into content-defined chunks, returning each chunk's
+/// `[start, end)` byte range.
+fn chunk_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let mask = boundary_mask();
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        let pos_in_chunk = i - start;
+        hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+
+        let at_max = pos_in_chunk + 1 >= MAX_CHUNK_SIZE;
+        let is_last_byte = i + 1 == data.len();
+
+        if pos_in_chunk + 1 >= MIN_CHUNK_SIZE && (hash & mask == 0 || at_max) {
+            boundaries.push((start, i + 1));
+            start = i + 1;
+            hash = 0;
+        } else if is_last_byte {
+            boundaries.push((start, i + 1));
+        }
+    }
+
+    boundaries
+}
+
+fn hash_chunk(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Content-addressed store for chunk bytes, deduplicating by SHA-256 hash.
+struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn chunk_path(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[0..2]).join(&hash[2..])
+    }
+
+    /// Write a chunk if it isn't already present, returning its hash.
+    fn put(&self, bytes: &[u8]) -> Result<String, StoreError> {
+        let hash = hash_chunk(bytes);
+        let path = self.chunk_path(&hash);
+        if !path.is_file() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, bytes)?;
+        }
+        Ok(hash)
+    }
+
+    fn get(&self, hash: &str) -> Result<Vec<u8>, StoreError> {
+        fs::read(self.chunk_path(hash)).map_err(|_| StoreError::ChunkMissing(hash.to_string()))
+    }
+}
+
+/// Either a value stored inline, or a reference to the content-defined
+/// chunks it was split into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ChunkedValue {
+    Chunks {
+        #[serde(rename = "$chunks")]
+        chunks: Vec<String>,
+    },
+    Inline(Value),
+}
+
+fn externalize_value(value: &Value, blobs: &BlobStore) -> Result<ChunkedValue, StoreError> {
+    let bytes = serde_json::to_vec(value)?;
+    if bytes.len() <= INLINE_THRESHOLD {
+        return Ok(ChunkedValue::Inline(value.clone()));
+    }
+
+    let chunks = chunk_boundaries(&bytes)
+        .into_iter()
+        .map(|(start, end)| blobs.put(&bytes[start..end]))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ChunkedValue::Chunks { chunks })
+}
+
+fn inline_value(chunked: ChunkedValue, blobs: &BlobStore) -> Result<Value, StoreError> {
+    match chunked {
+        ChunkedValue::Inline(value) => Ok(value),
+        ChunkedValue::Chunks { chunks } => {
+            let mut bytes = Vec::new();
+            for hash in chunks {
+                bytes.extend(blobs.get(&hash)?);
+            }
+            Ok(serde_json::from_slice(&bytes)?)
+        }
+    }
+}
+
+/// On-disk shape of an action, with `details` possibly externalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredAction {
+    action_type: String,
+    description: String,
+    details: ChunkedValue,
+    success: bool,
+    timestamp: DateTime<Utc>,
+}
+
+/// On-disk shape of an engram, with large values replaced by chunk
+/// references. Mirrors [`Engram`], the way [`crate::hash::HashableEngram`]
+/// mirrors it for hashing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredEngram {
+    id: EngramId,
+    tenant_id: Uuid,
+    agent_id: String,
+    intent: String,
+    context: ChunkedValue,
+    decisions: Vec<Decision>,
+    alternatives: Vec<Alternative>,
+    actions: Vec<StoredAction>,
+    started_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    chained_from: Option<String>,
+    content_hash: Option<String>,
+}
+
+impl StoredEngram {
+    fn from_engram(engram: &Engram, blobs: &BlobStore) -> Result<Self, StoreError> {
+        Ok(Self {
+            id: engram.id,
+            tenant_id: engram.tenant_id,
+            agent_id: engram.agent_id.clone(),
+            intent: engram.intent.clone(),
+            context: externalize_value(&engram.context, blobs)?,
+            decisions: engram.decisions.clone(),
+            alternatives: engram.alternatives.clone(),
+            actions: engram
+                .actions
+                .iter()
+                .map(|action| {
+                    Ok(StoredAction {
+                        action_type: action.action_type.clone(),
+                        description: action.description.clone(),
+                        details: externalize_value(&action.details, blobs)?,
+                        success: action.success,
+                        timestamp: action.timestamp,
+                    })
+                })
+                .collect::<Result<Vec<_>, StoreError>>()?,
+            started_at: engram.started_at,
+            completed_at: engram.completed_at,
+            chained_from: engram.chained_from.clone(),
+            content_hash: engram.content_hash.clone(),
+        })
+    }
+
+    fn into_engram(self, blobs: &BlobStore) -> Result<Engram, StoreError> {
+        Ok(Engram {
+            id: self.id,
+            tenant_id: self.tenant_id,
+            agent_id: self.agent_id,
+            intent: self.intent,
+            context: inline_value(self.context, blobs)?,
+            decisions: self.decisions,
+            alternatives: self.alternatives,
+            actions: self
+                .actions
+                .into_iter()
+                .map(|action| {
+                    Ok(Action {
+                        action_type: action.action_type,
+                        description: action.description,
+                        details: inline_value(action.details, blobs)?,
+                        success: action.success,
+                        timestamp: action.timestamp,
+                    })
+                })
+                .collect::<Result<Vec<_>, StoreError>>()?,
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+            chained_from: self.chained_from,
+            content_hash: self.content_hash,
+        })
+    }
+}
+
+/// File-system backed engram store that externalizes large embedded
+/// values into a deduplicated, content-addressed blob store.
+///
+/// Uses the same `{root}/YYYY/MM/DD/{session_id}.json` layout as
+/// `GitEngramStore`, plus `{root}/chunks/` for blob data. Because
+/// `content_hash` is computed over the logical engram (see
+/// [`Engram::compute_hash`]), reassembling chunked values back into their
+/// original form before checking `verify_integrity()` is what lets the
+/// existing tamper-evidence guarantee carry over unchanged.
+pub struct ChunkedEngramStore {
+    root: PathBuf,
+    blobs: BlobStore,
+}
+
+impl ChunkedEngramStore {
+    /// Create a new store rooted at the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let root = root.into();
+        let blobs = BlobStore::new(root.join("chunks"));
+        fs::create_dir_all(&root)?;
+        fs::create_dir_all(&blobs.root)?;
+        Ok(Self { root, blobs })
+    }
+
+    fn engram_path(&self, engram: &Engram) -> PathBuf {
+        let date = engram.started_at.format("%Y/%m/%d");
+        self.root.join(format!("{}/{}.json", date, engram.id.0))
+    }
+
+    fn find_path(&self, id: EngramId) -> Result<PathBuf, StoreError> {
+        let filename = format!("{}.json", id.0);
+        find_file_recursive(&self.root, &filename).ok_or(StoreError::NotFound(id))
+    }
+}
+
+impl EngramStore for ChunkedEngramStore {
+    fn save(&self, engram: &Engram) -> Result<(), StoreError> {
+        if engram.content_hash.is_none() {
+            return Err(StoreError::NotFinalized);
+        }
+
+        let stored = StoredEngram::from_engram(engram, &self.blobs)?;
+
+        let path = self.engram_path(engram);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&stored)?)?;
+
+        tracing::debug!(
+            engram_id = %engram.id,
+            path = %path.display(),
+            "Chunked engram saved"
+        );
+
+        Ok(())
+    }
+
+    fn get(&self, id: EngramId) -> Result<Engram, StoreError> {
+        let path = self.find_path(id)?;
+        let json = fs::read_to_string(&path)?;
+        let stored: StoredEngram = serde_json::from_str(&json)?;
+        let engram = stored.into_engram(&self.blobs)?;
+
+        if !engram.verify_integrity() {
+            return Err(StoreError::IntegrityViolation(id));
+        }
+
+        Ok(engram)
+    }
+
+    fn list(&self, query: &EngramQuery) -> Result<Vec<Engram>, StoreError> {
+        let mut results = Vec::new();
+        collect_chunked_recursive(&self.root, &self.blobs, query, &mut results)?;
+        results.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(results)
+    }
+}
+
+fn collect_chunked_recursive(
+    dir: &Path,
+    blobs: &BlobStore,
+    query: &EngramQuery,
+    results: &mut Vec<Engram>,
+) -> Result<(), StoreError> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path == blobs.root {
+                continue;
+            }
+            collect_chunked_recursive(&path, blobs, query, results)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            let json = fs::read_to_string(&path)?;
+            let stored: StoredEngram = serde_json::from_str(&json)?;
+            let engram = stored.into_engram(blobs)?;
+            if matches_query(&engram, query) {
+                results.push(engram);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::EngramSession;
+
+    fn large_value(byte: u8, len: usize) -> Value {
+        serde_json::json!({ "blob": String::from_utf8(vec![byte; len]).unwrap() })
+    }
+
+    fn test_engram(tenant_id: Uuid, context: Value) -> Engram {
+        let mut session = EngramSession::new(tenant_id, "scanner", "Test intent");
+        session.set_context(context);
+        session.add_action("scan", "ran a scan", large_value(b'x', 40_000), true);
+        session.finalize()
+    }
+
+    fn count_chunk_files(dir: &Path) -> usize {
+        if !dir.is_dir() {
+            return 0;
+        }
+        let mut count = 0;
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                count += count_chunk_files(&path);
+            } else {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn small_fields_stay_inline_with_no_chunks_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ChunkedEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+
+        let mut session = EngramSession::new(tenant_id, "scanner", "Test intent");
+        session.set_context(serde_json::json!({"subnet": "10.0.1.0/24"}));
+        session.add_action("scan", "ran a scan", serde_json::json!({"hosts": 254}), true);
+        let engram = session.finalize();
+
+        store.save(&engram).unwrap();
+        assert_eq!(count_chunk_files(&dir.path().join("chunks")), 0);
+
+        let retrieved = store.get(engram.id).unwrap();
+        assert_eq!(retrieved.context, engram.context);
+        assert!(retrieved.verify_integrity());
+    }
+
+    #[test]
+    fn large_fields_roundtrip_through_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ChunkedEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let engram = test_engram(tenant_id, large_value(b'a', 50_000));
+        let id = engram.id;
+
+        store.save(&engram).unwrap();
+        assert!(count_chunk_files(&dir.path().join("chunks")) > 0);
+
+        let retrieved = store.get(id).unwrap();
+        assert_eq!(retrieved.context, engram.context);
+        assert_eq!(retrieved.actions[0].details, engram.actions[0].details);
+        assert!(retrieved.verify_integrity());
+    }
+
+    #[test]
+    fn identical_large_values_deduplicate_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ChunkedEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let shared_context = large_value(b'z', 50_000);
+
+        let e1 = test_engram(tenant_id, shared_context.clone());
+        let e2 = test_engram(tenant_id, shared_context);
+        store.save(&e1).unwrap();
+        let chunk_count_after_first = count_chunk_files(&dir.path().join("chunks"));
+
+        store.save(&e2).unwrap();
+        let chunk_count_after_second = count_chunk_files(&dir.path().join("chunks"));
+
+        // The second engram's identical context and action details should
+        // reuse every chunk the first one already wrote.
+        assert_eq!(chunk_count_after_first, chunk_count_after_second);
+    }
+
+    #[test]
+    fn list_reassembles_chunked_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ChunkedEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let engram = test_engram(tenant_id, large_value(b'b', 50_000));
+        store.save(&engram).unwrap();
+
+        let results = store
+            .list(&EngramQuery {
+                tenant_id: Some(tenant_id),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].context, engram.context);
+    }
+}
@@ -0,0 +1,390 @@
+//! Encrypted-at-rest engram store.
+//!
+//! `GitEngramStore` writes engrams as plaintext JSON, which leaks decision
+//! logs and context blobs to anyone with filesystem access. This module
+//! wraps the same on-disk layout with authenticated encryption: a 256-bit
+//! per-tenant data key is derived via HKDF-SHA256 from a configured master
+//! key (using the tenant ID as the HKDF `info` parameter), and the
+//! serialized engram JSON is sealed with ChaCha20-Poly1305 using a fresh
+//! random 96-bit nonce per save.
+//!
+//! Files are written as a small self-describing envelope: a version byte,
+//! a key-id (so rotated master keys can still decrypt old files), the
+//! tenant ID (needed to re-derive the per-tenant key — not secret, the
+//! same role an IV or salt plays), the nonce, and the ciphertext (with the
+//! Poly1305 tag appended). The existing `verify_integrity()` content-hash
+//! check still runs on the decrypted plaintext as a second layer of
+//! tamper evidence.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, KeyInit, Nonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::store::{matches_query, EngramQuery, EngramStore, StoreError};
+use crate::{Engram, EngramId};
+
+const ENVELOPE_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+const TENANT_ID_LEN: usize = 16;
+const HEADER_LEN: usize = 1 + 4 + TENANT_ID_LEN + NONCE_LEN;
+
+/// A 256-bit master key used to derive per-tenant data keys.
+///
+/// Never logged or serialized; holders are expected to load it from a
+/// secrets manager or environment variable.
+#[derive(Clone)]
+pub struct MasterKey([u8; 32]);
+
+impl MasterKey {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("MasterKey").field(&"<redacted>").finish()
+    }
+}
+
+/// A keyed set of master keys, indexed by key-id, supporting rotation.
+///
+/// The `active` key-id is used for new saves; older key-ids remain
+/// available so engrams written before a rotation can still be decrypted.
+#[derive(Debug, Clone)]
+pub struct MasterKeyring {
+    active_key_id: u32,
+    keys: HashMap<u32, MasterKey>,
+}
+
+impl MasterKeyring {
+    /// Start a keyring with a single active master key.
+    pub fn new(active_key_id: u32, active_key: MasterKey) -> Self {
+        let mut keys = HashMap::new();
+        keys.insert(active_key_id, active_key);
+        Self { active_key_id, keys }
+    }
+
+    /// Register an additional (e.g. retired) key so old envelopes still decrypt.
+    pub fn add_key(&mut self, key_id: u32, key: MasterKey) {
+        self.keys.insert(key_id, key);
+    }
+
+    /// Rotate to a new active key, keeping the previous one available for decryption.
+    pub fn rotate(&mut self, new_key_id: u32, new_key: MasterKey) {
+        self.keys.insert(new_key_id, new_key);
+        self.active_key_id = new_key_id;
+    }
+
+    fn active(&self) -> (u32, &MasterKey) {
+        (
+            self.active_key_id,
+            self.keys
+                .get(&self.active_key_id)
+                .expect("active key id always present in keyring"),
+        )
+    }
+
+    fn get(&self, key_id: u32) -> Option<&MasterKey> {
+        self.keys.get(&key_id)
+    }
+}
+
+/// Derive a 256-bit per-tenant data key via HKDF-SHA256.
+///
+/// Uses the tenant ID's raw bytes as the HKDF `info` parameter so every
+/// tenant gets an independent data key from the same master key.
+fn derive_tenant_key(master: &MasterKey, tenant_id: &Uuid) -> chacha20poly1305::Key {
+    let hkdf = Hkdf::<Sha256>::new(None, &master.0);
+    let mut okm = [0u8; 32];
+    hkdf.expand(tenant_id.as_bytes(), &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    okm.into()
+}
+
+/// Encrypted, file-system backed engram store.
+///
+/// Uses the same `{root}/YYYY/MM/DD/{session_id}.engram` directory layout
+/// as `GitEngramStore`, but every file holds an encrypted envelope instead
+/// of plaintext JSON.
+pub struct EncryptedEngramStore {
+    root: PathBuf,
+    keyring: MasterKeyring,
+}
+
+impl EncryptedEngramStore {
+    /// Create a new encrypted store rooted at the given directory.
+    ///
+    /// A `MasterKeyring` is required at construction time so there is no
+    /// code path that can fall back to writing plaintext when a key is
+    /// missing.
+    pub fn new(root: impl Into<PathBuf>, keyring: MasterKeyring) -> Result<Self, StoreError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self { root, keyring })
+    }
+
+    fn engram_path(&self, engram: &Engram) -> PathBuf {
+        let date = engram.started_at.format("%Y/%m/%d");
+        self.root.join(format!("{}/{}.engram", date, engram.id.0))
+    }
+
+    fn find_path(&self, id: EngramId) -> Result<PathBuf, StoreError> {
+        let filename = format!("{}.engram", id.0);
+        find_file_recursive(&self.root, &filename).ok_or(StoreError::NotFound(id))
+    }
+}
+
+/// Build the on-disk envelope:
+/// `version || key_id(LE u32) || tenant_id(16 bytes) || nonce || ciphertext+tag`.
+fn build_envelope(key_id: u32, tenant_id: &Uuid, nonce: &Nonce, ciphertext: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    out.push(ENVELOPE_VERSION);
+    out.extend_from_slice(&key_id.to_le_bytes());
+    out.extend_from_slice(tenant_id.as_bytes());
+    out.extend_from_slice(nonce);
+    out.extend_from_slice(ciphertext);
+    out
+}
+
+/// Parse the on-disk envelope into `(key_id, tenant_id, nonce, ciphertext)`.
+fn parse_envelope(bytes: &[u8]) -> Result<(u32, Uuid, [u8; NONCE_LEN], &[u8]), StoreError> {
+    if bytes.len() < HEADER_LEN || bytes[0] != ENVELOPE_VERSION {
+        return Err(StoreError::DecryptionFailed(None));
+    }
+    let key_id = u32::from_le_bytes(bytes[1..5].try_into().unwrap());
+    let tenant_id = Uuid::from_slice(&bytes[5..5 + TENANT_ID_LEN])
+        .map_err(|_| StoreError::DecryptionFailed(None))?;
+    let nonce_start = 5 + TENANT_ID_LEN;
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&bytes[nonce_start..nonce_start + NONCE_LEN]);
+    let ciphertext = &bytes[nonce_start + NONCE_LEN..];
+    Ok((key_id, tenant_id, nonce, ciphertext))
+}
+
+/// Decrypt an envelope given a keyring, returning the plaintext engram JSON.
+fn open_envelope(bytes: &[u8], keyring: &MasterKeyring) -> Result<Vec<u8>, StoreError> {
+    let (key_id, tenant_id, nonce, ciphertext) = parse_envelope(bytes)?;
+    let master = keyring.get(key_id).ok_or(StoreError::DecryptionFailed(None))?;
+    let key = derive_tenant_key(master, &tenant_id);
+    let cipher = ChaCha20Poly1305::new(&key);
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext)
+        .map_err(|_| StoreError::DecryptionFailed(None))
+}
+
+impl EngramStore for EncryptedEngramStore {
+    fn save(&self, engram: &Engram) -> Result<(), StoreError> {
+        if engram.content_hash.is_none() {
+            return Err(StoreError::NotFinalized);
+        }
+
+        let (key_id, master) = self.keyring.active();
+        let key = derive_tenant_key(master, &engram.tenant_id);
+        let cipher = ChaCha20Poly1305::new(&key);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let plaintext = serde_json::to_vec(engram)?;
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| StoreError::DecryptionFailed(Some(engram.id)))?;
+
+        let envelope = build_envelope(key_id, &engram.tenant_id, &nonce, &ciphertext);
+
+        let path = self.engram_path(engram);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, envelope)?;
+
+        tracing::debug!(
+            engram_id = %engram.id,
+            path = %path.display(),
+            key_id,
+            "Encrypted engram saved"
+        );
+
+        Ok(())
+    }
+
+    fn get(&self, id: EngramId) -> Result<Engram, StoreError> {
+        let path = self.find_path(id)?;
+        let bytes = fs::read(&path)?;
+        let plaintext =
+            open_envelope(&bytes, &self.keyring).map_err(|_| StoreError::DecryptionFailed(Some(id)))?;
+
+        let engram: Engram = serde_json::from_slice(&plaintext)?;
+        if !engram.verify_integrity() {
+            return Err(StoreError::IntegrityViolation(id));
+        }
+
+        Ok(engram)
+    }
+
+    fn list(&self, query: &EngramQuery) -> Result<Vec<Engram>, StoreError> {
+        let mut results = Vec::new();
+        collect_encrypted_recursive(&self.root, &self.keyring, query, &mut results)?;
+        results.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(results)
+    }
+}
+
+fn find_file_recursive(dir: &Path, filename: &str) -> Option<PathBuf> {
+    if !dir.is_dir() {
+        return None;
+    }
+    let entries = fs::read_dir(dir).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(found) = find_file_recursive(&path, filename) {
+                return Some(found);
+            }
+        } else if path.file_name().and_then(|n| n.to_str()) == Some(filename) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+fn collect_encrypted_recursive(
+    dir: &Path,
+    keyring: &MasterKeyring,
+    query: &EngramQuery,
+    results: &mut Vec<Engram>,
+) -> Result<(), StoreError> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(dir)?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_encrypted_recursive(&path, keyring, query, results)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("engram") {
+            let bytes = fs::read(&path)?;
+            let Ok(plaintext) = open_envelope(&bytes, keyring) else {
+                continue;
+            };
+            let engram: Engram = serde_json::from_slice(&plaintext)?;
+            if matches_query(&engram, query) {
+                results.push(engram);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::EngramSession;
+
+    fn test_keyring() -> MasterKeyring {
+        MasterKeyring::new(1, MasterKey::new([0x42; 32]))
+    }
+
+    fn test_engram(tenant_id: Uuid) -> Engram {
+        let mut session = EngramSession::new(tenant_id, "test-agent", "Test intent");
+        session.set_context(serde_json::json!({"key": "value"}));
+        session.add_decision("choice A", "best option", 0.95);
+        session.add_action("test_action", "did something", serde_json::json!({}), true);
+        session.finalize()
+    }
+
+    #[test]
+    fn save_and_retrieve_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedEngramStore::new(dir.path(), test_keyring()).unwrap();
+        let engram = test_engram(Uuid::new_v4());
+        let id = engram.id;
+
+        store.save(&engram).unwrap();
+        let retrieved = store.get(id).unwrap();
+
+        assert_eq!(retrieved.id, id);
+        assert_eq!(retrieved.intent, "Test intent");
+        assert!(retrieved.verify_integrity());
+    }
+
+    #[test]
+    fn on_disk_bytes_are_not_plaintext_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedEngramStore::new(dir.path(), test_keyring()).unwrap();
+        let engram = test_engram(Uuid::new_v4());
+
+        store.save(&engram).unwrap();
+        let path = store.find_path(engram.id).unwrap();
+        let bytes = fs::read(&path).unwrap();
+
+        assert!(serde_json::from_slice::<Engram>(&bytes).is_err());
+        assert!(!String::from_utf8_lossy(&bytes).contains("Test intent"));
+    }
+
+    #[test]
+    fn unknown_key_id_fails_decryption() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedEngramStore::new(dir.path(), test_keyring()).unwrap();
+        let engram = test_engram(Uuid::new_v4());
+        let id = engram.id;
+        store.save(&engram).unwrap();
+
+        // A keyring that never learned key-id 1 cannot decrypt.
+        let other_keyring = MasterKeyring::new(2, MasterKey::new([0x99; 32]));
+        let other_store = EncryptedEngramStore {
+            root: dir.path().to_path_buf(),
+            keyring: other_keyring,
+        };
+
+        let result = other_store.get(id);
+        assert!(matches!(result, Err(StoreError::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn rotated_keyring_still_decrypts_old_engrams() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keyring = test_keyring();
+        let store = EncryptedEngramStore::new(dir.path(), keyring.clone()).unwrap();
+        let engram = test_engram(Uuid::new_v4());
+        let id = engram.id;
+        store.save(&engram).unwrap();
+
+        // Rotate to a new active key; old key-id 1 stays registered.
+        keyring.rotate(2, MasterKey::new([0x77; 32]));
+        let rotated_store = EncryptedEngramStore {
+            root: dir.path().to_path_buf(),
+            keyring,
+        };
+
+        let retrieved = rotated_store.get(id).unwrap();
+        assert_eq!(retrieved.id, id);
+    }
+
+    #[test]
+    fn list_filters_across_encrypted_engrams() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedEngramStore::new(dir.path(), test_keyring()).unwrap();
+        let tenant_id = Uuid::new_v4();
+
+        let e1 = test_engram(tenant_id);
+        let e2 = test_engram(Uuid::new_v4());
+        store.save(&e1).unwrap();
+        store.save(&e2).unwrap();
+
+        let query = EngramQuery {
+            tenant_id: Some(tenant_id),
+            ..Default::default()
+        };
+        let results = store.list(&query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].tenant_id, tenant_id);
+    }
+}
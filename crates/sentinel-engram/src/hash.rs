@@ -20,6 +20,7 @@ struct HashableEngram<'a> {
     actions: &'a [crate::Action],
     started_at: &'a chrono::DateTime<chrono::Utc>,
     completed_at: &'a Option<chrono::DateTime<chrono::Utc>>,
+    chained_from: &'a Option<String>,
 }
 
 /// Compute the BLAKE3 hash of an engram's content.
@@ -38,6 +39,7 @@ pub fn compute_engram_hash(engram: &Engram) -> String {
         actions: &engram.actions,
         started_at: &engram.started_at,
         completed_at: &engram.completed_at,
+        chained_from: &engram.chained_from,
     };
 
     let json = serde_json::to_vec(&hashable).expect("Engram serialization should not fail");
@@ -5,7 +5,12 @@
 //! Each engram is content-hashed with BLAKE3 for tamper evidence and
 //! stored as Git objects under `refs/engrams/`.
 
+pub mod auth;
+pub mod chunking;
+pub mod crypto;
+pub mod generation;
 pub mod hash;
+pub mod ledger;
 pub mod session;
 pub mod store;
 
@@ -100,6 +105,11 @@ pub struct Engram {
     pub started_at: DateTime<Utc>,
     /// When the session ended.
     pub completed_at: Option<DateTime<Utc>>,
+    /// The root hash of an [`ledger::EngramLog`] this engram was chained
+    /// from at finalization time, if any. Folded into `content_hash` so
+    /// replaying this engram against a different history is detectable.
+    #[serde(default)]
+    pub chained_from: Option<String>,
     /// BLAKE3 content hash (hex) — set on finalization.
     pub content_hash: Option<String>,
 }
@@ -42,6 +42,7 @@ impl EngramSession {
                 actions: Vec::new(),
                 started_at: Utc::now(),
                 completed_at: None,
+                chained_from: None,
                 content_hash: None,
             },
         }
@@ -92,9 +93,28 @@ impl EngramSession {
         self.engram.id
     }
 
+    /// The tenant this session belongs to (available before finalization),
+    /// e.g. so a caller can look up the right tenant's ledger to chain from
+    /// before calling [`Self::finalize_chained`].
+    pub fn tenant_id(&self) -> uuid::Uuid {
+        self.engram.tenant_id
+    }
+
     /// Finalize the session: set completed_at and compute the content hash.
-    pub fn finalize(mut self) -> Engram {
+    pub fn finalize(self) -> Engram {
+        self.finalize_chained(None)
+    }
+
+    /// Finalize the session, chaining `previous_root` — typically an
+    /// [`crate::ledger::EngramLog`]'s current root at the time this session
+    /// started — into the content hash input. Two engrams with identical
+    /// reasoning but different `previous_root`s hash differently, so
+    /// replaying an engram against a different (e.g. truncated or
+    /// reordered) history is detectable even before checking the log
+    /// itself.
+    pub fn finalize_chained(mut self, previous_root: Option<&str>) -> Engram {
         self.engram.completed_at = Some(Utc::now());
+        self.engram.chained_from = previous_root.map(String::from);
         let hash = self.engram.compute_hash();
         self.engram.content_hash = Some(hash);
         self.engram
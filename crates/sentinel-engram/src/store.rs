@@ -3,13 +3,25 @@
 //! Engrams are stored as JSON files organized by date and session ID.
 //! The Git-backed store keeps them under a configurable directory,
 //! suitable for backing by a Git repository (refs/engrams/).
-
+//!
+//! [`GitEngramStore::append`] additionally chains each tenant's engrams
+//! through a per-tenant [`crate::ledger::EngramLog`], so the store itself
+//! — not just ad-hoc caller discipline — maintains an append-only,
+//! tamper-evident history. [`GitEngramStore::verify_chain`] replays that
+//! history to confirm it wasn't tampered with or reordered.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
 use uuid::Uuid;
 
+use crate::ledger::EngramLog;
+use crate::session::EngramSession;
 use crate::{Engram, EngramId};
 
 /// Errors that can occur during engram storage operations.
@@ -29,10 +41,22 @@ pub enum StoreError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("Decryption failed{}", .0.map(|id| format!(" for engram {id}")).unwrap_or_default())]
+    DecryptionFailed(Option<EngramId>),
+
+    #[error("Engram index error: {0}")]
+    Index(#[from] rusqlite::Error),
+
+    #[error("Not authorized for this operation")]
+    Unauthorized,
+
+    #[error("Content-addressed chunk {0} is missing from the blob store")]
+    ChunkMissing(String),
 }
 
 /// Query parameters for listing engrams.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct EngramQuery {
     /// Filter by tenant.
     pub tenant_id: Option<Uuid>,
@@ -70,18 +94,119 @@ pub trait EngramStore {
 /// ```
 ///
 /// This directory can be initialized as a Git repository for
-/// version tracking under `refs/engrams/`.
+/// version tracking under `refs/engrams/`. A SQLite index
+/// (`{root}/index.sqlite`) is maintained alongside the tree so lookups
+/// and filtered listing don't require walking and parsing every file;
+/// the filesystem remains the source of truth and the index is
+/// rebuildable from it via [`GitEngramStore::reindex`].
 pub struct GitEngramStore {
     root: PathBuf,
+    index: Mutex<Connection>,
+    ledgers: Mutex<HashMap<Uuid, EngramLog>>,
 }
 
 impl GitEngramStore {
     /// Create a new store rooted at the given directory.
-    /// Creates the directory if it doesn't exist.
+    /// Creates the directory and the index database if they don't exist.
     pub fn new(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
         let root = root.into();
         fs::create_dir_all(&root)?;
-        Ok(Self { root })
+
+        let conn = Connection::open(root.join("index.sqlite"))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS engrams (
+                id TEXT PRIMARY KEY,
+                tenant_id TEXT NOT NULL,
+                agent_id TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                completed_at TEXT,
+                content_hash TEXT,
+                rel_path TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_engrams_tenant_id ON engrams(tenant_id);
+            CREATE INDEX IF NOT EXISTS idx_engrams_agent_id ON engrams(agent_id);
+            CREATE INDEX IF NOT EXISTS idx_engrams_started_at ON engrams(started_at);",
+        )?;
+
+        Ok(Self {
+            root,
+            index: Mutex::new(conn),
+            ledgers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The directory this store is rooted at, for modules (e.g. generation
+    /// snapshots) that need to lay out their own files alongside it.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory a tenant's [`EngramLog`] is persisted under.
+    fn ledger_dir(&self, tenant_id: Uuid) -> PathBuf {
+        self.root.join("ledgers").join(tenant_id.to_string())
+    }
+
+    /// Run `f` against a tenant's ledger, opening (and lazily caching) it
+    /// first if this is the first access this process has made to it.
+    fn with_ledger<T>(
+        &self,
+        tenant_id: Uuid,
+        f: impl FnOnce(&mut EngramLog) -> Result<T, StoreError>,
+    ) -> Result<T, StoreError> {
+        fs::create_dir_all(self.ledger_dir(tenant_id))?;
+
+        let mut ledgers = self.ledgers.lock().expect("engram ledger mutex poisoned");
+        let log = match ledgers.entry(tenant_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(EngramLog::open(&self.ledger_dir(tenant_id))?),
+        };
+        f(log)
+    }
+
+    /// Finalize `session` chained from its tenant's current ledger head,
+    /// save the resulting engram, and advance the ledger so the next
+    /// `append` for this tenant chains from it in turn.
+    ///
+    /// This is the only way an engram should enter the store if the
+    /// tamper-evident chain is to mean anything — calling [`Self::save`]
+    /// directly on an engram finalized via [`EngramSession::finalize`]
+    /// persists it but leaves it outside the chain.
+    pub fn append(&self, session: EngramSession) -> Result<Engram, StoreError> {
+        let tenant_id = session.tenant_id();
+        let previous_root = self.with_ledger(tenant_id, |log| Ok(log.root()))?;
+
+        let engram = session.finalize_chained(previous_root.as_deref());
+        self.save(&engram)?;
+        self.with_ledger(tenant_id, |log| log.append(&engram))?;
+
+        Ok(engram)
+    }
+
+    /// Replay a tenant's engrams in append order, recomputing each one's
+    /// content hash and confirming its `chained_from` matches the ledger
+    /// root that actually preceded it. Returns the append-order index of
+    /// the first engram that fails either check — a rewritten, dropped, or
+    /// reordered engram changes its own hash or the root after it — or
+    /// `None` if the whole history verifies.
+    pub fn verify_chain(&self, tenant_id: Uuid) -> Result<Option<u64>, StoreError> {
+        let mut engrams = self.list(&EngramQuery {
+            tenant_id: Some(tenant_id),
+            ..Default::default()
+        })?;
+        engrams.reverse(); // `list` orders newest-first; replay oldest-first.
+
+        self.with_ledger(tenant_id, |log| {
+            for (i, engram) in engrams.iter().enumerate() {
+                if !engram.verify_integrity() {
+                    return Ok(Some(i as u64));
+                }
+                if engram.chained_from != log.root_as_of(i as u64) {
+                    return Ok(Some(i as u64));
+                }
+            }
+            Ok(None)
+        })
     }
 
     /// Build the file path for an engram based on its start date and ID.
@@ -90,10 +215,99 @@ impl GitEngramStore {
         self.root.join(format!("{}/{}.json", date, engram.id.0))
     }
 
-    /// Build the file path for an engram ID by scanning the directory tree.
+    /// Resolve an engram ID to its file path via the index, falling back to
+    /// a recursive scan (and repairing the index row) if the index is
+    /// missing the entry or points at a file that no longer exists.
     fn find_path(&self, id: EngramId) -> Result<PathBuf, StoreError> {
+        let indexed_rel_path: Option<String> = self
+            .index
+            .lock()
+            .expect("engram index mutex poisoned")
+            .query_row(
+                "SELECT rel_path FROM engrams WHERE id = ?1",
+                [id.0.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if let Some(rel_path) = indexed_rel_path {
+            let path = self.root.join(&rel_path);
+            if path.is_file() {
+                return Ok(path);
+            }
+            // Stale index entry (file moved/deleted out-of-band) — fall through to the scan below.
+        }
+
         let filename = format!("{}.json", id.0);
-        find_file_recursive(&self.root, &filename).ok_or(StoreError::NotFound(id))
+        let path = find_file_recursive(&self.root, &filename).ok_or(StoreError::NotFound(id))?;
+        self.index_engram_file(&path)?;
+        Ok(path)
+    }
+
+    /// Relative-path the given absolute path under `root`, for storage in the index.
+    fn rel_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Read and parse the engram JSON at `path`, then upsert its index row.
+    /// Used both to repair a single stale row and to rebuild the whole index.
+    fn index_engram_file(&self, path: &Path) -> Result<(), StoreError> {
+        let json = fs::read_to_string(path)?;
+        let engram: Engram = serde_json::from_str(&json)?;
+        let rel_path = self.rel_path(path);
+        self.upsert_index_row(&engram, &rel_path)
+    }
+
+    /// Insert or update the index row for an engram.
+    fn upsert_index_row(&self, engram: &Engram, rel_path: &str) -> Result<(), StoreError> {
+        self.index
+            .lock()
+            .expect("engram index mutex poisoned")
+            .execute(
+                "INSERT INTO engrams (id, tenant_id, agent_id, session_id, started_at, completed_at, content_hash, rel_path)
+                 VALUES (?1, ?2, ?3, ?1, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(id) DO UPDATE SET
+                    tenant_id = excluded.tenant_id,
+                    agent_id = excluded.agent_id,
+                    started_at = excluded.started_at,
+                    completed_at = excluded.completed_at,
+                    content_hash = excluded.content_hash,
+                    rel_path = excluded.rel_path",
+                rusqlite::params![
+                    engram.id.0.to_string(),
+                    engram.tenant_id.to_string(),
+                    engram.agent_id,
+                    engram.started_at.to_rfc3339(),
+                    engram.completed_at.map(|t| t.to_rfc3339()),
+                    engram.content_hash,
+                    rel_path,
+                ],
+            )?;
+        Ok(())
+    }
+
+    /// Rebuild the index from scratch by scanning the directory tree.
+    ///
+    /// Use this for recovery after `index.sqlite` is deleted, or after an
+    /// out-of-band `git pull` adds engram files the index never saw.
+    /// Returns the number of engrams indexed.
+    pub fn reindex(&self) -> Result<usize, StoreError> {
+        let mut paths = Vec::new();
+        collect_json_paths_recursive(&self.root, &mut paths)?;
+
+        self.index
+            .lock()
+            .expect("engram index mutex poisoned")
+            .execute("DELETE FROM engrams", [])?;
+
+        for path in &paths {
+            self.index_engram_file(path)?;
+        }
+
+        Ok(paths.len())
     }
 }
 
@@ -111,6 +325,9 @@ impl EngramStore for GitEngramStore {
         let json = serde_json::to_string_pretty(engram)?;
         fs::write(&path, json)?;
 
+        let rel_path = self.rel_path(&path);
+        self.upsert_index_row(engram, &rel_path)?;
+
         tracing::debug!(
             engram_id = %engram.id,
             path = %path.display(),
@@ -133,12 +350,62 @@ impl EngramStore for GitEngramStore {
     }
 
     fn list(&self, query: &EngramQuery) -> Result<Vec<Engram>, StoreError> {
+        let mut sql = String::from("SELECT rel_path FROM engrams WHERE 1=1");
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(tenant_id) = &query.tenant_id {
+            sql.push_str(" AND tenant_id = ?");
+            params.push(Box::new(tenant_id.to_string()));
+        }
+        if let Some(agent_id) = &query.agent_id {
+            sql.push_str(" AND agent_id = ?");
+            params.push(Box::new(agent_id.clone()));
+        }
+        if let Some(session_id) = &query.session_id {
+            sql.push_str(" AND id = ?");
+            params.push(Box::new(session_id.0.to_string()));
+        }
+        if let Some(from) = &query.from {
+            sql.push_str(" AND started_at >= ?");
+            params.push(Box::new(from.to_rfc3339()));
+        }
+        if let Some(to) = &query.to {
+            sql.push_str(" AND started_at <= ?");
+            params.push(Box::new(to.to_rfc3339()));
+        }
+        sql.push_str(" ORDER BY started_at DESC");
+
+        let rel_paths: Vec<String> = {
+            let conn = self.index.lock().expect("engram index mutex poisoned");
+            let mut stmt = conn.prepare(&sql)?;
+            stmt.query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| row.get(0),
+            )?
+            .collect::<Result<_, _>>()?
+        };
+
+        // Only the JSON files the index matched get opened and parsed.
         let mut results = Vec::new();
+        for rel_path in rel_paths {
+            let path = self.root.join(&rel_path);
+            if !path.is_file() {
+                // Stale row left behind by an out-of-band delete/move; drop it
+                // rather than fail the whole query. `reindex()` covers full recovery.
+                self.index
+                    .lock()
+                    .expect("engram index mutex poisoned")
+                    .execute("DELETE FROM engrams WHERE rel_path = ?1", [&rel_path])?;
+                continue;
+            }
 
-        // Walk the directory tree and collect matching engrams
-        collect_engrams_recursive(&self.root, query, &mut results)?;
+            let json = fs::read_to_string(&path)?;
+            let engram: Engram = serde_json::from_str(&json)?;
+            if matches_query(&engram, query) {
+                results.push(engram);
+            }
+        }
 
-        // Sort by started_at descending
         results.sort_by(|a, b| b.started_at.cmp(&a.started_at));
 
         Ok(results)
@@ -146,7 +413,7 @@ impl EngramStore for GitEngramStore {
 }
 
 /// Recursively find a file by name.
-fn find_file_recursive(dir: &Path, filename: &str) -> Option<PathBuf> {
+pub(crate) fn find_file_recursive(dir: &Path, filename: &str) -> Option<PathBuf> {
     if !dir.is_dir() {
         return None;
     }
@@ -166,12 +433,9 @@ fn find_file_recursive(dir: &Path, filename: &str) -> Option<PathBuf> {
     None
 }
 
-/// Recursively collect engrams matching a query.
-fn collect_engrams_recursive(
-    dir: &Path,
-    query: &EngramQuery,
-    results: &mut Vec<Engram>,
-) -> Result<(), StoreError> {
+/// Recursively collect the paths of every `.json` engram file under `dir`.
+/// Used by [`GitEngramStore::reindex`] to rebuild the index from scratch.
+fn collect_json_paths_recursive(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), StoreError> {
     if !dir.is_dir() {
         return Ok(());
     }
@@ -180,14 +444,9 @@ fn collect_engrams_recursive(
     for entry in entries.flatten() {
         let path = entry.path();
         if path.is_dir() {
-            collect_engrams_recursive(&path, query, results)?;
+            collect_json_paths_recursive(&path, paths)?;
         } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
-            let json = fs::read_to_string(&path)?;
-            let engram: Engram = serde_json::from_str(&json)?;
-
-            if matches_query(&engram, query) {
-                results.push(engram);
-            }
+            paths.push(path);
         }
     }
 
@@ -195,7 +454,7 @@ fn collect_engrams_recursive(
 }
 
 /// Check whether an engram matches the given query filters.
-fn matches_query(engram: &Engram, query: &EngramQuery) -> bool {
+pub(crate) fn matches_query(engram: &Engram, query: &EngramQuery) -> bool {
     if let Some(tid) = &query.tenant_id {
         if &engram.tenant_id != tid {
             return false;
@@ -299,6 +558,7 @@ mod tests {
             actions: vec![],
             started_at: Utc::now(),
             completed_at: None,
+            chained_from: None,
             content_hash: None, // not finalized
         };
 
@@ -351,4 +611,122 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].tenant_id, t1);
     }
+
+    #[test]
+    fn find_path_self_heals_when_index_is_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GitEngramStore::new(dir.path()).unwrap();
+        let engram = create_test_engram(Uuid::new_v4(), "test-agent");
+        let id = engram.id;
+        store.save(&engram).unwrap();
+
+        // Simulate an out-of-band move: the file moves but the index doesn't know yet.
+        let real_path = store.find_path(id).unwrap();
+        let moved_path = dir.path().join("moved.json");
+        fs::rename(&real_path, &moved_path).unwrap();
+        store
+            .index
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE engrams SET rel_path = ?1 WHERE id = ?2",
+                rusqlite::params!["no/such/file.json", id.0.to_string()],
+            )
+            .unwrap();
+
+        // The lookup falls back to a recursive scan and repairs the row.
+        let found = store.find_path(id).unwrap();
+        assert_eq!(found, moved_path);
+
+        let repaired: String = store
+            .index
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT rel_path FROM engrams WHERE id = ?1",
+                [id.0.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(repaired, "moved.json");
+    }
+
+    #[test]
+    fn append_chains_successive_engrams_through_the_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+
+        let mut first = EngramSession::new(tenant_id, "scanner", "first");
+        first.add_decision("x", "y", 0.5);
+        let first = store.append(first).unwrap();
+        assert_eq!(first.chained_from, None);
+
+        let mut second = EngramSession::new(tenant_id, "scanner", "second");
+        second.add_decision("x", "y", 0.5);
+        let second = store.append(second).unwrap();
+        assert!(second.chained_from.is_some());
+
+        assert_eq!(store.verify_chain(tenant_id).unwrap(), None);
+    }
+
+    #[test]
+    fn verify_chain_detects_a_rewritten_engram() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+
+        for n in 0..3 {
+            let mut session = EngramSession::new(tenant_id, "scanner", "intent");
+            session.add_decision(&format!("decision {n}"), "because", 0.9);
+            store.append(session).unwrap();
+        }
+        assert_eq!(store.verify_chain(tenant_id).unwrap(), None);
+
+        // Tamper with the first engram on disk without touching the ledger.
+        let first = store
+            .list(&EngramQuery {
+                tenant_id: Some(tenant_id),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .min_by_key(|e| e.started_at)
+            .unwrap();
+        let path = store.find_path(first.id).unwrap();
+        let mut tampered: Engram =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        tampered.intent = "TAMPERED".to_string();
+        fs::write(&path, serde_json::to_string_pretty(&tampered).unwrap()).unwrap();
+
+        assert_eq!(store.verify_chain(tenant_id).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn reindex_recovers_after_index_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GitEngramStore::new(dir.path()).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let engram = create_test_engram(tenant_id, "scanner");
+        store.save(&engram).unwrap();
+
+        // Simulate losing the index (e.g. index.sqlite deleted and recreated empty).
+        store
+            .index
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM engrams", [])
+            .unwrap();
+        assert!(store.list(&EngramQuery::default()).unwrap().is_empty());
+
+        let reindexed = store.reindex().unwrap();
+        assert_eq!(reindexed, 1);
+
+        let query = EngramQuery {
+            tenant_id: Some(tenant_id),
+            ..Default::default()
+        };
+        let results = store.list(&query).unwrap();
+        assert_eq!(results.len(), 1);
+    }
 }
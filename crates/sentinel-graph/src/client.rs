@@ -1,6 +1,10 @@
 //! Neo4j connection management and shared graph client.
 
-use neo4rs::{ConfigBuilder, Graph, Query};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use neo4rs::{query, ConfigBuilder, Graph, Query};
+use rand::Rng;
 
 /// Errors from graph operations.
 #[derive(Debug, thiserror::Error)]
@@ -20,6 +24,22 @@ pub enum GraphError {
 
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    #[error("Neo4j {0} timed out")]
+    Timeout(String),
+
+    /// An error from a non-Neo4j [`crate::writer::GraphWriter`] backend
+    /// (e.g. `sqlite_writer`), which has no `neo4rs::Error` to wrap.
+    #[error("Graph storage backend error: {0}")]
+    Backend(String),
+
+    /// A transient, retryable error kept recurring until `max_retries` was
+    /// spent. Distinguishes "gave up after retrying" from a one-shot fatal
+    /// error so callers (e.g. a daemon-mode scan loop) can tell the two
+    /// apart -- this one is worth logging loudly and maybe alerting on;
+    /// a fatal `Query`/`Serialization` error is a bug, not a hiccup.
+    #[error("Exceeded retry budget for Neo4j {operation} after {attempts} attempt(s)")]
+    RetriesExhausted { operation: String, attempts: u32 },
 }
 
 /// Configuration for connecting to Neo4j.
@@ -30,6 +50,44 @@ pub struct GraphConfig {
     pub password: String,
     pub max_connections: u32,
     pub fetch_size: usize,
+
+    /// How long to wait for the initial Bolt handshake before giving up.
+    pub connect_timeout: Duration,
+
+    /// How long to wait for any single query execution before giving up.
+    pub query_timeout: Duration,
+
+    /// Maximum number of retries for an operation that fails with a
+    /// transient, retryable error (e.g. the server restarted, a leader
+    /// election, or a transaction deadlock). Applies to reads
+    /// (`query_rows`/`query_one`) and to the idempotent `MERGE`-based
+    /// writes issued through `run_for` and the batched `upsert_nodes`/
+    /// `upsert_edges` transactions. A bare `run()` call is never retried --
+    /// the driver has no way to know an arbitrary write is safe to resend.
+    pub max_retries: u32,
+
+    /// Base delay for the exponential backoff between retries; attempt `n`
+    /// (0-indexed) waits a random duration in `[0, base_backoff * 2^n]`
+    /// ("full jitter"), so that many callers retrying after the same outage
+    /// don't all wake up and hammer the cluster in lockstep.
+    pub base_backoff: Duration,
+
+    /// PEM-encoded CA bundle to verify the server certificate against,
+    /// instead of the system trust store. Set this for a self-signed or
+    /// internal-CA-issued Neo4j certificate.
+    pub tls_ca_cert: Option<PathBuf>,
+
+    /// PEM-encoded client certificate for mutual TLS, paired with
+    /// `tls_client_key`.
+    pub tls_client_cert: Option<PathBuf>,
+
+    /// PEM-encoded private key for `tls_client_cert`.
+    pub tls_client_key: Option<PathBuf>,
+
+    /// Verify the server's hostname against its certificate. Only disable
+    /// this against an internal endpoint reached by address rather than
+    /// name, where that check would otherwise legitimately fail.
+    pub tls_verify_hostname: bool,
 }
 
 impl Default for GraphConfig {
@@ -40,6 +98,14 @@ impl Default for GraphConfig {
             password: "sentinel-dev".to_string(),
             max_connections: 16,
             fetch_size: 256,
+            connect_timeout: Duration::from_secs(10),
+            query_timeout: Duration::from_secs(30),
+            max_retries: 3,
+            base_backoff: Duration::from_millis(200),
+            tls_ca_cert: None,
+            tls_client_cert: None,
+            tls_client_key: None,
+            tls_verify_hostname: true,
         }
     }
 }
@@ -51,26 +117,49 @@ impl Default for GraphConfig {
 #[derive(Clone)]
 pub struct GraphClient {
     graph: Graph,
+    query_timeout: Duration,
+    max_retries: u32,
+    base_backoff: Duration,
 }
 
 impl GraphClient {
     /// Connect to Neo4j with the given configuration.
+    ///
+    /// If any `tls_*` field is set, `config.uri`'s scheme is upgraded to
+    /// its encrypted form (`bolt+s`/`neo4j+s`, or the `+ssc` variant when
+    /// `tls_verify_hostname` is false) following the standard Bolt URI
+    /// scheme convention shared by all Neo4j drivers; a URI that already
+    /// names an explicit scheme is left alone. A custom CA bundle and/or
+    /// client certificate are layered on top via an explicit rustls config.
     pub async fn connect(config: &GraphConfig) -> Result<Self, GraphError> {
-        let neo_config = ConfigBuilder::default()
-            .uri(&config.uri)
+        let uri = effective_uri(config);
+        let mut builder = ConfigBuilder::default()
+            .uri(&uri)
             .user(&config.user)
             .password(&config.password)
             .max_connections(config.max_connections as usize)
-            .fetch_size(config.fetch_size)
+            .fetch_size(config.fetch_size);
+
+        if let Some(tls) = build_client_tls_config(config)? {
+            builder = builder.tls_config(tls);
+        }
+
+        let neo_config = builder
             .build()
             .map_err(|e| GraphError::Connection(e.to_string()))?;
 
-        let graph = Graph::connect(neo_config)
+        let graph = tokio::time::timeout(config.connect_timeout, Graph::connect(neo_config))
             .await
+            .map_err(|_| GraphError::Timeout("connect".to_string()))?
             .map_err(|e| GraphError::Connection(e.to_string()))?;
 
         tracing::info!(uri = %config.uri, "Connected to Neo4j");
-        Ok(Self { graph })
+        Ok(Self {
+            graph,
+            query_timeout: config.query_timeout,
+            max_retries: config.max_retries,
+            base_backoff: config.base_backoff,
+        })
     }
 
     /// Get a reference to the underlying neo4rs Graph for direct operations.
@@ -79,25 +168,265 @@ impl GraphClient {
     }
 
     /// Execute a write-only query (CREATE, MERGE, DELETE, SET).
+    ///
+    /// Bounded by `query_timeout` but never retried: a write isn't
+    /// guaranteed idempotent, so resending it after an ambiguous failure
+    /// (e.g. the response was lost after the server applied it) could
+    /// double-apply. Callers that need retries for a write should make it
+    /// idempotent (e.g. `MERGE`) and retry at the call site.
     pub async fn run(&self, query: Query) -> Result<(), GraphError> {
-        self.graph.run(query).await?;
+        self.with_timeout("run", self.graph.run(query)).await?;
         Ok(())
     }
 
+    /// Like [`run`](Self::run), but retried via
+    /// [`retry_graph`](Self::retry_graph) the same way reads are, and
+    /// records the `sentinel.graph.query.*` duration/error metrics under
+    /// `operation`. Safe here specifically because `sentinel-graph/src/mutations.rs`
+    /// (the sole caller) only issues idempotent `MERGE`-based writes through
+    /// this path -- resending one after an ambiguous failure just
+    /// re-applies the same merge instead of double-creating anything.
+    pub async fn run_for(&self, operation: &str, query: Query) -> Result<(), GraphError> {
+        let started = std::time::Instant::now();
+        let result = self
+            .retry_graph(operation, || {
+                let query = query.clone();
+                async move { self.run(query).await }
+            })
+            .await;
+        record_query_metrics(operation, started.elapsed(), &result, |_| 0);
+        result
+    }
+
     /// Execute a read query and collect all rows.
+    ///
+    /// Retried up to `max_retries` times with exponential backoff if the
+    /// failure looks connection-class (the server restarted or briefly
+    /// dropped the link) -- reads are safe to retry since they have no
+    /// side effects.
     pub async fn query_rows(&self, query: Query) -> Result<Vec<neo4rs::Row>, GraphError> {
-        let mut stream = self.graph.execute(query).await?;
-        let mut rows = Vec::new();
-        while let Some(row) = stream.next().await? {
-            rows.push(row);
-        }
-        Ok(rows)
+        self.retry("query_rows", || {
+            let query = query.clone();
+            async move {
+                let mut stream = self.graph.execute(query).await?;
+                let mut rows = Vec::new();
+                while let Some(row) = stream.next().await? {
+                    rows.push(row);
+                }
+                Ok(rows)
+            }
+        })
+        .await
     }
 
     /// Execute a read query and return the first row, if any.
+    ///
+    /// Retried the same way as [`query_rows`](Self::query_rows).
     pub async fn query_one(&self, query: Query) -> Result<Option<neo4rs::Row>, GraphError> {
-        let mut stream = self.graph.execute(query).await?;
-        Ok(stream.next().await?)
+        self.retry("query_one", || {
+            let query = query.clone();
+            async move {
+                let mut stream = self.graph.execute(query).await?;
+                stream.next().await
+            }
+        })
+        .await
+    }
+
+    /// Run a cheap `RETURN 1` to confirm the connection is alive and the
+    /// server is responding within `query_timeout`.
+    pub async fn health_check(&self) -> Result<(), GraphError> {
+        self.query_one(query("RETURN 1")).await.map(|_| ())
+    }
+
+    /// Stream a query's rows to `f` one at a time instead of collecting them
+    /// into a `Vec` like [`query_rows`](Self::query_rows) does. The driver
+    /// still fetches in `fetch_size`-sized batches under the hood; this just
+    /// avoids holding every row of a large result (e.g. a 50,000-node
+    /// subgraph) in memory at once.
+    ///
+    /// Not retried: a partial callback failure partway through a large
+    /// result can't be safely replayed without re-invoking `f` for rows it
+    /// already saw, so a connection-class error here is surfaced directly
+    /// rather than retried like a buffered [`query_rows`](Self::query_rows)
+    /// call is.
+    pub async fn for_each_row<F>(&self, query: Query, mut f: F) -> Result<(), GraphError>
+    where
+        F: FnMut(neo4rs::Row) -> Result<(), GraphError>,
+    {
+        let mut stream = self.with_timeout("for_each_row", self.graph.execute(query)).await?;
+        while let Some(row) = self.with_timeout("for_each_row", stream.next()).await? {
+            f(row)?;
+        }
+        Ok(())
+    }
+
+    /// Page through a query that doesn't already carry its own `SKIP`/
+    /// `LIMIT`, fetching `page_size` rows per round.
+    ///
+    /// `build_page(offset, limit)` must return a `Query` whose Cypher
+    /// applies `SKIP $offset LIMIT $limit` (or equivalent) over a stable
+    /// ordering -- `query_paginated` has no way to inspect or rewrite the
+    /// Cypher itself, so an unordered `MATCH` will produce overlapping or
+    /// missing rows across pages. `on_page` is called once per page, in
+    /// order; paging stops as soon as a page comes back shorter than
+    /// `page_size`.
+    pub async fn query_paginated<F>(
+        &self,
+        page_size: u32,
+        mut build_page: impl FnMut(u32, u32) -> Query,
+        mut on_page: F,
+    ) -> Result<(), GraphError>
+    where
+        F: FnMut(Vec<neo4rs::Row>) -> Result<(), GraphError>,
+    {
+        let mut offset = 0u32;
+        loop {
+            let page = self.query_rows(build_page(offset, page_size)).await?;
+            let page_len = page.len() as u32;
+            let is_last_page = page_len < page_size;
+
+            on_page(page)?;
+
+            if is_last_page {
+                return Ok(());
+            }
+            offset += page_size;
+        }
+    }
+
+    /// Run `f` once, bounded by `query_timeout`, mapping a timeout to
+    /// [`GraphError::Timeout`].
+    async fn with_timeout<T>(
+        &self,
+        operation: &str,
+        fut: impl std::future::Future<Output = Result<T, neo4rs::Error>>,
+    ) -> Result<T, GraphError> {
+        tokio::time::timeout(self.query_timeout, fut)
+            .await
+            .map_err(|_| GraphError::Timeout(operation.to_string()))?
+            .map_err(GraphError::from)
+    }
+
+    /// Run `f` with a timeout, retrying up to `max_retries` times with
+    /// jittered exponential backoff when the failure is transient (see
+    /// [`is_retryable_error`]). Timeouts and non-retryable errors are
+    /// returned immediately; exhausting the retry budget on a retryable
+    /// error surfaces [`GraphError::RetriesExhausted`] instead of the last
+    /// underlying error.
+    async fn retry<T, F, Fut>(&self, operation: &str, f: F) -> Result<T, GraphError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, neo4rs::Error>>,
+    {
+        self.retry_graph(operation, || async { f().await.map_err(GraphError::from) })
+            .await
+    }
+
+    /// Run `f` (which already produces a `GraphError`, e.g. a multi-step
+    /// transaction spanning several queries) bounded by `query_timeout` per
+    /// attempt, retrying up to `max_retries` times with jittered
+    /// exponential backoff when the failure is transient (see
+    /// [`is_retryable_error`]). Exhausting the budget on a retryable error
+    /// surfaces [`GraphError::RetriesExhausted`] instead of the last
+    /// underlying error, so callers can distinguish "gave up after
+    /// retrying" from a fatal, non-retryable failure.
+    ///
+    /// `pub(crate)` so `mutations.rs` can wrap its own multi-query
+    /// transactions (see `upsert_nodes`/`upsert_edges`) the same way this
+    /// module retries reads internally.
+    pub(crate) async fn retry_graph<T, F, Fut>(&self, operation: &str, f: F) -> Result<T, GraphError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, GraphError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match tokio::time::timeout(self.query_timeout, f()).await {
+                Ok(result) => result,
+                Err(_) => return Err(GraphError::Timeout(operation.to_string())),
+            };
+
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e @ GraphError::Timeout(_)) => return Err(e),
+                Err(e) if attempt < self.max_retries && is_retryable_error(&e) => {
+                    let delay = backoff_with_jitter(self.base_backoff, attempt);
+                    tracing::warn!(
+                        operation,
+                        attempt,
+                        error = %e,
+                        delay_ms = delay.as_millis(),
+                        "Retrying Neo4j operation after transient error"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) if is_retryable_error(&e) => {
+                    return Err(GraphError::RetriesExhausted {
+                        operation: operation.to_string(),
+                        attempts: attempt + 1,
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Like [`query_rows`](Self::query_rows), but also records the
+    /// `sentinel.graph.query.*` metrics (duration, rows returned, errors by
+    /// kind) under `operation`. `sentinel-graph/src/queries.rs` is the sole
+    /// caller -- every public query method there funnels through here so
+    /// instrumentation lives in one place instead of at each call site.
+    pub async fn query_rows_for(
+        &self,
+        operation: &str,
+        query: Query,
+    ) -> Result<Vec<neo4rs::Row>, GraphError> {
+        let started = std::time::Instant::now();
+        let result = self.query_rows(query).await;
+        record_query_metrics(operation, started.elapsed(), &result, |rows| rows.len());
+        result
+    }
+
+    /// Like [`for_each_row`](Self::for_each_row), but also records the
+    /// `sentinel.graph.query.*` metrics as [`query_rows_for`](Self::query_rows_for)
+    /// does, counting rows as they stream through rather than from a
+    /// collected `Vec`.
+    pub async fn for_each_row_for<F>(
+        &self,
+        operation: &str,
+        query: Query,
+        mut f: F,
+    ) -> Result<(), GraphError>
+    where
+        F: FnMut(neo4rs::Row) -> Result<(), GraphError>,
+    {
+        let started = std::time::Instant::now();
+        let mut rows_seen = 0usize;
+        let result = self
+            .for_each_row(query, |row| {
+                rows_seen += 1;
+                f(row)
+            })
+            .await;
+        record_query_metrics(operation, started.elapsed(), &result, |_| rows_seen);
+        result
+    }
+
+    /// Like [`query_one`](Self::query_one), with the same metrics recording
+    /// as [`query_rows_for`](Self::query_rows_for).
+    pub async fn query_one_for(
+        &self,
+        operation: &str,
+        query: Query,
+    ) -> Result<Option<neo4rs::Row>, GraphError> {
+        let started = std::time::Instant::now();
+        let result = self.query_one(query).await;
+        record_query_metrics(operation, started.elapsed(), &result, |row| {
+            row.is_some() as usize
+        });
+        result
     }
 
     /// Begin a transaction.
@@ -105,3 +434,162 @@ impl GraphClient {
         Ok(self.graph.start_txn().await?)
     }
 }
+
+/// Record the `sentinel.graph.query.*` metrics for one query invocation.
+/// `count` extracts a rows-returned count from the success case; it's a
+/// closure rather than always `usize` since `query_rows_for` counts a `Vec`
+/// and `query_one_for` counts an `Option`.
+fn record_query_metrics<T>(
+    operation: &str,
+    elapsed: std::time::Duration,
+    result: &Result<T, GraphError>,
+    count: impl FnOnce(&T) -> usize,
+) {
+    metrics::histogram!("sentinel.graph.query.duration_ms", "operation" => operation.to_string())
+        .record(elapsed.as_secs_f64() * 1000.0);
+
+    match result {
+        Ok(value) => {
+            metrics::counter!("sentinel.graph.query.rows_total", "operation" => operation.to_string())
+                .increment(count(value) as u64);
+        }
+        Err(e) => {
+            metrics::counter!(
+                "sentinel.graph.query.errors_total",
+                "operation" => operation.to_string(),
+                "error_type" => error_kind(e),
+            )
+            .increment(1);
+        }
+    }
+}
+
+fn error_kind(e: &GraphError) -> &'static str {
+    match e {
+        GraphError::Connection(_) => "connection",
+        GraphError::Query(_) => "query",
+        GraphError::NotFound { .. } => "not_found",
+        GraphError::Serialization(_) => "serialization",
+        GraphError::Timeout(_) => "timeout",
+        GraphError::Backend(_) => "backend",
+        GraphError::RetriesExhausted { .. } => "retries_exhausted",
+    }
+}
+
+/// Whether `e` looks like a transient failure worth retrying (a dropped
+/// connection, leader re-election, or a transaction deadlock) rather than a
+/// genuine, fatal one (bad Cypher, constraint violation, a serialization
+/// bug). `neo4rs::Error`'s variants aren't all `PartialEq`-friendly for
+/// this, so -- the same way `error_kind` already classifies errors for
+/// metrics -- this matches on the rendered message rather than the variant.
+fn is_retryable_error(e: &GraphError) -> bool {
+    let GraphError::Query(inner) = e else {
+        return false;
+    };
+    let msg = inner.to_string().to_lowercase();
+    [
+        "connection",
+        "broken pipe",
+        "reset by peer",
+        "closed",
+        "transienterror",
+        "deadlock",
+        "leader switch",
+        "not a leader",
+        "session expired",
+    ]
+    .iter()
+    .any(|needle| msg.contains(needle))
+}
+
+/// Apply "full jitter" to the exponential backoff delay: attempt `n`
+/// (0-indexed) waits a random duration somewhere in
+/// `[0, base_backoff * 2^n]`, rather than the same fixed delay every time,
+/// so that many callers retrying after the same cluster hiccup spread out
+/// instead of all hammering it again in lockstep.
+fn backoff_with_jitter(base_backoff: Duration, attempt: u32) -> Duration {
+    let max_delay = base_backoff * 2u32.pow(attempt);
+    let jitter_fraction: f64 = rand::thread_rng().gen_range(0.0..=1.0);
+    max_delay.mul_f64(jitter_fraction)
+}
+
+/// Rewrite a plain `bolt://`/`neo4j://` URI to its encrypted (`+s`) or
+/// encrypted-but-unverified (`+ssc`) form when `config`'s TLS settings call
+/// for it. A URI that already names an explicit scheme (e.g. `bolt+s://`)
+/// is returned unchanged.
+fn effective_uri(config: &GraphConfig) -> String {
+    let wants_tls = config.tls_ca_cert.is_some()
+        || config.tls_client_cert.is_some()
+        || !config.tls_verify_hostname;
+    if !wants_tls {
+        return config.uri.clone();
+    }
+
+    let suffix = if config.tls_verify_hostname { "+s" } else { "+ssc" };
+    for scheme in ["bolt", "neo4j"] {
+        if let Some(rest) = config.uri.strip_prefix(&format!("{scheme}://")) {
+            return format!("{scheme}{suffix}://{rest}");
+        }
+    }
+    config.uri.clone()
+}
+
+/// Build a custom rustls client config carrying a CA bundle and/or client
+/// certificate for mutual TLS, or `None` to leave neo4rs's scheme-driven
+/// TLS defaults (system trust store, no client cert) alone.
+fn build_client_tls_config(config: &GraphConfig) -> Result<Option<rustls::ClientConfig>, GraphError> {
+    if config.tls_ca_cert.is_none() && config.tls_client_cert.is_none() {
+        return Ok(None);
+    }
+
+    let mut roots = rustls::RootCertStore::empty();
+    match &config.tls_ca_cert {
+        Some(path) => {
+            for cert in load_pem_certs(path)? {
+                roots
+                    .add(cert)
+                    .map_err(|e| GraphError::Connection(format!("invalid TLS CA cert: {e}")))?;
+            }
+        }
+        None => {
+            for cert in rustls_native_certs::load_native_certs()
+                .map_err(|e| GraphError::Connection(format!("loading system trust store: {e}")))?
+            {
+                roots
+                    .add(cert)
+                    .map_err(|e| GraphError::Connection(format!("invalid system trust root: {e}")))?;
+            }
+        }
+    }
+
+    let builder = rustls::ClientConfig::builder().with_root_certificates(roots);
+
+    let tls_config = match (&config.tls_client_cert, &config.tls_client_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let certs = load_pem_certs(cert_path)?;
+            let key = load_pem_key(key_path)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| GraphError::Connection(format!("invalid TLS client cert: {e}")))?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+
+    Ok(Some(tls_config))
+}
+
+fn load_pem_certs(path: &Path) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, GraphError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| GraphError::Connection(format!("reading {}: {e}", path.display())))?;
+    rustls_pemfile::certs(&mut bytes.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| GraphError::Connection(format!("parsing {}: {e}", path.display())))
+}
+
+fn load_pem_key(path: &Path) -> Result<rustls::pki_types::PrivateKeyDer<'static>, GraphError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| GraphError::Connection(format!("reading {}: {e}", path.display())))?;
+    rustls_pemfile::private_key(&mut bytes.as_slice())
+        .map_err(|e| GraphError::Connection(format!("parsing {}: {e}", path.display())))?
+        .ok_or_else(|| GraphError::Connection(format!("no private key found in {}", path.display())))
+}
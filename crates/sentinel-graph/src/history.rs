@@ -0,0 +1,181 @@
+//! Point-in-time history for graph nodes.
+//!
+//! The batched `upsert_nodes` write (see `mutations.rs`) appends a
+//! `NodeHistory` snapshot alongside every live node update instead of only
+//! ever overwriting it: each node accumulates a `(tenant_id, node_id)`-keyed
+//! chain of `:HAS_HISTORY` -> `:NodeHistory` nodes, each holding a JSON
+//! property snapshot and a `[valid_from, valid_to)` validity interval
+//! (`valid_to` absent means "still current"). That turns
+//! `sentinel-discover`'s `diff` module -- which only ever compares the
+//! in-flight scan against the current graph state -- into a queryable time
+//! series: [`GraphClient::node_at`] answers "what did this node look like
+//! at time T", and [`GraphClient::nodes_at`]/[`GraphClient::changes_between`]
+//! answer it for a whole label at once.
+//!
+//! Scope: only the batched `upsert_nodes` path records history today. The
+//! generic single-node upsert and edge upserts/deletes don't yet feed this
+//! log; the CRDT-versioned `upsert_host`/`upsert_service`/`upsert_port`
+//! methods already carry enough information (`*_version_ms`/
+//! `*_version_writer`) to reconstruct a coarser history later, so wiring
+//! them into this same log is a natural follow-up rather than part of this
+//! change.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+
+use sentinel_core::{NodeId, TenantId};
+
+use crate::client::{GraphClient, GraphError};
+use crate::queries::NodeRecord;
+
+/// One node that changed between two [`GraphClient::changes_between`]
+/// snapshots, carrying both the `before` and `after` property sets.
+#[derive(Debug, Clone)]
+pub struct ModifiedNode {
+    pub before: NodeRecord,
+    pub after: NodeRecord,
+}
+
+/// Nodes added, removed, and modified between two points in time for one
+/// label, per [`GraphClient::changes_between`].
+///
+/// Only node history is tracked today (see this module's doc comment), so
+/// there's no edge-level equivalent yet.
+#[derive(Debug, Clone, Default)]
+pub struct NodeChangeSet {
+    pub added: Vec<NodeRecord>,
+    pub removed: Vec<NodeRecord>,
+    pub modified: Vec<ModifiedNode>,
+}
+
+impl GraphClient {
+    /// What did this node's properties look like at `at`?
+    ///
+    /// Returns `None` if no history snapshot covers `at` (the node didn't
+    /// exist yet, or `at` predates this subsystem being enabled), rather
+    /// than [`GraphError::NotFound`] -- a point-in-time miss isn't an
+    /// error the way looking up a live node by id and getting nothing is.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
+    pub async fn node_at(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        node_id: &NodeId,
+        at: DateTime<Utc>,
+    ) -> Result<Option<NodeRecord>, GraphError> {
+        let cypher = format!(
+            "MATCH (n:{label} {{tenant_id: $tenant_id, id: $id}})-[:HAS_HISTORY]->(h:NodeHistory)
+             WHERE h.valid_from <= $at AND (h.valid_to IS NULL OR h.valid_to > $at)
+             RETURN h.properties AS snapshot
+             LIMIT 1"
+        );
+        let q = query(&cypher)
+            .param("tenant_id", tenant_id.0.to_string())
+            .param("id", node_id.0.to_string())
+            .param("at", at.to_rfc3339());
+
+        match self.query_one_for("node_at", q).await? {
+            Some(row) => {
+                let snapshot: String = row.get("snapshot").unwrap_or_default();
+                Ok(Some(snapshot_to_record(
+                    &snapshot,
+                    label,
+                    &tenant_id.0.to_string(),
+                    &node_id.0.to_string(),
+                )?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// A full point-in-time snapshot of every node of `label` as of `at` --
+    /// the label-wide version of [`node_at`](Self::node_at).
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
+    pub async fn nodes_at(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<NodeRecord>, GraphError> {
+        let cypher = format!(
+            "MATCH (n:{label} {{tenant_id: $tenant_id}})-[:HAS_HISTORY]->(h:NodeHistory)
+             WHERE h.valid_from <= $at AND (h.valid_to IS NULL OR h.valid_to > $at)
+             RETURN n.id AS id, h.properties AS snapshot"
+        );
+        let q = query(&cypher)
+            .param("tenant_id", tenant_id.0.to_string())
+            .param("at", at.to_rfc3339());
+
+        let rows = self.query_rows_for("nodes_at", q).await?;
+        rows.iter()
+            .map(|row| {
+                let id: String = row.get("id").unwrap_or_default();
+                let snapshot: String = row.get("snapshot").unwrap_or_default();
+                snapshot_to_record(&snapshot, label, &tenant_id.0.to_string(), &id)
+            })
+            .collect()
+    }
+
+    /// Diff every node of `label` between two points in time, returning
+    /// what was added, removed, and modified. See [`NodeChangeSet`].
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
+    pub async fn changes_between(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        t0: DateTime<Utc>,
+        t1: DateTime<Utc>,
+    ) -> Result<NodeChangeSet, GraphError> {
+        let mut before: HashMap<String, NodeRecord> = self
+            .nodes_at(tenant_id, label, t0)
+            .await?
+            .into_iter()
+            .map(|r| (r.id.clone(), r))
+            .collect();
+        let after = self.nodes_at(tenant_id, label, t1).await?;
+
+        let mut added = Vec::new();
+        let mut modified = Vec::new();
+
+        for after_record in after {
+            match before.remove(&after_record.id) {
+                None => added.push(after_record),
+                Some(before_record) => {
+                    if before_record.properties != after_record.properties {
+                        modified.push(ModifiedNode {
+                            before: before_record,
+                            after: after_record,
+                        });
+                    }
+                }
+            }
+        }
+
+        let removed = before.into_values().collect();
+        Ok(NodeChangeSet {
+            added,
+            removed,
+            modified,
+        })
+    }
+}
+
+/// Parse a `NodeHistory.properties` JSON-string snapshot back into a
+/// [`NodeRecord`].
+fn snapshot_to_record(
+    snapshot: &str,
+    label: &str,
+    tenant_id: &str,
+    id: &str,
+) -> Result<NodeRecord, GraphError> {
+    let properties = serde_json::from_str(snapshot)
+        .map_err(|e| GraphError::Serialization(format!("invalid history snapshot: {e}")))?;
+    Ok(NodeRecord {
+        id: id.to_string(),
+        label: label.to_string(),
+        tenant_id: tenant_id.to_string(),
+        properties,
+    })
+}
@@ -5,7 +5,14 @@
 //! tenant isolation, schema compliance, and delta tracking.
 
 pub mod client;
+pub mod history;
 pub mod mutations;
 pub mod queries;
+pub mod sqlite_writer;
+pub mod writer;
 
 pub use client::{GraphClient, GraphConfig, GraphError};
+pub use history::{ModifiedNode, NodeChangeSet};
+pub use mutations::UpsertCounts;
+pub use sqlite_writer::SqliteGraphWriter;
+pub use writer::GraphWriter;
@@ -2,24 +2,57 @@
 //!
 //! All mutations use MERGE (upsert) semantics to handle idempotent
 //! re-discovery. Nodes are identified by (tenant_id, id).
+//!
+//! Every write is wrapped in a `tracing::instrument` span carrying
+//! `tenant_id` and the node label / edge type it touches, and funnels
+//! through `GraphClient::run_for` so the `sentinel.graph.query.*`
+//! duration/error metrics cover writes the same as reads do (see
+//! `queries.rs`). On top of that, each upsert/stale pass bumps a
+//! domain-specific counter (`sentinel.graph.nodes_upserted_total`,
+//! `sentinel.graph.edges_upserted_total`, `sentinel.graph.stale_marked_total`,
+//! `sentinel.graph.stale_removed_total`) labeled by node label or edge type,
+//! so a scan run's write volume is visible per-kind rather than just in
+//! aggregate.
+
+use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
-use neo4rs::query;
+use neo4rs::{query, BoltType, Query};
 
+use sentinel_core::crdt::{FieldVersion, HostFieldVersions};
 use sentinel_core::{
-    Edge, EdgeProperties, EdgeType, Host, Node, NodeId, Service, TenantId, User, Vulnerability,
+    Edge, EdgeProperties, EdgeType, Host, Node, NodeId, Port, Service, TenantId, User,
+    Vulnerability,
 };
 
 use crate::client::{GraphClient, GraphError};
 
+/// Created vs. matched counts from a batch UNWIND upsert, keyed by label
+/// (for [`GraphClient::upsert_nodes`]) or relationship type (for
+/// [`GraphClient::upsert_edges`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpsertCounts {
+    pub created: i64,
+    pub matched: i64,
+}
+
 impl GraphClient {
     // ── Node Upserts ─────────────────────────────────────────────
 
     /// Upsert any node type into the graph.
+    ///
+    /// Host, Service, and Port nodes carry CRDT field versions; callers
+    /// that don't have version information (e.g. batch imports) fall
+    /// back to [`FieldVersion::genesis`], which any version-aware writer
+    /// will dominate on the next upsert.
     pub async fn upsert_node(&self, node: &Node) -> Result<(), GraphError> {
         match node {
-            Node::Host(h) => self.upsert_host(h).await,
-            Node::Service(s) => self.upsert_service(s).await,
+            Node::Host(h) => {
+                self.upsert_host(h, &HostFieldVersions::stamped(FieldVersion::genesis()))
+                    .await
+            }
+            Node::Service(s) => self.upsert_service(s, &FieldVersion::genesis()).await,
+            Node::Port(p) => self.upsert_port(p, &FieldVersion::genesis()).await,
             Node::User(u) => self.upsert_user(u).await,
             Node::Vulnerability(v) => self.upsert_vulnerability(v).await,
             _ => self.upsert_generic_node(node).await,
@@ -27,7 +60,20 @@ impl GraphClient {
     }
 
     /// Upsert a Host node.
-    pub async fn upsert_host(&self, host: &Host) -> Result<(), GraphError> {
+    ///
+    /// `versions` carries the already-resolved CRDT version for each
+    /// mutable field group (`hostname`, `os`, `mac_address`, `tags`).
+    /// Resolution itself happens upstream (see `sentinel-discover`'s
+    /// diff module) — by the time a caller reaches this method, `host`'s
+    /// fields already reflect whichever writer won, so this is a plain
+    /// unconditional upsert that also persists the winning versions for
+    /// the next conflict to resolve against.
+    #[tracing::instrument(skip(self, host, versions), fields(db_system = "neo4j", tenant_id = %host.tenant_id.0, label = "Host"))]
+    pub async fn upsert_host(
+        &self,
+        host: &Host,
+        versions: &HostFieldVersions,
+    ) -> Result<(), GraphError> {
         let q = query(
             "MERGE (n:Host {tenant_id: $tenant_id, id: $id})
              ON CREATE SET
@@ -36,14 +82,32 @@ impl GraphClient {
                n.cloud_provider = $cloud_provider,
                n.cloud_instance_id = $cloud_instance_id,
                n.cloud_region = $cloud_region, n.criticality = $criticality,
-               n.tags = $tags, n.first_seen = $now, n.last_seen = $now
+               n.tags = $tags,
+               n.hostname_version_ms = $hostname_version_ms,
+               n.hostname_version_writer = $hostname_version_writer,
+               n.os_version_ms = $os_version_ms,
+               n.os_version_writer = $os_version_writer,
+               n.mac_address_version_ms = $mac_address_version_ms,
+               n.mac_address_version_writer = $mac_address_version_writer,
+               n.tags_version_ms = $tags_version_ms,
+               n.tags_version_writer = $tags_version_writer,
+               n.first_seen = $now, n.last_seen = $now
              ON MATCH SET
                n.ip = $ip, n.hostname = $hostname, n.os = $os,
                n.os_version = $os_version, n.mac_address = $mac_address,
                n.cloud_provider = $cloud_provider,
                n.cloud_instance_id = $cloud_instance_id,
                n.cloud_region = $cloud_region, n.criticality = $criticality,
-               n.tags = $tags, n.last_seen = $now",
+               n.tags = $tags,
+               n.hostname_version_ms = $hostname_version_ms,
+               n.hostname_version_writer = $hostname_version_writer,
+               n.os_version_ms = $os_version_ms,
+               n.os_version_writer = $os_version_writer,
+               n.mac_address_version_ms = $mac_address_version_ms,
+               n.mac_address_version_writer = $mac_address_version_writer,
+               n.tags_version_ms = $tags_version_ms,
+               n.tags_version_writer = $tags_version_writer,
+               n.last_seen = $now",
         )
         .param("tenant_id", host.tenant_id.0.to_string())
         .param("id", host.id.0.to_string())
@@ -57,22 +121,45 @@ impl GraphClient {
         .param("cloud_region", opt_string(&host.cloud_region))
         .param("criticality", ser(&host.criticality))
         .param("tags", host.tags.clone())
+        .param("hostname_version_ms", versions.hostname.wallclock_ms)
+        .param("hostname_version_writer", versions.hostname.writer_id.clone())
+        .param("os_version_ms", versions.os.wallclock_ms)
+        .param("os_version_writer", versions.os.writer_id.clone())
+        .param("mac_address_version_ms", versions.mac_address.wallclock_ms)
+        .param(
+            "mac_address_version_writer",
+            versions.mac_address.writer_id.clone(),
+        )
+        .param("tags_version_ms", versions.tags.wallclock_ms)
+        .param("tags_version_writer", versions.tags.writer_id.clone())
         .param("now", Utc::now().to_rfc3339());
 
-        self.run(q).await
+        self.run_for("upsert_host", q).await?;
+        count_node_upserted("Host");
+        Ok(())
     }
 
     /// Upsert a Service node.
-    pub async fn upsert_service(&self, svc: &Service) -> Result<(), GraphError> {
+    ///
+    /// `version` is the already-resolved CRDT version for this service —
+    /// see [`Self::upsert_host`] for why resolution happens upstream.
+    #[tracing::instrument(skip(self, svc, version), fields(db_system = "neo4j", tenant_id = %svc.tenant_id.0, label = "Service"))]
+    pub async fn upsert_service(
+        &self,
+        svc: &Service,
+        version: &FieldVersion,
+    ) -> Result<(), GraphError> {
         let q = query(
             "MERGE (n:Service {tenant_id: $tenant_id, id: $id})
              ON CREATE SET
                n.name = $name, n.version = $version, n.port = $port,
                n.protocol = $protocol, n.state = $state, n.banner = $banner,
+               n.version_ms = $version_ms, n.version_writer = $version_writer,
                n.first_seen = $now, n.last_seen = $now
              ON MATCH SET
                n.name = $name, n.version = $version, n.port = $port,
                n.protocol = $protocol, n.state = $state, n.banner = $banner,
+               n.version_ms = $version_ms, n.version_writer = $version_writer,
                n.last_seen = $now",
         )
         .param("tenant_id", svc.tenant_id.0.to_string())
@@ -83,12 +170,48 @@ impl GraphClient {
         .param("protocol", ser(&svc.protocol))
         .param("state", ser(&svc.state))
         .param("banner", opt_string(&svc.banner))
+        .param("version_ms", version.wallclock_ms)
+        .param("version_writer", version.writer_id.clone())
+        .param("now", Utc::now().to_rfc3339());
+
+        self.run_for("upsert_service", q).await?;
+        count_node_upserted("Service");
+        Ok(())
+    }
+
+    /// Upsert a Port node.
+    ///
+    /// `version` is the already-resolved CRDT version for this port —
+    /// see [`Self::upsert_host`] for why resolution happens upstream.
+    #[tracing::instrument(skip(self, port, version), fields(db_system = "neo4j", tenant_id = %port.tenant_id.0, label = "Port"))]
+    pub async fn upsert_port(&self, port: &Port, version: &FieldVersion) -> Result<(), GraphError> {
+        let q = query(
+            "MERGE (n:Port {tenant_id: $tenant_id, id: $id})
+             ON CREATE SET
+               n.number = $number, n.protocol = $protocol, n.state = $state,
+               n.version_ms = $version_ms, n.version_writer = $version_writer,
+               n.first_seen = $now, n.last_seen = $now
+             ON MATCH SET
+               n.number = $number, n.protocol = $protocol, n.state = $state,
+               n.version_ms = $version_ms, n.version_writer = $version_writer,
+               n.last_seen = $now",
+        )
+        .param("tenant_id", port.tenant_id.0.to_string())
+        .param("id", port.id.0.to_string())
+        .param("number", port.number as i64)
+        .param("protocol", ser(&port.protocol))
+        .param("state", ser(&port.state))
+        .param("version_ms", version.wallclock_ms)
+        .param("version_writer", version.writer_id.clone())
         .param("now", Utc::now().to_rfc3339());
 
-        self.run(q).await
+        self.run_for("upsert_port", q).await?;
+        count_node_upserted("Port");
+        Ok(())
     }
 
     /// Upsert a User node.
+    #[tracing::instrument(skip(self, user), fields(db_system = "neo4j", tenant_id = %user.tenant_id.0, label = "User"))]
     pub async fn upsert_user(&self, user: &User) -> Result<(), GraphError> {
         let q = query(
             "MERGE (n:User {tenant_id: $tenant_id, id: $id})
@@ -114,10 +237,13 @@ impl GraphClient {
         .param("mfa_enabled", user.mfa_enabled.unwrap_or(false))
         .param("now", Utc::now().to_rfc3339());
 
-        self.run(q).await
+        self.run_for("upsert_user", q).await?;
+        count_node_upserted("User");
+        Ok(())
     }
 
     /// Upsert a Vulnerability node.
+    #[tracing::instrument(skip(self, vuln), fields(db_system = "neo4j", tenant_id = %vuln.tenant_id.0, label = "Vulnerability"))]
     pub async fn upsert_vulnerability(&self, vuln: &Vulnerability) -> Result<(), GraphError> {
         let q = query(
             "MERGE (n:Vulnerability {tenant_id: $tenant_id, id: $id})
@@ -146,35 +272,28 @@ impl GraphClient {
         .param("in_cisa_kev", vuln.in_cisa_kev)
         .param("now", Utc::now().to_rfc3339());
 
-        self.run(q).await
+        self.run_for("upsert_vulnerability", q).await?;
+        count_node_upserted("Vulnerability");
+        Ok(())
     }
 
     /// Generic upsert for node types without specialized handling.
+    #[tracing::instrument(skip(self, node), fields(db_system = "neo4j", tenant_id = %node.tenant_id().0, label = node_label(node)))]
     async fn upsert_generic_node(&self, node: &Node) -> Result<(), GraphError> {
         let label = node_label(node);
         let tenant_id = node.tenant_id().0.to_string();
         let node_id = node.id().0.to_string();
-        let props_json =
-            serde_json::to_string(node).map_err(|e| GraphError::Serialization(e.to_string()))?;
-
-        let cypher = format!(
-            "MERGE (n:{label} {{tenant_id: $tenant_id, id: $id}})
-             SET n += apoc.convert.fromJsonMap($props)
-             SET n.last_seen = $now"
-        );
-
-        let q = query(&cypher)
-            .param("tenant_id", tenant_id)
-            .param("id", node_id)
-            .param("props", props_json)
-            .param("now", Utc::now().to_rfc3339());
+        let q = generic_upsert_query(label, &tenant_id, &node_id, node)?;
 
-        self.run(q).await
+        self.run_for("upsert_generic_node", q).await?;
+        count_node_upserted(label);
+        Ok(())
     }
 
     // ── Edge Upserts ─────────────────────────────────────────────
 
     /// Upsert an edge between two nodes.
+    #[tracing::instrument(skip(self, edge), fields(db_system = "neo4j", tenant_id = %edge.tenant_id.0, edge_type = edge_type_to_cypher(&edge.edge_type)))]
     pub async fn upsert_edge(&self, edge: &Edge) -> Result<(), GraphError> {
         let rel_type = edge_type_to_cypher(&edge.edge_type);
         let cypher = format!(
@@ -186,11 +305,13 @@ impl GraphClient {
                r.protocol = $protocol, r.port = $port,
                r.encrypted = $encrypted, r.permissions = $permissions,
                r.exploitability_score = $exploitability_score,
+               r.external_ip = $external_ip, r.external_port = $external_port,
                r.first_seen = $now, r.last_seen = $now
              ON MATCH SET
                r.protocol = $protocol, r.port = $port,
                r.encrypted = $encrypted, r.permissions = $permissions,
                r.exploitability_score = $exploitability_score,
+               r.external_ip = $external_ip, r.external_port = $external_port,
                r.last_seen = $now"
         );
 
@@ -207,9 +328,14 @@ impl GraphClient {
                 "exploitability_score",
                 edge.properties.exploitability_score.unwrap_or(0.0),
             )
+            .param("external_ip", opt_string(&edge.properties.external_ip))
+            .param("external_port", edge.properties.external_port.unwrap_or(0) as i64)
             .param("now", Utc::now().to_rfc3339());
 
-        self.run(q).await
+        self.run_for("upsert_edge", q).await?;
+        metrics::counter!("sentinel.graph.edges_upserted_total", "edge_type" => rel_type.to_string())
+            .increment(1);
+        Ok(())
     }
 
     /// Upsert an edge between two nodes identified by their IDs.
@@ -238,6 +364,7 @@ impl GraphClient {
 
     /// Mark nodes of a given label as stale if last_seen < cutoff.
     /// Returns the count of stale nodes found.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
     pub async fn mark_stale(
         &self,
         tenant_id: &TenantId,
@@ -255,14 +382,18 @@ impl GraphClient {
             .param("tenant_id", tenant_id.0.to_string())
             .param("cutoff", cutoff.to_rfc3339());
 
-        match self.query_one(q).await? {
-            Some(row) => Ok(row.get::<i64>("cnt").unwrap_or(0)),
-            None => Ok(0),
-        }
+        let count = match self.query_one_for("mark_stale", q).await? {
+            Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+            None => 0,
+        };
+        metrics::counter!("sentinel.graph.stale_marked_total", "label" => label.to_string())
+            .increment(count as u64);
+        Ok(count)
     }
 
     /// Delete stale nodes for a tenant and label.
     /// Returns the count of deleted nodes.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
     pub async fn remove_stale(
         &self,
         tenant_id: &TenantId,
@@ -280,45 +411,200 @@ impl GraphClient {
             .param("tenant_id", tenant_id.0.to_string())
             .param("cutoff", cutoff.to_rfc3339());
 
-        match self.query_one(q).await? {
-            Some(row) => Ok(row.get::<i64>("cnt").unwrap_or(0)),
-            None => Ok(0),
-        }
+        let count = match self.query_one_for("remove_stale", q).await? {
+            Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+            None => 0,
+        };
+        metrics::counter!("sentinel.graph.stale_removed_total", "label" => label.to_string())
+            .increment(count as u64);
+        Ok(count)
     }
 
     // ── Batch Operations ─────────────────────────────────────────
 
-    /// Upsert multiple nodes in a single transaction.
-    pub async fn upsert_nodes(&self, nodes: &[Node]) -> Result<(), GraphError> {
+    /// Upsert multiple nodes, grouped by label and chunked to bound
+    /// transaction memory, returning per-label created/matched counts.
+    ///
+    /// Each label gets one `UNWIND $rows AS row MERGE ... SET n += row.props`
+    /// query per chunk instead of one round-trip per node -- the dominant
+    /// cost when a scan discovers thousands of hosts/services at once.
+    /// `first_seen` is still only set the first time a node is created
+    /// (`ON CREATE SET`); UNWIND doesn't change that per-row semantics.
+    ///
+    /// Alongside the live upsert, each row also appends a `NodeHistory`
+    /// snapshot (see `history.rs`) so the result is queryable as a time
+    /// series afterwards via [`GraphClient::node_at`]/
+    /// [`GraphClient::changes_between`], not just diffed against the
+    /// in-flight scan the way `sentinel-discover`'s `diff` module already
+    /// does.
+    ///
+    /// The whole transaction (every label's every chunk, through the final
+    /// commit) is replayed as a unit by [`GraphClient::retry_graph`] if it
+    /// hits a transient error -- Neo4j transactions can't be resumed
+    /// mid-chunk, so a deadlock or dropped connection partway through
+    /// discards whatever the aborted transaction staged and starts a fresh
+    /// one from scratch rather than leaving partial state behind.
+    #[tracing::instrument(skip(self, nodes), fields(db_system = "neo4j", node_count = nodes.len()))]
+    pub async fn upsert_nodes(
+        &self,
+        nodes: &[Node],
+    ) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        let mut by_label: HashMap<&'static str, Vec<&Node>> = HashMap::new();
+        for node in nodes {
+            by_label.entry(node_label(node)).or_default().push(node);
+        }
+
+        let started = std::time::Instant::now();
+        let totals = self
+            .retry_graph("upsert_nodes", || self.run_upsert_nodes_txn(&by_label))
+            .await?;
+
+        for label in totals.keys() {
+            count_node_upserted(label);
+        }
+        metrics::histogram!("sentinel.graph.query.duration_ms", "operation" => "upsert_nodes")
+            .record(started.elapsed().as_secs_f64() * 1000.0);
+        Ok(totals)
+    }
+
+    /// Run one full attempt of the `upsert_nodes` transaction: a fresh
+    /// [`neo4rs::Txn`], one `UNWIND` query per label/chunk, and a commit.
+    /// Replayed wholesale by [`retry_graph`](Self::retry_graph) on a
+    /// transient failure; see [`upsert_nodes`](Self::upsert_nodes)'s doc
+    /// comment for why that's safe here.
+    async fn run_upsert_nodes_txn(
+        &self,
+        by_label: &HashMap<&'static str, Vec<&Node>>,
+    ) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        const CHUNK_SIZE: usize = 10_000;
+
+        let mut totals: HashMap<String, UpsertCounts> = HashMap::new();
         let mut txn = self.start_txn().await?;
 
-        for node in nodes {
-            let label = node_label(node);
-            let tenant_id = node.tenant_id().0.to_string();
-            let node_id = node.id().0.to_string();
-            let props_json = serde_json::to_string(node)
-                .map_err(|e| GraphError::Serialization(e.to_string()))?;
+        for (label, label_nodes) in by_label {
+            let mut counts = UpsertCounts::default();
+            for chunk in label_nodes.chunks(CHUNK_SIZE) {
+                let rows = build_upsert_rows(chunk)?;
+                let now = Utc::now().to_rfc3339();
+                let cypher = format!(
+                    "UNWIND $rows AS row
+                     MERGE (n:{label} {{tenant_id: row.tenant_id, id: row.id}})
+                     ON CREATE SET n.first_seen = $now
+                     SET n += row.props, n.last_seen = $now
+                     RETURN
+                       count(CASE WHEN n.first_seen = $now THEN 1 END) AS created,
+                       count(CASE WHEN n.first_seen <> $now THEN 1 END) AS matched"
+                );
+
+                let q = query(&cypher).param("rows", rows).param("now", now.clone());
+                let mut stream = txn.execute(q).await?;
+                if let Some(row) = stream.next().await? {
+                    counts.created += row.get::<i64>("created").unwrap_or(0);
+                    counts.matched += row.get::<i64>("matched").unwrap_or(0);
+                }
+
+                let history_rows = build_history_rows(chunk)?;
+                let history_cypher = format!(
+                    "UNWIND $rows AS row
+                     MATCH (n:{label} {{tenant_id: row.tenant_id, id: row.id}})
+                     OPTIONAL MATCH (n)-[:HAS_HISTORY]->(prev:NodeHistory)
+                     WHERE prev.valid_to IS NULL
+                     SET prev.valid_to = $now
+                     WITH n, row
+                     CREATE (n)-[:HAS_HISTORY]->(:NodeHistory {{
+                       tenant_id: row.tenant_id, node_id: row.id, label: $label,
+                       properties: row.snapshot, valid_from: $now
+                     }})"
+                );
+                let hq = query(&history_cypher)
+                    .param("rows", history_rows)
+                    .param("now", now)
+                    .param("label", label.to_string());
+                txn.execute(hq).await?;
+            }
+            totals.insert(label.to_string(), counts);
+        }
 
-            let cypher = format!(
-                "MERGE (n:{label} {{tenant_id: $tenant_id, id: $id}})
-                 SET n += apoc.convert.fromJsonMap($props)
-                 SET n.last_seen = $now"
-            );
+        txn.commit().await?;
+        Ok(totals)
+    }
+
+    /// Upsert multiple edges, grouped by relationship type and chunked to
+    /// bound transaction memory, returning per-edge-type created/matched
+    /// counts. Mirrors [`GraphClient::upsert_nodes`]'s UNWIND batching and
+    /// whole-transaction retry-on-transient-error behavior.
+    #[tracing::instrument(skip(self, edges), fields(db_system = "neo4j", edge_count = edges.len()))]
+    pub async fn upsert_edges(
+        &self,
+        edges: &[Edge],
+    ) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        let mut by_type: HashMap<&'static str, Vec<&Edge>> = HashMap::new();
+        for edge in edges {
+            by_type
+                .entry(edge_type_to_cypher(&edge.edge_type))
+                .or_default()
+                .push(edge);
+        }
+
+        let started = std::time::Instant::now();
+        let totals = self
+            .retry_graph("upsert_edges", || self.run_upsert_edges_txn(&by_type))
+            .await?;
+
+        for (rel_type, counts) in &totals {
+            metrics::counter!("sentinel.graph.edges_upserted_total", "edge_type" => rel_type.clone())
+                .increment((counts.created + counts.matched) as u64);
+        }
+        metrics::histogram!("sentinel.graph.query.duration_ms", "operation" => "upsert_edges")
+            .record(started.elapsed().as_secs_f64() * 1000.0);
+        Ok(totals)
+    }
+
+    /// Run one full attempt of the `upsert_edges` transaction. Replayed
+    /// wholesale by [`retry_graph`](Self::retry_graph) on a transient
+    /// failure, mirroring [`run_upsert_nodes_txn`](Self::run_upsert_nodes_txn).
+    async fn run_upsert_edges_txn(
+        &self,
+        by_type: &HashMap<&'static str, Vec<&Edge>>,
+    ) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        const CHUNK_SIZE: usize = 10_000;
 
-            let q = query(&cypher)
-                .param("tenant_id", tenant_id)
-                .param("id", node_id)
-                .param("props", props_json)
-                .param("now", Utc::now().to_rfc3339());
+        let mut totals: HashMap<String, UpsertCounts> = HashMap::new();
+        let mut txn = self.start_txn().await?;
 
-            txn.run(q).await?;
+        for (rel_type, type_edges) in by_type {
+            let mut counts = UpsertCounts::default();
+            for chunk in type_edges.chunks(CHUNK_SIZE) {
+                let rows = build_edge_upsert_rows(chunk)?;
+                let now = Utc::now().to_rfc3339();
+                let cypher = format!(
+                    "UNWIND $rows AS row
+                     MATCH (a {{tenant_id: row.tenant_id, id: row.source_id}})
+                     MATCH (b {{tenant_id: row.tenant_id, id: row.target_id}})
+                     MERGE (a)-[r:{rel_type} {{id: row.edge_id}}]->(b)
+                     ON CREATE SET r.tenant_id = row.tenant_id, r.first_seen = $now
+                     SET r += row.props, r.last_seen = $now
+                     RETURN
+                       count(CASE WHEN r.first_seen = $now THEN 1 END) AS created,
+                       count(CASE WHEN r.first_seen <> $now THEN 1 END) AS matched"
+                );
+
+                let q = query(&cypher).param("rows", rows).param("now", now);
+                let mut stream = txn.execute(q).await?;
+                if let Some(row) = stream.next().await? {
+                    counts.created += row.get::<i64>("created").unwrap_or(0);
+                    counts.matched += row.get::<i64>("matched").unwrap_or(0);
+                }
+            }
+            totals.insert(rel_type.to_string(), counts);
         }
 
         txn.commit().await?;
-        Ok(())
+        Ok(totals)
     }
 
     /// Delete a node by tenant, label, and id.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0, label))]
     pub async fn delete_node(
         &self,
         tenant_id: &TenantId,
@@ -334,14 +620,18 @@ impl GraphClient {
             .param("tenant_id", tenant_id.0.to_string())
             .param("id", node_id.0.to_string());
 
-        self.run(q).await
+        self.run_for("delete_node", q).await
     }
 }
 
 // ── Helpers ──────────────────────────────────────────────────────
 
 /// Get the Neo4j label for a node variant.
-fn node_label(node: &Node) -> &'static str {
+///
+/// `pub(crate)` so other [`crate::writer::GraphWriter`] backends (e.g.
+/// `sqlite_writer`) can reuse the same label scheme instead of inventing
+/// their own.
+pub(crate) fn node_label(node: &Node) -> &'static str {
     match node {
         Node::Host(_) => "Host",
         Node::Service(_) => "Service",
@@ -360,7 +650,10 @@ fn node_label(node: &Node) -> &'static str {
 }
 
 /// Convert EdgeType to its Cypher relationship type string.
-fn edge_type_to_cypher(et: &EdgeType) -> &'static str {
+///
+/// `pub(crate)` so other [`crate::writer::GraphWriter`] backends can use
+/// the same naming for their own edge-type column/key.
+pub(crate) fn edge_type_to_cypher(et: &EdgeType) -> &'static str {
     match et {
         EdgeType::ConnectsTo => "CONNECTS_TO",
         EdgeType::HasAccess => "HAS_ACCESS",
@@ -376,6 +669,179 @@ fn edge_type_to_cypher(et: &EdgeType) -> &'static str {
         EdgeType::HasCertificate => "HAS_CERTIFICATE",
         EdgeType::BelongsToSubnet => "BELONGS_TO_SUBNET",
         EdgeType::BelongsToVpc => "BELONGS_TO_VPC",
+        EdgeType::ExposedExternally => "EXPOSED_EXTERNALLY",
+    }
+}
+
+/// Bump the `sentinel.graph.nodes_upserted_total` counter for `label`.
+fn count_node_upserted(label: &str) {
+    metrics::counter!("sentinel.graph.nodes_upserted_total", "label" => label.to_string())
+        .increment(1);
+}
+
+/// Build the MERGE+SET query shared by [`GraphClient::upsert_generic_node`]
+/// and the batch path in [`GraphClient::upsert_nodes`].
+///
+/// Each scalar field of `node`'s serialized JSON gets its own `SET n.key =
+/// $pN` clause and bound parameter, rather than a single
+/// `apoc.convert.fromJsonMap($props)` call -- that requires the APOC plugin,
+/// which isn't guaranteed to be installed, and won't exist at all for a
+/// non-Neo4j `GraphWriter` backend. `tenant_id`/`id` are skipped since the
+/// `MERGE` clause already binds them.
+fn generic_upsert_query(
+    label: &str,
+    tenant_id: &str,
+    node_id: &str,
+    node: &Node,
+) -> Result<Query, GraphError> {
+    let props =
+        serde_json::to_value(node).map_err(|e| GraphError::Serialization(e.to_string()))?;
+    let props = props.as_object().cloned().unwrap_or_default();
+
+    let mut clauses = vec!["n.last_seen = $now".to_string()];
+    let mut bound = Vec::new();
+    for (idx, (key, value)) in props.iter().enumerate() {
+        if key == "tenant_id" || key == "id" {
+            continue;
+        }
+        let param = format!("p{idx}");
+        clauses.push(format!("n.{key} = ${param}"));
+        bound.push((param, value));
+    }
+
+    let cypher = format!(
+        "MERGE (n:{label} {{tenant_id: $tenant_id, id: $id}})
+         SET {}",
+        clauses.join(", ")
+    );
+
+    let mut q = query(&cypher)
+        .param("tenant_id", tenant_id.to_string())
+        .param("id", node_id.to_string())
+        .param("now", Utc::now().to_rfc3339());
+    for (param, value) in bound {
+        q = bind_json_property(q, &param, value);
+    }
+    Ok(q)
+}
+
+/// Bind a single JSON-object field as a Cypher parameter, choosing the
+/// closest native type for each `serde_json::Value` variant. `Null` binds
+/// as an empty string, matching this file's existing `opt_string`
+/// convention that an empty string means "absent".
+fn bind_json_property(q: Query, param: &str, value: &serde_json::Value) -> Query {
+    match value {
+        serde_json::Value::Null => q.param(param, String::new()),
+        serde_json::Value::Bool(b) => q.param(param, *b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => q.param(param, i),
+            None => q.param(param, n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => q.param(param, s.clone()),
+        serde_json::Value::Array(items) => {
+            let strings: Vec<String> = items
+                .iter()
+                .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                .collect();
+            q.param(param, strings)
+        }
+        serde_json::Value::Object(_) => q.param(param, value.to_string()),
+    }
+}
+
+/// Build the `$rows` list for [`GraphClient::upsert_nodes`]'s UNWIND query:
+/// one map per node, each with `tenant_id`/`id` at the top level (so they
+/// can drive the `MERGE` key) and every other scalar field nested under
+/// `props` (so `SET n += row.props` doesn't clobber the identity fields).
+fn build_upsert_rows(nodes: &[&Node]) -> Result<Vec<BoltType>, GraphError> {
+    nodes
+        .iter()
+        .map(|node| {
+            let json = serde_json::to_value(node)
+                .map_err(|e| GraphError::Serialization(e.to_string()))?;
+            let mut props = json.as_object().cloned().unwrap_or_default();
+            props.remove("tenant_id");
+            props.remove("id");
+
+            let mut row: HashMap<String, BoltType> = HashMap::new();
+            row.insert("tenant_id".to_string(), node.tenant_id().0.to_string().into());
+            row.insert("id".to_string(), node.id().0.to_string().into());
+            row.insert(
+                "props".to_string(),
+                json_to_bolt(&serde_json::Value::Object(props)),
+            );
+            Ok(BoltType::from(row))
+        })
+        .collect()
+}
+
+/// Build the `$rows` list for the `NodeHistory`-appending UNWIND query
+/// [`GraphClient::upsert_nodes`] runs alongside its main upsert: one map
+/// per node, with `tenant_id`/`id` to find the live node and a `snapshot`
+/// -- the whole node serialized to a JSON *string* rather than nested as a
+/// map, since `NodeHistory.properties` is a plain string property (Neo4j
+/// node properties can't hold nested maps; see `bind_json_property` for
+/// the same constraint on the single-row generic upsert path).
+fn build_history_rows(nodes: &[&Node]) -> Result<Vec<BoltType>, GraphError> {
+    nodes
+        .iter()
+        .map(|node| {
+            let snapshot = serde_json::to_string(node)
+                .map_err(|e| GraphError::Serialization(e.to_string()))?;
+
+            let mut row: HashMap<String, BoltType> = HashMap::new();
+            row.insert("tenant_id".to_string(), node.tenant_id().0.to_string().into());
+            row.insert("id".to_string(), node.id().0.to_string().into());
+            row.insert("snapshot".to_string(), snapshot.into());
+            Ok(BoltType::from(row))
+        })
+        .collect()
+}
+
+/// Build the `$rows` list for [`GraphClient::upsert_edges`]'s UNWIND query:
+/// one map per edge, with `tenant_id`/`source_id`/`target_id`/`edge_id` at
+/// the top level to drive the `MATCH`/`MERGE`, and the rest of the edge's
+/// properties nested under `props`.
+fn build_edge_upsert_rows(edges: &[&Edge]) -> Result<Vec<BoltType>, GraphError> {
+    edges
+        .iter()
+        .map(|edge| {
+            let props_json = serde_json::to_value(&edge.properties)
+                .map_err(|e| GraphError::Serialization(e.to_string()))?;
+
+            let mut row: HashMap<String, BoltType> = HashMap::new();
+            row.insert("tenant_id".to_string(), edge.tenant_id.0.to_string().into());
+            row.insert("source_id".to_string(), edge.source_id.0.to_string().into());
+            row.insert("target_id".to_string(), edge.target_id.0.to_string().into());
+            row.insert("edge_id".to_string(), edge.id.0.to_string().into());
+            row.insert("props".to_string(), json_to_bolt(&props_json));
+            Ok(BoltType::from(row))
+        })
+        .collect()
+}
+
+/// Convert a `serde_json::Value` into the equivalent `neo4rs::BoltType`,
+/// the reverse of `queries.rs`'s `bolt_to_json`. Used to build nested
+/// `props` maps for the UNWIND-based batch upserts above, where the
+/// per-property `$pN` parameters [`bind_json_property`] uses for a single
+/// row don't apply -- an UNWIND row is one parameter, not many.
+fn json_to_bolt(value: &serde_json::Value) -> BoltType {
+    match value {
+        serde_json::Value::Null => BoltType::from(String::new()),
+        serde_json::Value::Bool(b) => BoltType::from(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => BoltType::from(i),
+            None => BoltType::from(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => BoltType::from(s.clone()),
+        serde_json::Value::Array(items) => {
+            BoltType::from(items.iter().map(json_to_bolt).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(map) => BoltType::from(
+            map.iter()
+                .map(|(k, v)| (k.clone(), json_to_bolt(v)))
+                .collect::<HashMap<String, BoltType>>(),
+        ),
     }
 }
 
@@ -1,5 +1,8 @@
 //! Read operations and Cypher query builder for the knowledge graph.
 
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
 use neo4rs::query;
 
 use sentinel_core::{NodeId, TenantId};
@@ -39,15 +42,48 @@ pub struct SubgraphResult {
     pub edges: Vec<EdgeRecord>,
 }
 
+/// A keyset-pagination cursor for [`GraphClient::list_nodes_after`]: the
+/// `(last_seen, id)` of the last row on the previous page. Encoded as
+/// `base64(json)` so callers can pass it around as an opaque string; decode
+/// with [`Cursor::decode`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Cursor {
+    pub last_seen: DateTime<Utc>,
+    pub id: String,
+}
+
+impl Cursor {
+    /// Decode a cursor string returned by a previous [`GraphClient::list_nodes_after`] call.
+    pub fn decode(raw: &str) -> Result<Self, GraphError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(raw)
+            .map_err(|e| GraphError::Serialization(format!("invalid cursor: {e}")))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| GraphError::Serialization(format!("invalid cursor: {e}")))
+    }
+
+    /// Encode as an opaque string suitable for returning to a caller.
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("Cursor serializes infallibly");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+}
+
 impl GraphClient {
     // ── Single Node Lookups ──────────────────────────────────────
 
     /// Get a node by label, tenant, and id.
+    ///
+    /// `projection` limits extracted properties to the given keys (cheap
+    /// path for callers that only need a subset); `None` extracts every
+    /// property present on the node.
+    #[tracing::instrument(skip(self, projection), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn get_node(
         &self,
         tenant_id: &TenantId,
         label: &str,
         node_id: &NodeId,
+        projection: Option<&[&str]>,
     ) -> Result<NodeRecord, GraphError> {
         let cypher = format!(
             "MATCH (n:{label} {{tenant_id: $tenant_id, id: $id}})
@@ -58,12 +94,12 @@ impl GraphClient {
             .param("tenant_id", tenant_id.0.to_string())
             .param("id", node_id.0.to_string());
 
-        match self.query_one(q).await? {
+        match self.query_one_for("get_node", q).await? {
             Some(row) => {
                 let node: neo4rs::Node = row.get("n").map_err(|e| {
                     GraphError::Serialization(format!("Failed to deserialize node: {e}"))
                 })?;
-                Ok(neo4j_node_to_record(&node, label))
+                Ok(neo4j_node_to_record(&node, label, projection))
             }
             None => Err(GraphError::NotFound {
                 label: label.to_string(),
@@ -74,12 +110,16 @@ impl GraphClient {
     }
 
     /// Get a node by a property lookup (e.g., Host by IP).
+    ///
+    /// See [`get_node`](Self::get_node) for `projection`.
+    #[tracing::instrument(skip(self, projection), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn find_node_by_property(
         &self,
         tenant_id: &TenantId,
         label: &str,
         property: &str,
         value: &str,
+        projection: Option<&[&str]>,
     ) -> Result<Option<NodeRecord>, GraphError> {
         let cypher = format!(
             "MATCH (n:{label} {{tenant_id: $tenant_id, {property}: $value}})
@@ -90,12 +130,12 @@ impl GraphClient {
             .param("tenant_id", tenant_id.0.to_string())
             .param("value", value.to_string());
 
-        match self.query_one(q).await? {
+        match self.query_one_for("find_node_by_property", q).await? {
             Some(row) => {
                 let node: neo4rs::Node = row.get("n").map_err(|e| {
                     GraphError::Serialization(format!("Failed to deserialize node: {e}"))
                 })?;
-                Ok(Some(neo4j_node_to_record(&node, label)))
+                Ok(Some(neo4j_node_to_record(&node, label, projection)))
             }
             None => Ok(None),
         }
@@ -104,12 +144,16 @@ impl GraphClient {
     // ── List Queries ─────────────────────────────────────────────
 
     /// List all nodes of a given label for a tenant.
+    ///
+    /// See [`get_node`](Self::get_node) for `projection`.
+    #[tracing::instrument(skip(self, projection), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn list_nodes(
         &self,
         tenant_id: &TenantId,
         label: &str,
         limit: u32,
         offset: u32,
+        projection: Option<&[&str]>,
     ) -> Result<Vec<NodeRecord>, GraphError> {
         let cypher = format!(
             "MATCH (n:{label} {{tenant_id: $tenant_id}})
@@ -123,18 +167,134 @@ impl GraphClient {
             .param("limit", limit as i64)
             .param("offset", offset as i64);
 
-        let rows = self.query_rows(q).await?;
+        let rows = self.query_rows_for("list_nodes", q).await?;
         let mut results = Vec::with_capacity(rows.len());
         for row in rows {
             let node: neo4rs::Node = row.get("n").map_err(|e| {
                 GraphError::Serialization(format!("Failed to deserialize node: {e}"))
             })?;
-            results.push(neo4j_node_to_record(&node, label));
+            results.push(neo4j_node_to_record(&node, label, projection));
         }
         Ok(results)
     }
 
+    /// Keyset-pagination variant of [`list_nodes`](Self::list_nodes): pass
+    /// the opaque cursor string returned by the previous call as `after` to
+    /// fetch the next page, keeping read cost constant regardless of how
+    /// deep the caller pages, unlike `SKIP`/`LIMIT` which re-scans every
+    /// skipped row.
+    ///
+    /// Returns the page of records plus an opaque next-cursor, or `None`
+    /// when the page came back short (fewer than `limit` rows), meaning
+    /// there's nothing left to fetch.
+    #[tracing::instrument(skip(self, after, projection), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
+    pub async fn list_nodes_after(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        after: Option<&str>,
+        limit: u32,
+        projection: Option<&[&str]>,
+    ) -> Result<(Vec<NodeRecord>, Option<String>), GraphError> {
+        let cursor = after.map(Cursor::decode).transpose()?;
+
+        let cypher = format!(
+            "MATCH (n:{label} {{tenant_id: $tenant_id}})
+             WHERE $cursor_ts IS NULL
+                OR n.last_seen < $cursor_ts
+                OR (n.last_seen = $cursor_ts AND n.id < $cursor_id)
+             RETURN n
+             ORDER BY n.last_seen DESC, n.id DESC
+             LIMIT $limit"
+        );
+
+        let q = query(&cypher)
+            .param("tenant_id", tenant_id.0.to_string())
+            .param("limit", limit as i64)
+            .param("cursor_ts", cursor.as_ref().map(|c| c.last_seen.to_rfc3339()))
+            .param("cursor_id", cursor.as_ref().map(|c| c.id.clone()));
+
+        let rows = self.query_rows_for("list_nodes_after", q).await?;
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            let node: neo4rs::Node = row.get("n").map_err(|e| {
+                GraphError::Serialization(format!("Failed to deserialize node: {e}"))
+            })?;
+            results.push(neo4j_node_to_record(&node, label, projection));
+        }
+
+        let next_cursor = if results.len() < limit as usize {
+            None
+        } else {
+            results.last().and_then(|last| {
+                let last_seen = last
+                    .properties
+                    .get("last_seen")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc))?;
+                Some(Cursor { last_seen, id: last.id.clone() }.encode())
+            })
+        };
+
+        Ok((results, next_cursor))
+    }
+
+    /// List just the `ip` property of every node of a given label for a
+    /// tenant, without the rest of its properties.
+    ///
+    /// Used by `sentinel-discover`'s Bloom-filter stale-host reconciliation
+    /// (see `sentinel_core::bloom`) to avoid paying for a full
+    /// [`Self::list_nodes`] property load just to compare IP sets.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
+    pub async fn list_node_ips(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        limit: u32,
+    ) -> Result<Vec<String>, GraphError> {
+        let cypher = format!(
+            "MATCH (n:{label} {{tenant_id: $tenant_id}})
+             RETURN n.ip AS ip
+             LIMIT $limit"
+        );
+
+        let q = query(&cypher)
+            .param("tenant_id", tenant_id.0.to_string())
+            .param("limit", limit as i64);
+
+        let rows = self.query_rows_for("list_node_ips", q).await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| row.get::<String>("ip").ok())
+            .collect())
+    }
+
+    /// List the `ip` of every `Host` with at least one `HasCve` edge to an
+    /// exploitable `Vulnerability`.
+    ///
+    /// Used by `sentinel-discover`'s criticality-weighted scan scheduling
+    /// (see `sentinel_discover::priority`) as one of the signals that boosts
+    /// a host's scan priority.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
+    pub async fn list_exploitable_host_ips(
+        &self,
+        tenant_id: &TenantId,
+    ) -> Result<Vec<String>, GraphError> {
+        let cypher = "MATCH (h:Host {tenant_id: $tenant_id})-[:HAS_CVE]->(v:Vulnerability {exploitable: true})
+             RETURN DISTINCT h.ip AS ip";
+
+        let q = query(cypher).param("tenant_id", tenant_id.0.to_string());
+
+        let rows = self.query_rows_for("list_exploitable_host_ips", q).await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| row.get::<String>("ip").ok())
+            .collect())
+    }
+
     /// Count nodes of a given label for a tenant.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn count_nodes(&self, tenant_id: &TenantId, label: &str) -> Result<i64, GraphError> {
         let cypher = format!(
             "MATCH (n:{label} {{tenant_id: $tenant_id}})
@@ -143,7 +303,7 @@ impl GraphClient {
 
         let q = query(&cypher).param("tenant_id", tenant_id.0.to_string());
 
-        match self.query_one(q).await? {
+        match self.query_one_for("count_nodes", q).await? {
             Some(row) => Ok(row.get::<i64>("cnt").unwrap_or(0)),
             None => Ok(0),
         }
@@ -152,6 +312,7 @@ impl GraphClient {
     // ── Neighbor Queries ─────────────────────────────────────────
 
     /// Get all neighbors of a node (any direction, any relationship type).
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn get_neighbors(
         &self,
         tenant_id: &TenantId,
@@ -168,7 +329,7 @@ impl GraphClient {
         .param("id", node_id.0.to_string())
         .param("limit", limit as i64);
 
-        let rows = self.query_rows(q).await?;
+        let rows = self.query_rows_for("get_neighbors", q).await?;
         let mut results = Vec::with_capacity(rows.len());
 
         for row in rows {
@@ -179,7 +340,7 @@ impl GraphClient {
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
 
-            let node_record = neo4j_node_to_record(&neo_node, &label);
+            let node_record = neo4j_node_to_record(&neo_node, &label, None);
 
             let neo_rel: neo4rs::Relation = row
                 .get("r")
@@ -205,6 +366,7 @@ impl GraphClient {
     // ── Path Queries ─────────────────────────────────────────────
 
     /// Find shortest path between two nodes.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn shortest_path(
         &self,
         tenant_id: &TenantId,
@@ -224,7 +386,7 @@ impl GraphClient {
         .param("from", from_id.0.to_string())
         .param("to", to_id.0.to_string());
 
-        let rows = self.query_rows(q).await?;
+        let rows = self.query_rows_for("shortest_path", q).await?;
         let mut results = Vec::with_capacity(rows.len());
         for row in rows {
             let neo_node: neo4rs::Node = row.get("n").map_err(|e| {
@@ -232,7 +394,7 @@ impl GraphClient {
             })?;
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
-            results.push(neo4j_node_to_record(&neo_node, &label));
+            results.push(neo4j_node_to_record(&neo_node, &label, None));
         }
         Ok(results)
     }
@@ -241,7 +403,13 @@ impl GraphClient {
 
     /// Fetch the full subgraph for a tenant: all nodes and all directed edges.
     ///
-    /// Used by sentinel-pathfind for in-memory graph construction.
+    /// Used by sentinel-pathfind for in-memory graph construction. Streams
+    /// both phases row-by-row via [`GraphClient::for_each_row`] and
+    /// converts each one immediately, rather than collecting a
+    /// `Vec<neo4rs::Row>` up front and converting it afterwards -- on a
+    /// 50,000-node tenant that's one fewer full copy of the result set
+    /// sitting in memory at once.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn fetch_subgraph(
         &self,
         tenant_id: &TenantId,
@@ -257,17 +425,17 @@ impl GraphClient {
         .param("tenant_id", tenant_id.0.to_string())
         .param("limit", node_limit as i64);
 
-        let node_rows = self.query_rows(node_query).await?;
-        let mut nodes = Vec::with_capacity(node_rows.len());
-
-        for row in &node_rows {
+        let mut nodes = Vec::new();
+        self.for_each_row_for("fetch_subgraph.nodes", node_query, |row| {
             let neo_node: neo4rs::Node = row.get("n").map_err(|e| {
                 GraphError::Serialization(format!("Failed to deserialize subgraph node: {e}"))
             })?;
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
-            nodes.push(neo4j_node_to_record(&neo_node, &label));
-        }
+            nodes.push(neo4j_node_to_record(&neo_node, &label, None));
+            Ok(())
+        })
+        .await?;
 
         // Phase 2: fetch all directed edges.
         let edge_query = query(
@@ -278,10 +446,8 @@ impl GraphClient {
         .param("tenant_id", tenant_id.0.to_string())
         .param("limit", edge_limit as i64);
 
-        let edge_rows = self.query_rows(edge_query).await?;
-        let mut edges = Vec::with_capacity(edge_rows.len());
-
-        for row in &edge_rows {
+        let mut edges = Vec::new();
+        self.for_each_row_for("fetch_subgraph.edges", edge_query, |row| {
             let rel_type: String = row.get("rel_type").unwrap_or_default();
             let src: String = row.get("src").unwrap_or_default();
             let tgt: String = row.get("tgt").unwrap_or_default();
@@ -314,12 +480,15 @@ impl GraphClient {
                 target_id: tgt,
                 properties: serde_json::Value::Object(props),
             });
-        }
+            Ok(())
+        })
+        .await?;
 
         Ok(SubgraphResult { nodes, edges })
     }
 
     /// Fetch a neighborhood subgraph within N hops of a specific node.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn fetch_neighborhood(
         &self,
         tenant_id: &TenantId,
@@ -337,7 +506,7 @@ impl GraphClient {
         .param("tenant_id", tenant_id.0.to_string())
         .param("center_id", center_node_id.to_string());
 
-        let node_rows = self.query_rows(node_query).await?;
+        let node_rows = self.query_rows_for("fetch_neighborhood.nodes", node_query).await?;
         let mut nodes = Vec::with_capacity(node_rows.len() + 1);
 
         // Also add the center node itself.
@@ -348,13 +517,16 @@ impl GraphClient {
         .param("tenant_id", tenant_id.0.to_string())
         .param("center_id", center_node_id.to_string());
 
-        if let Some(row) = self.query_one(center_query).await? {
+        if let Some(row) = self
+            .query_one_for("fetch_neighborhood.center", center_query)
+            .await?
+        {
             let neo_node: neo4rs::Node = row.get("n").map_err(|e| {
                 GraphError::Serialization(format!("Failed to get center node: {e}"))
             })?;
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
-            nodes.push(neo4j_node_to_record(&neo_node, &label));
+            nodes.push(neo4j_node_to_record(&neo_node, &label, None));
         }
 
         for row in &node_rows {
@@ -363,7 +535,7 @@ impl GraphClient {
             })?;
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
-            nodes.push(neo4j_node_to_record(&neo_node, &label));
+            nodes.push(neo4j_node_to_record(&neo_node, &label, None));
         }
 
         // Fetch edges between nodes in the neighborhood.
@@ -383,7 +555,7 @@ impl GraphClient {
         .param("tenant_id", tenant_id.0.to_string())
         .param("ids", node_ids);
 
-        let edge_rows = self.query_rows(edge_query).await?;
+        let edge_rows = self.query_rows_for("fetch_neighborhood.edges", edge_query).await?;
         let mut edges = Vec::with_capacity(edge_rows.len());
 
         for row in &edge_rows {
@@ -418,6 +590,7 @@ impl GraphClient {
     // ── Full-Text Search ─────────────────────────────────────────
 
     /// Full-text search across indexed node types.
+    #[tracing::instrument(skip(self), fields(db_system = "neo4j", tenant_id = %tenant_id.0))]
     pub async fn search(
         &self,
         tenant_id: &TenantId,
@@ -437,7 +610,7 @@ impl GraphClient {
         .param("term", search_term.to_string())
         .param("limit", limit as i64);
 
-        let rows = self.query_rows(q).await?;
+        let rows = self.query_rows_for("search", q).await?;
         let mut results = Vec::with_capacity(rows.len());
         for row in rows {
             let neo_node: neo4rs::Node = row.get("node").map_err(|e| {
@@ -445,36 +618,40 @@ impl GraphClient {
             })?;
             let labels: Vec<String> = row.get("labels").unwrap_or_default();
             let label = labels.first().cloned().unwrap_or_default();
-            results.push(neo4j_node_to_record(&neo_node, &label));
+            results.push(neo4j_node_to_record(&neo_node, &label, None));
         }
         Ok(results)
     }
 }
 
 /// Convert a neo4rs::Node to our lightweight NodeRecord.
-fn neo4j_node_to_record(node: &neo4rs::Node, label: &str) -> NodeRecord {
+///
+/// `id`/`tenant_id` are always pulled out as structured fields and never
+/// duplicated into `properties`. The rest of the node's properties are
+/// extracted dynamically -- via [`bolt_to_json`] -- rather than against a
+/// fixed allowlist, so a new node type or a custom enrichment property
+/// shows up in `NodeRecord.properties` without a code change here.
+/// `projection`, when given, limits extraction to just those keys.
+fn neo4j_node_to_record(
+    node: &neo4rs::Node,
+    label: &str,
+    projection: Option<&[&str]>,
+) -> NodeRecord {
     let id: String = node.get("id").unwrap_or_default();
     let tenant_id: String = node.get("tenant_id").unwrap_or_default();
 
     let mut props = serde_json::Map::new();
-    // Extract common properties that may exist on various node types
-    for key in &[
-        "ip",
-        "hostname",
-        "name",
-        "username",
-        "cve_id",
-        "last_seen",
-        "first_seen",
-        "os",
-        "criticality",
-        "severity",
-        "email",
-        "cidr",
-        "endpoint",
-    ] {
-        if let Ok(v) = node.get::<String>(key) {
-            props.insert((*key).to_string(), serde_json::Value::String(v));
+    let keys: Vec<String> = match projection {
+        Some(fields) => fields.iter().map(|f| f.to_string()).collect(),
+        None => node.keys().into_iter().map(|k| k.to_string()).collect(),
+    };
+
+    for key in keys {
+        if key == "id" || key == "tenant_id" {
+            continue;
+        }
+        if let Ok(value) = node.get::<neo4rs::BoltType>(&key) {
+            props.insert(key, bolt_to_json(&value));
         }
     }
 
@@ -485,3 +662,49 @@ fn neo4j_node_to_record(node: &neo4rs::Node, label: &str) -> NodeRecord {
         properties: serde_json::Value::Object(props),
     }
 }
+
+/// Recursively convert a raw `neo4rs::BoltType` property value into the
+/// equivalent `serde_json::Value`, so arbitrary Neo4j property shapes
+/// (nested lists/maps included) round-trip through [`NodeRecord`] without
+/// us having to know each node type's schema up front.
+fn bolt_to_json(value: &neo4rs::BoltType) -> serde_json::Value {
+    use neo4rs::BoltType;
+
+    match value {
+        BoltType::Null(_) => serde_json::Value::Null,
+        BoltType::Boolean(b) => serde_json::Value::Bool(b.value),
+        BoltType::Integer(i) => serde_json::Value::from(i.value),
+        BoltType::Float(f) => serde_json::json!(f.value),
+        BoltType::String(s) => serde_json::Value::String(s.value.clone()),
+        BoltType::List(l) => serde_json::Value::Array(l.value.iter().map(bolt_to_json).collect()),
+        BoltType::Map(m) => serde_json::Value::Object(
+            m.value
+                .iter()
+                .map(|(k, v)| (k.value.clone(), bolt_to_json(v)))
+                .collect(),
+        ),
+        _ => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn cursor_round_trips_through_encode_decode() {
+        let cursor = Cursor {
+            last_seen: Utc.with_ymd_and_hms(2026, 7, 26, 12, 0, 0).unwrap(),
+            id: "host-123".to_string(),
+        };
+
+        let decoded = Cursor::decode(&cursor.encode()).expect("valid cursor");
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn cursor_decode_rejects_garbage() {
+        assert!(Cursor::decode("not-a-cursor").is_err());
+    }
+}
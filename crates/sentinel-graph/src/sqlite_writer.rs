@@ -0,0 +1,296 @@
+//! Embedded SQLite-backed [`GraphWriter`] for tests and single-node
+//! deployments that don't want to run Neo4j.
+//!
+//! Unlike [`GraphClient`](crate::client::GraphClient), this backend keeps no
+//! per-node-type schema: every node is a `(tenant_id, id, label, properties)`
+//! row with `properties` as a JSON blob, and every edge a
+//! `(tenant_id, id, source_id, target_id, edge_type, properties)` row. It
+//! also does no CRDT field-version resolution -- `upsert_host`/
+//! `upsert_service`/`upsert_port` accept and ignore their `versions`
+//! argument, always writing whatever the caller passed, unconditionally.
+//! Callers that need last-writer-wins conflict resolution across
+//! concurrent scanners should use `GraphClient` against Neo4j instead.
+//!
+//! Methods are `async` to satisfy the [`GraphWriter`] trait, but do their
+//! work synchronously against a `Mutex<Connection>` in the method body, the
+//! same way `sentinel-engram`'s `GitEngramStore` uses its SQLite index.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+
+use sentinel_core::crdt::{FieldVersion, HostFieldVersions};
+use sentinel_core::{
+    Edge, EdgeProperties, EdgeType, Host, Node, NodeId, Port, Service, TenantId, User,
+    Vulnerability,
+};
+
+use crate::client::GraphError;
+use crate::mutations::{edge_type_to_cypher, node_label, UpsertCounts};
+use crate::writer::GraphWriter;
+
+/// Embedded, APOC-free [`GraphWriter`] backed by a local SQLite file.
+pub struct SqliteGraphWriter {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteGraphWriter {
+    /// Open (creating if needed) a SQLite-backed writer at `path`.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let conn =
+            Connection::open(path).map_err(|e| GraphError::Backend(e.to_string()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS nodes (
+                tenant_id TEXT NOT NULL,
+                id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                properties TEXT NOT NULL,
+                last_seen TEXT NOT NULL,
+                stale INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (tenant_id, id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_nodes_label ON nodes(tenant_id, label);
+            CREATE TABLE IF NOT EXISTS edges (
+                tenant_id TEXT NOT NULL,
+                id TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                target_id TEXT NOT NULL,
+                edge_type TEXT NOT NULL,
+                properties TEXT NOT NULL,
+                last_seen TEXT NOT NULL,
+                PRIMARY KEY (tenant_id, id)
+            );",
+        )
+        .map_err(|e| GraphError::Backend(e.to_string()))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Upsert a single node row, returning whether it was newly created
+    /// (as opposed to an existing row being updated) so batch callers can
+    /// report created/matched counts the same way `GraphClient` does.
+    fn upsert_node_row(&self, node: &Node) -> Result<bool, GraphError> {
+        let label = node_label(node);
+        let tenant_id = node.tenant_id().0.to_string();
+        let node_id = node.id().0.to_string();
+        let properties = serde_json::to_string(node)
+            .map_err(|e| GraphError::Serialization(e.to_string()))?;
+
+        let conn = self.conn.lock().expect("sqlite graph writer mutex poisoned");
+        let existed: bool = conn
+            .query_row(
+                "SELECT 1 FROM nodes WHERE tenant_id = ?1 AND id = ?2",
+                rusqlite::params![tenant_id, node_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| GraphError::Backend(e.to_string()))?
+            .is_some();
+
+        conn.execute(
+            "INSERT INTO nodes (tenant_id, id, label, properties, last_seen, stale)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0)
+             ON CONFLICT(tenant_id, id) DO UPDATE SET
+                label = excluded.label,
+                properties = excluded.properties,
+                last_seen = excluded.last_seen,
+                stale = 0",
+            rusqlite::params![
+                tenant_id,
+                node_id,
+                label,
+                properties,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|e| GraphError::Backend(e.to_string()))?;
+        Ok(!existed)
+    }
+
+    /// Upsert a single edge row, returning whether it was newly created.
+    fn upsert_edge_row(&self, edge: &Edge) -> Result<bool, GraphError> {
+        let edge_type = edge_type_to_cypher(&edge.edge_type);
+        let tenant_id = edge.tenant_id.0.to_string();
+        let edge_id = edge.id.0.to_string();
+        let properties = serde_json::to_string(&edge.properties)
+            .map_err(|e| GraphError::Serialization(e.to_string()))?;
+
+        let conn = self.conn.lock().expect("sqlite graph writer mutex poisoned");
+        let existed: bool = conn
+            .query_row(
+                "SELECT 1 FROM edges WHERE tenant_id = ?1 AND id = ?2",
+                rusqlite::params![tenant_id, edge_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| GraphError::Backend(e.to_string()))?
+            .is_some();
+
+        conn.execute(
+            "INSERT INTO edges (tenant_id, id, source_id, target_id, edge_type, properties, last_seen)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(tenant_id, id) DO UPDATE SET
+                source_id = excluded.source_id,
+                target_id = excluded.target_id,
+                edge_type = excluded.edge_type,
+                properties = excluded.properties,
+                last_seen = excluded.last_seen",
+            rusqlite::params![
+                tenant_id,
+                edge_id,
+                edge.source_id.0.to_string(),
+                edge.target_id.0.to_string(),
+                edge_type,
+                properties,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|e| GraphError::Backend(e.to_string()))?;
+        Ok(!existed)
+    }
+}
+
+#[async_trait]
+impl GraphWriter for SqliteGraphWriter {
+    async fn upsert_node(&self, node: &Node) -> Result<(), GraphError> {
+        self.upsert_node_row(node).map(|_| ())
+    }
+
+    async fn upsert_host(&self, host: &Host, _versions: &HostFieldVersions) -> Result<(), GraphError> {
+        self.upsert_node_row(&Node::Host(host.clone())).map(|_| ())
+    }
+
+    async fn upsert_service(
+        &self,
+        service: &Service,
+        _version: &FieldVersion,
+    ) -> Result<(), GraphError> {
+        self.upsert_node_row(&Node::Service(service.clone())).map(|_| ())
+    }
+
+    async fn upsert_port(&self, port: &Port, _version: &FieldVersion) -> Result<(), GraphError> {
+        self.upsert_node_row(&Node::Port(port.clone())).map(|_| ())
+    }
+
+    async fn upsert_user(&self, user: &User) -> Result<(), GraphError> {
+        self.upsert_node_row(&Node::User(user.clone())).map(|_| ())
+    }
+
+    async fn upsert_vulnerability(&self, vuln: &Vulnerability) -> Result<(), GraphError> {
+        self.upsert_node_row(&Node::Vulnerability(vuln.clone())).map(|_| ())
+    }
+
+    async fn upsert_edge(&self, edge: &Edge) -> Result<(), GraphError> {
+        self.upsert_edge_row(edge).map(|_| ())
+    }
+
+    async fn upsert_edge_by_ids(
+        &self,
+        tenant_id: &TenantId,
+        source_id: &NodeId,
+        target_id: &NodeId,
+        edge_type: &EdgeType,
+        properties: &EdgeProperties,
+    ) -> Result<(), GraphError> {
+        let edge = Edge {
+            id: sentinel_core::types::EdgeId::new(),
+            tenant_id: tenant_id.clone(),
+            source_id: source_id.clone(),
+            target_id: target_id.clone(),
+            edge_type: edge_type.clone(),
+            properties: properties.clone(),
+            first_seen: Utc::now(),
+            last_seen: Utc::now(),
+        };
+        self.upsert_edge(&edge).await
+    }
+
+    async fn upsert_nodes(&self, nodes: &[Node]) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        let mut totals: HashMap<String, UpsertCounts> = HashMap::new();
+        for node in nodes {
+            let created = self.upsert_node_row(node)?;
+            let counts = totals.entry(node_label(node).to_string()).or_default();
+            if created {
+                counts.created += 1;
+            } else {
+                counts.matched += 1;
+            }
+        }
+        Ok(totals)
+    }
+
+    async fn upsert_edges(&self, edges: &[Edge]) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        let mut totals: HashMap<String, UpsertCounts> = HashMap::new();
+        for edge in edges {
+            let created = self.upsert_edge_row(edge)?;
+            let counts = totals
+                .entry(edge_type_to_cypher(&edge.edge_type).to_string())
+                .or_default();
+            if created {
+                counts.created += 1;
+            } else {
+                counts.matched += 1;
+            }
+        }
+        Ok(totals)
+    }
+
+    async fn mark_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError> {
+        let conn = self.conn.lock().expect("sqlite graph writer mutex poisoned");
+        let count = conn
+            .execute(
+                "UPDATE nodes SET stale = 1
+                 WHERE tenant_id = ?1 AND label = ?2 AND last_seen < ?3",
+                rusqlite::params![tenant_id.0.to_string(), label, cutoff.to_rfc3339()],
+            )
+            .map_err(|e| GraphError::Backend(e.to_string()))?;
+        Ok(count as i64)
+    }
+
+    async fn remove_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError> {
+        let conn = self.conn.lock().expect("sqlite graph writer mutex poisoned");
+        let count = conn
+            .execute(
+                "DELETE FROM nodes WHERE tenant_id = ?1 AND label = ?2 AND last_seen < ?3",
+                rusqlite::params![tenant_id.0.to_string(), label, cutoff.to_rfc3339()],
+            )
+            .map_err(|e| GraphError::Backend(e.to_string()))?;
+        Ok(count as i64)
+    }
+
+    async fn delete_node(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        node_id: &NodeId,
+    ) -> Result<(), GraphError> {
+        let conn = self.conn.lock().expect("sqlite graph writer mutex poisoned");
+        conn.execute(
+            "DELETE FROM nodes WHERE tenant_id = ?1 AND label = ?2 AND id = ?3",
+            rusqlite::params![tenant_id.0.to_string(), label, node_id.0.to_string()],
+        )
+        .map_err(|e| GraphError::Backend(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM edges WHERE tenant_id = ?1 AND (source_id = ?2 OR target_id = ?2)",
+            rusqlite::params![tenant_id.0.to_string(), node_id.0.to_string()],
+        )
+        .map_err(|e| GraphError::Backend(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! `GraphWriter` — trait abstraction over graph write backends.
+//!
+//! [`GraphClient`] is the only implementation most callers need, but its
+//! generic-node/edge upserts no longer depend on APOC (see `mutations.rs`),
+//! which makes it possible to swap in a simpler, embedded backend for
+//! tests or single-node deployments that don't want to run Neo4j at all.
+//! `sqlite_writer` provides that second implementation.
+//!
+//! Callers that only need to write to the graph -- not run the read-side
+//! queries in `queries.rs` -- can depend on `dyn GraphWriter` instead of
+//! `GraphClient` directly, the same way `sentinel-engram` callers depend
+//! on `dyn EngramStore` instead of `GitEngramStore`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use sentinel_core::crdt::{FieldVersion, HostFieldVersions};
+use sentinel_core::{
+    Edge, EdgeProperties, EdgeType, Host, Node, NodeId, Port, Service, TenantId, User,
+    Vulnerability,
+};
+
+use std::collections::HashMap;
+
+use crate::client::{GraphClient, GraphError};
+use crate::mutations::UpsertCounts;
+
+/// Write-side operations against the knowledge graph.
+///
+/// Method signatures mirror [`GraphClient`]'s inherent upsert/delete
+/// methods exactly; see their doc comments in `mutations.rs` for the
+/// semantics of each (MERGE-based upsert, CRDT field versions, etc.).
+#[async_trait]
+pub trait GraphWriter: Send + Sync {
+    async fn upsert_node(&self, node: &Node) -> Result<(), GraphError>;
+
+    async fn upsert_host(
+        &self,
+        host: &Host,
+        versions: &HostFieldVersions,
+    ) -> Result<(), GraphError>;
+
+    async fn upsert_service(
+        &self,
+        service: &Service,
+        version: &FieldVersion,
+    ) -> Result<(), GraphError>;
+
+    async fn upsert_port(
+        &self,
+        port: &Port,
+        version: &FieldVersion,
+    ) -> Result<(), GraphError>;
+
+    async fn upsert_user(&self, user: &User) -> Result<(), GraphError>;
+
+    async fn upsert_vulnerability(&self, vuln: &Vulnerability) -> Result<(), GraphError>;
+
+    async fn upsert_edge(&self, edge: &Edge) -> Result<(), GraphError>;
+
+    async fn upsert_edge_by_ids(
+        &self,
+        tenant_id: &TenantId,
+        source_id: &NodeId,
+        target_id: &NodeId,
+        edge_type: &EdgeType,
+        properties: &EdgeProperties,
+    ) -> Result<(), GraphError>;
+
+    async fn upsert_nodes(&self, nodes: &[Node]) -> Result<HashMap<String, UpsertCounts>, GraphError>;
+
+    async fn upsert_edges(&self, edges: &[Edge]) -> Result<HashMap<String, UpsertCounts>, GraphError>;
+
+    async fn mark_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError>;
+
+    async fn remove_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError>;
+
+    async fn delete_node(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        node_id: &NodeId,
+    ) -> Result<(), GraphError>;
+}
+
+#[async_trait]
+impl GraphWriter for GraphClient {
+    async fn upsert_node(&self, node: &Node) -> Result<(), GraphError> {
+        GraphClient::upsert_node(self, node).await
+    }
+
+    async fn upsert_host(
+        &self,
+        host: &Host,
+        versions: &HostFieldVersions,
+    ) -> Result<(), GraphError> {
+        GraphClient::upsert_host(self, host, versions).await
+    }
+
+    async fn upsert_service(
+        &self,
+        service: &Service,
+        version: &FieldVersion,
+    ) -> Result<(), GraphError> {
+        GraphClient::upsert_service(self, service, version).await
+    }
+
+    async fn upsert_port(
+        &self,
+        port: &Port,
+        version: &FieldVersion,
+    ) -> Result<(), GraphError> {
+        GraphClient::upsert_port(self, port, version).await
+    }
+
+    async fn upsert_user(&self, user: &User) -> Result<(), GraphError> {
+        GraphClient::upsert_user(self, user).await
+    }
+
+    async fn upsert_vulnerability(&self, vuln: &Vulnerability) -> Result<(), GraphError> {
+        GraphClient::upsert_vulnerability(self, vuln).await
+    }
+
+    async fn upsert_edge(&self, edge: &Edge) -> Result<(), GraphError> {
+        GraphClient::upsert_edge(self, edge).await
+    }
+
+    async fn upsert_edge_by_ids(
+        &self,
+        tenant_id: &TenantId,
+        source_id: &NodeId,
+        target_id: &NodeId,
+        edge_type: &EdgeType,
+        properties: &EdgeProperties,
+    ) -> Result<(), GraphError> {
+        GraphClient::upsert_edge_by_ids(self, tenant_id, source_id, target_id, edge_type, properties)
+            .await
+    }
+
+    async fn upsert_nodes(&self, nodes: &[Node]) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        GraphClient::upsert_nodes(self, nodes).await
+    }
+
+    async fn upsert_edges(&self, edges: &[Edge]) -> Result<HashMap<String, UpsertCounts>, GraphError> {
+        GraphClient::upsert_edges(self, edges).await
+    }
+
+    async fn mark_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError> {
+        GraphClient::mark_stale(self, tenant_id, label, cutoff).await
+    }
+
+    async fn remove_stale(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, GraphError> {
+        GraphClient::remove_stale(self, tenant_id, label, cutoff).await
+    }
+
+    async fn delete_node(
+        &self,
+        tenant_id: &TenantId,
+        label: &str,
+        node_id: &NodeId,
+    ) -> Result<(), GraphError> {
+        GraphClient::delete_node(self, tenant_id, label, node_id).await
+    }
+}
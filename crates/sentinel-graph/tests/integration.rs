@@ -6,6 +6,7 @@
 //! Skipped automatically if Neo4j is not available.
 
 use sentinel_core::{
+    crdt::{FieldVersion, HostFieldVersions},
     types::{
         CloudProvider, Criticality, EdgeId, EdgeProperties, IdentitySource, Protocol, ServiceState,
         UserType, VulnSeverity,
@@ -16,6 +17,14 @@ use sentinel_graph::{GraphClient, GraphConfig};
 
 use chrono::Utc;
 
+fn test_versions() -> HostFieldVersions {
+    HostFieldVersions::stamped(FieldVersion::new(Utc::now().timestamp_millis(), "test-writer"))
+}
+
+fn test_version() -> FieldVersion {
+    FieldVersion::new(Utc::now().timestamp_millis(), "test-writer")
+}
+
 async fn connect_or_skip() -> Option<GraphClient> {
     let config = GraphConfig::default();
     match GraphClient::connect(&config).await {
@@ -69,10 +78,10 @@ async fn test_upsert_and_get_host() {
     let host_id = host.id.clone();
 
     // Create
-    client.upsert_host(&host).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
 
     // Read back
-    let record = client.get_node(&tid, "Host", &host_id).await.unwrap();
+    let record = client.get_node(&tid, "Host", &host_id, None).await.unwrap();
     assert_eq!(record.id, host_id.0.to_string());
     assert_eq!(record.label, "Host");
 
@@ -98,8 +107,8 @@ async fn test_upsert_host_is_idempotent() {
     let host = make_host(&tid, "10.0.2.1", "db-01");
 
     // Upsert twice
-    client.upsert_host(&host).await.unwrap();
-    client.upsert_host(&host).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
 
     // Should still be exactly 1 node
     let count = client.count_nodes(&tid, "Host").await.unwrap();
@@ -130,9 +139,9 @@ async fn test_upsert_service_and_list() {
         last_seen: Utc::now(),
     };
 
-    client.upsert_service(&svc).await.unwrap();
+    client.upsert_service(&svc, &test_version()).await.unwrap();
 
-    let nodes = client.list_nodes(&tid, "Service", 10, 0).await.unwrap();
+    let nodes = client.list_nodes(&tid, "Service", 10, 0, None).await.unwrap();
     assert_eq!(nodes.len(), 1);
     assert_eq!(
         nodes[0].properties.get("name").and_then(|v| v.as_str()),
@@ -216,7 +225,7 @@ async fn test_upsert_edge_and_neighbors() {
 
     let host = make_host(&tid, "10.0.3.1", "app-01");
     let host_id = host.id.clone();
-    client.upsert_host(&host).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
 
     let svc = Service {
         id: NodeId::new(),
@@ -231,7 +240,7 @@ async fn test_upsert_edge_and_neighbors() {
         last_seen: Utc::now(),
     };
     let svc_id = svc.id.clone();
-    client.upsert_service(&svc).await.unwrap();
+    client.upsert_service(&svc, &test_version()).await.unwrap();
 
     // Create edge: Host -[RUNS_ON]-> Service
     let edge = Edge {
@@ -264,11 +273,11 @@ async fn test_find_node_by_property() {
     cleanup(&client, &tid).await;
 
     let host = make_host(&tid, "192.168.1.100", "lookup-test");
-    client.upsert_host(&host).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
 
     // Find by IP
     let found = client
-        .find_node_by_property(&tid, "Host", "ip", "192.168.1.100")
+        .find_node_by_property(&tid, "Host", "ip", "192.168.1.100", None)
         .await
         .unwrap();
     assert!(found.is_some());
@@ -279,7 +288,7 @@ async fn test_find_node_by_property() {
 
     // Not found
     let not_found = client
-        .find_node_by_property(&tid, "Host", "ip", "1.2.3.4")
+        .find_node_by_property(&tid, "Host", "ip", "1.2.3.4", None)
         .await
         .unwrap();
     assert!(not_found.is_none());
@@ -298,7 +307,7 @@ async fn test_delete_node() {
 
     let host = make_host(&tid, "10.0.9.1", "delete-me");
     let host_id = host.id.clone();
-    client.upsert_host(&host).await.unwrap();
+    client.upsert_host(&host, &test_versions()).await.unwrap();
 
     assert_eq!(client.count_nodes(&tid, "Host").await.unwrap(), 1);
 
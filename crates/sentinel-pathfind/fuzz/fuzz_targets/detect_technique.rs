@@ -0,0 +1,52 @@
+//! Fuzz target: `detect_technique` must never panic on attacker-influenced
+//! edge types or node properties, and must always return a stable
+//! (non-empty) technique label when it returns one at all.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use sentinel_pathfind::lateral::detect_technique;
+
+/// A bounded stand-in for `serde_json::Value` that `arbitrary` can derive
+/// fuzzed instances of directly (`serde_json::Value` has no `Arbitrary`
+/// impl of its own).
+#[derive(Debug, Arbitrary)]
+enum ArbitraryJson {
+    Null,
+    Bool(bool),
+    Number(i64),
+    Str(String),
+    Array(Vec<ArbitraryJson>),
+    Object(Vec<(String, ArbitraryJson)>),
+}
+
+impl From<ArbitraryJson> for serde_json::Value {
+    fn from(value: ArbitraryJson) -> Self {
+        match value {
+            ArbitraryJson::Null => serde_json::Value::Null,
+            ArbitraryJson::Bool(b) => serde_json::Value::Bool(b),
+            ArbitraryJson::Number(n) => serde_json::json!(n),
+            ArbitraryJson::Str(s) => serde_json::Value::String(s),
+            ArbitraryJson::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(Into::into).collect())
+            }
+            ArbitraryJson::Object(fields) => serde_json::Value::Object(
+                fields.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    edge_type: String,
+    properties: ArbitraryJson,
+}
+
+fuzz_target!(|input: Input| {
+    let properties: serde_json::Value = input.properties.into();
+    if let Some(label) = detect_technique(&input.edge_type, &properties) {
+        assert!(!label.is_empty(), "technique label must never be empty");
+    }
+});
@@ -1,9 +1,15 @@
-//! Core pathfinding algorithms: DFS all-paths and Dijkstra shortest weighted path.
+//! Core pathfinding algorithms: DFS all-paths (parallelized across sources
+//! with rayon), Dijkstra/A* shortest weighted path, and A-Res weighted
+//! random path sampling.
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 
-use crate::graph::InMemoryGraph;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use crate::graph::{GraphEdge, InMemoryGraph};
 
 /// A raw path through the in-memory graph.
 #[derive(Debug, Clone)]
@@ -18,8 +24,13 @@ pub struct RawPath {
 
 /// All-paths enumeration from source nodes to target nodes using DFS.
 ///
-/// Finds all paths from any source to any target with cycle detection and depth limiting.
-/// Returns paths sorted by total weight ascending (most exploitable first).
+/// Finds all paths from any source to any target with cycle detection and
+/// depth limiting. Each source's DFS runs on its own rayon worker (sources
+/// are independent, so this scales with core count on large tenant graphs),
+/// capped at `max_paths` per source since no single source could usefully
+/// contribute more than the global cap; per-worker results are then merged
+/// and truncated to `max_paths`. Returns paths sorted by total weight
+/// ascending (most exploitable first).
 pub fn enumerate_all_paths(
     graph: &InMemoryGraph,
     sources: &[usize],
@@ -28,71 +39,32 @@ pub fn enumerate_all_paths(
     max_paths: usize,
 ) -> Vec<RawPath> {
     let target_set: HashSet<usize> = targets.iter().copied().collect();
-    let mut all_paths = Vec::new();
-
-    for &source in sources {
-        if all_paths.len() >= max_paths {
-            break;
-        }
 
-        let mut stack: Vec<DfsState> = vec![DfsState {
-            node: source,
-            path_nodes: vec![source],
-            path_edges: Vec::new(),
-            weight: 0.0,
-            visited: {
-                let mut s = HashSet::new();
-                s.insert(source);
-                s
-            },
-        }];
-
-        while let Some(state) = stack.pop() {
-            if all_paths.len() >= max_paths {
-                break;
-            }
+    let mut all_paths: Vec<RawPath> = sources
+        .par_iter()
+        .flat_map(|&source| {
+            let mut visited = vec![false; graph.node_count()];
+            visited[source] = true;
+            let mut out = Vec::new();
 
-            // Check if we've reached a target.
-            if state.path_nodes.len() > 1 && target_set.contains(&state.node) {
-                all_paths.push(RawPath {
-                    node_indices: state.path_nodes.clone(),
-                    edges: state.path_edges.clone(),
-                    total_weight: state.weight,
-                });
-                continue;
-            }
-
-            // Stop if we've reached max depth.
-            if state.path_nodes.len() > max_depth {
-                continue;
-            }
-
-            // Explore neighbors.
-            for (edge_pos, edge) in graph.adjacency[state.node].iter().enumerate() {
-                if state.visited.contains(&edge.target_index) {
-                    continue;
-                }
+            dfs_collect_paths(
+                graph,
+                source,
+                &target_set,
+                max_depth,
+                max_paths,
+                &mut visited,
+                &mut vec![source],
+                &mut Vec::new(),
+                0.0,
+                &mut out,
+            );
 
-                let edge_weight = 1.0 - edge.exploitability.clamp(0.0, 1.0);
-                let mut new_visited = state.visited.clone();
-                new_visited.insert(edge.target_index);
-
-                let mut new_nodes = state.path_nodes.clone();
-                new_nodes.push(edge.target_index);
-
-                let mut new_edges = state.path_edges.clone();
-                new_edges.push((state.node, edge_pos));
-
-                stack.push(DfsState {
-                    node: edge.target_index,
-                    path_nodes: new_nodes,
-                    path_edges: new_edges,
-                    weight: state.weight + edge_weight,
-                    visited: new_visited,
-                });
-            }
-        }
-    }
+            out.sort_by(|a, b| a.total_weight.partial_cmp(&b.total_weight).unwrap_or(Ordering::Equal));
+            out.truncate(max_paths);
+            out
+        })
+        .collect();
 
     // Sort by total weight ascending (most exploitable path = lowest weight first).
     all_paths.sort_by(|a, b| {
@@ -105,6 +77,73 @@ pub fn enumerate_all_paths(
     all_paths
 }
 
+/// Recursive DFS worker for one source, used by [`enumerate_all_paths`].
+/// `visited` is a single reusable bitset toggled on enter/exit as the
+/// search backtracks, instead of cloning a `HashSet` on every step; `node`
+/// plus `path_nodes`/`path_edges`/`weight` track the path in progress.
+/// Stops early once `out` already holds `max_paths` candidates.
+#[allow(clippy::too_many_arguments)]
+fn dfs_collect_paths(
+    graph: &InMemoryGraph,
+    node: usize,
+    targets: &HashSet<usize>,
+    max_depth: usize,
+    max_paths: usize,
+    visited: &mut [bool],
+    path_nodes: &mut Vec<usize>,
+    path_edges: &mut Vec<(usize, usize)>,
+    weight: f64,
+    out: &mut Vec<RawPath>,
+) {
+    if out.len() >= max_paths {
+        return;
+    }
+
+    if path_nodes.len() > 1 && targets.contains(&node) {
+        out.push(RawPath {
+            node_indices: path_nodes.clone(),
+            edges: path_edges.clone(),
+            total_weight: weight,
+        });
+        return;
+    }
+
+    if path_nodes.len() > max_depth {
+        return;
+    }
+
+    for (edge_pos, edge) in graph.adjacency[node].iter().enumerate() {
+        if out.len() >= max_paths {
+            break;
+        }
+        if visited[edge.target_index] {
+            continue;
+        }
+
+        let edge_weight = 1.0 - edge.exploitability.clamp(0.0, 1.0);
+        visited[edge.target_index] = true;
+        path_nodes.push(edge.target_index);
+        path_edges.push((node, edge_pos));
+
+        dfs_collect_paths(
+            graph,
+            edge.target_index,
+            targets,
+            max_depth,
+            max_paths,
+            visited,
+            path_nodes,
+            path_edges,
+            weight + edge_weight,
+            out,
+        );
+
+        path_edges.pop();
+        path_nodes.pop();
+        visited[edge.target_index] = false;
+    }
+}
+
 /// Shortest weighted path using Dijkstra's algorithm.
 ///
 /// Edge weight = `1.0 - exploitability` so the most exploitable path has the
@@ -184,13 +223,604 @@ pub fn shortest_weighted_path(
     })
 }
 
-/// Internal DFS state for all-paths enumeration.
-struct DfsState {
+/// Precomputed landmark distance tables for the ALT (A*, Landmarks,
+/// Triangle inequality) heuristic used by [`shortest_weighted_path_astar`].
+/// Build once per graph snapshot with [`Self::build`] and reuse across
+/// queries -- the landmark Dijkstra runs are the expensive part, and they
+/// don't depend on the query's source/target.
+pub struct LandmarkTables {
+    /// `dist_to_landmark[l][v]` = shortest `1.0 - exploitability` distance
+    /// from `v` to landmark `l`.
+    dist_to_landmark: Vec<Vec<f64>>,
+}
+
+impl LandmarkTables {
+    /// Build landmark tables over `graph`, using its internet-facing nodes
+    /// and crown jewels as landmarks -- the extremes of an attack graph, and
+    /// so usually the most informative anchors for the triangle-inequality
+    /// bound. Falls back to node 0 if the graph has neither flagged.
+    pub fn build(graph: &InMemoryGraph) -> Self {
+        let mut landmarks: Vec<usize> = graph
+            .internet_facing_nodes()
+            .into_iter()
+            .chain(graph.crown_jewel_nodes())
+            .collect();
+        landmarks.sort_unstable();
+        landmarks.dedup();
+        if landmarks.is_empty() && graph.node_count() > 0 {
+            landmarks.push(0);
+        }
+
+        let reverse_adjacency = reverse_weighted_adjacency(graph);
+        let dist_to_landmark = landmarks
+            .iter()
+            .map(|&l| dijkstra_all_distances(&reverse_adjacency, l))
+            .collect();
+
+        Self { dist_to_landmark }
+    }
+
+    /// Admissible, consistent lower bound on the true remaining cost from
+    /// `u` to `t`: `max over landmarks l of |dist_to_landmark[l][u] -
+    /// dist_to_landmark[l][t]|`, by the triangle inequality.
+    fn heuristic(&self, u: usize, t: usize) -> f64 {
+        self.dist_to_landmark
+            .iter()
+            .map(|row| (row[u] - row[t]).abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Build `graph`'s adjacency list in `(neighbor, weight)` form with every
+/// edge reversed, so a single Dijkstra run from a landmark `l` yields `l`'s
+/// distance *to* every other node (`dist_to_landmark`).
+fn reverse_weighted_adjacency(graph: &InMemoryGraph) -> Vec<Vec<(usize, f64)>> {
+    let mut reverse = vec![Vec::new(); graph.node_count()];
+    for (from, edges) in graph.adjacency.iter().enumerate() {
+        for edge in edges {
+            let weight = 1.0 - edge.exploitability.clamp(0.0, 1.0);
+            reverse[edge.target_index].push((from, weight));
+        }
+    }
+    reverse
+}
+
+/// Plain Dijkstra over a `(neighbor, weight)` adjacency list, returning the
+/// distance from `source` to every reachable node. Used by
+/// [`LandmarkTables::build`] to fill in one landmark's row.
+fn dijkstra_all_distances(adjacency: &[Vec<(usize, f64)>], source: usize) -> Vec<f64> {
+    let n = adjacency.len();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut visited = vec![false; n];
+    dist[source] = 0.0;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(DijkstraState { cost: 0.0, node: source });
+
+    while let Some(DijkstraState { cost, node }) = heap.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        if cost > dist[node] {
+            continue;
+        }
+
+        for &(next, weight) in &adjacency[node] {
+            let new_dist = dist[node] + weight;
+            if new_dist < dist[next] {
+                dist[next] = new_dist;
+                heap.push(DijkstraState { cost: new_dist, node: next });
+            }
+        }
+    }
+
+    dist
+}
+
+/// A* variant of [`shortest_weighted_path`] using the ALT heuristic from
+/// `landmarks` to prune the search instead of exploring the whole frontier
+/// -- worthwhile on large attack graphs with a known crown-jewel target,
+/// where plain Dijkstra wastes time expanding nodes far from both endpoints.
+/// `dist[]` still tracks true g-scores; only the heap's priority includes
+/// the heuristic, so the reconstructed path and its `total_weight` are
+/// identical to what [`shortest_weighted_path`] would return.
+pub fn shortest_weighted_path_astar(
+    graph: &InMemoryGraph,
+    source: usize,
+    target: usize,
+    landmarks: &LandmarkTables,
+) -> Option<RawPath> {
+    let n = graph.node_count();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut prev: Vec<Option<(usize, usize)>> = vec![None; n];
+    let mut visited = vec![false; n];
+
+    dist[source] = 0.0;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(AstarState {
+        priority: landmarks.heuristic(source, target),
+        cost: 0.0,
+        node: source,
+    });
+
+    while let Some(AstarState { cost, node, .. }) = heap.pop() {
+        if node == target {
+            break;
+        }
+
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+
+        if cost > dist[node] {
+            continue;
+        }
+
+        for (edge_pos, edge) in graph.adjacency[node].iter().enumerate() {
+            let edge_weight = 1.0 - edge.exploitability.clamp(0.0, 1.0);
+            let new_dist = dist[node] + edge_weight;
+
+            if new_dist < dist[edge.target_index] {
+                dist[edge.target_index] = new_dist;
+                prev[edge.target_index] = Some((node, edge_pos));
+                heap.push(AstarState {
+                    priority: new_dist + landmarks.heuristic(edge.target_index, target),
+                    cost: new_dist,
+                    node: edge.target_index,
+                });
+            }
+        }
+    }
+
+    if dist[target].is_infinite() {
+        return None;
+    }
+
+    let mut node_indices = Vec::new();
+    let mut edges = Vec::new();
+    let mut current = target;
+
+    while let Some((parent, edge_pos)) = prev[current] {
+        node_indices.push(current);
+        edges.push((parent, edge_pos));
+        current = parent;
+    }
+    node_indices.push(source);
+
+    node_indices.reverse();
+    edges.reverse();
+
+    Some(RawPath {
+        node_indices,
+        edges,
+        total_weight: dist[target],
+    })
+}
+
+/// State for [`shortest_weighted_path_astar`]'s priority queue: ordered by
+/// `priority` (g-score + heuristic) for min-heap behavior, while `cost`
+/// keeps the true g-score around for the staleness check on pop.
+#[derive(Debug, Clone, PartialEq)]
+struct AstarState {
+    priority: f64,
+    cost: f64,
     node: usize,
-    path_nodes: Vec<usize>,
-    path_edges: Vec<(usize, usize)>,
-    weight: f64,
-    visited: HashSet<usize>,
+}
+
+impl Eq for AstarState {}
+
+impl Ord for AstarState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for AstarState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sample up to `k` distinct, diverse attack paths from `sources` to
+/// `targets` using A-Res weighted-reservoir sampling, biased toward
+/// high-exploitability edges into high-criticality nodes rather than
+/// always returning the single deterministic best path.
+///
+/// Each outgoing edge gets weight `w = exploitability * (0.5 + target.criticality)`
+/// (floored above zero so no edge is ever impossible) and a random key
+/// `key = u^(1/w)` for `u` uniform in `(0, 1]`; at every step of a walk the
+/// highest-key unvisited edge is taken. Re-running the walk `k`-plus times
+/// with a seeded RNG gives reproducible results while still surfacing
+/// paths a deterministic shortest-path search would never return. Returned
+/// paths are deduplicated by their edge-ID sequence.
+pub fn sample_paths(
+    graph: &InMemoryGraph,
+    sources: &[usize],
+    targets: &[usize],
+    k: usize,
+    max_depth: usize,
+    seed: u64,
+) -> Vec<RawPath> {
+    if sources.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let target_set: HashSet<usize> = targets.iter().copied().collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut seen_edge_sequences = HashSet::new();
+    let mut paths = Vec::new();
+
+    // Walks can dead-end or repeat a path already found; allow enough
+    // attempts to still reach k distinct paths on a reasonably connected
+    // graph without spinning forever on a sparse one.
+    let max_attempts = k.saturating_mul(20).max(20);
+
+    for _ in 0..max_attempts {
+        if paths.len() >= k {
+            break;
+        }
+
+        let source = sources[rng.gen_range(0..sources.len())];
+        let Some(path) = weighted_walk(graph, source, &target_set, max_depth, &mut rng) else {
+            continue;
+        };
+
+        let edge_ids: Vec<&str> = path
+            .edges
+            .iter()
+            .map(|&(node, pos)| graph.adjacency[node][pos].id.as_str())
+            .collect();
+        if seen_edge_sequences.insert(edge_ids.join(">")) {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+/// A-Res weight for an edge: easier-to-exploit edges into more critical
+/// nodes score higher, but the floor keeps every edge reachable.
+fn edge_sampling_weight(graph: &InMemoryGraph, edge: &GraphEdge) -> f64 {
+    let target_criticality = graph.nodes[edge.target_index].criticality;
+    (edge.exploitability * (0.5 + target_criticality)).max(1e-6)
+}
+
+/// A-Res key for a candidate: `u^(1/weight)` for `u` uniform in `(0, 1]`.
+/// Higher weight pushes the key toward 1 (preferred), but any edge can win.
+fn a_res_key(rng: &mut StdRng, weight: f64) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>(); // uniform in (0, 1], avoids u=0 blowing up the root
+    u.powf(1.0 / weight)
+}
+
+/// A single randomized best-first walk from `source`: at each step, follow
+/// the not-yet-visited outgoing edge with the highest A-Res key. Stops at
+/// the first target hit, a dead end, or once `max_depth` nodes are visited.
+fn weighted_walk(
+    graph: &InMemoryGraph,
+    source: usize,
+    targets: &HashSet<usize>,
+    max_depth: usize,
+    rng: &mut StdRng,
+) -> Option<RawPath> {
+    let mut node_indices = vec![source];
+    let mut edges = Vec::new();
+    let mut total_weight = 0.0;
+    let mut visited = HashSet::new();
+    visited.insert(source);
+    let mut current = source;
+
+    loop {
+        if node_indices.len() > 1 && targets.contains(&current) {
+            return Some(RawPath {
+                node_indices,
+                edges,
+                total_weight,
+            });
+        }
+
+        if node_indices.len() > max_depth {
+            return None;
+        }
+
+        let next = graph.adjacency[current]
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| !visited.contains(&edge.target_index))
+            .map(|(pos, edge)| (a_res_key(rng, edge_sampling_weight(graph, edge)), pos, edge))
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let Some((_, pos, edge)) = next else {
+            return None;
+        };
+
+        total_weight += 1.0 - edge.exploitability.clamp(0.0, 1.0);
+        visited.insert(edge.target_index);
+        edges.push((current, pos));
+        current = edge.target_index;
+        node_indices.push(current);
+    }
+}
+
+/// Floor applied to an edge's exploitability before taking its log, so a
+/// zero-exploitability edge costs a large but finite amount rather than
+/// `f64::INFINITY`.
+const EXPLOIT_COST_EPSILON: f64 = 1e-6;
+
+/// Convert an edge's exploitability into an additive cost for
+/// [`k_shortest_weighted_paths`]: `-ln(max(exploitability, epsilon))`. Costs
+/// sum along a path the same way `-ln` of a product of probabilities does,
+/// so the cheapest path is the one an attacker is most likely to complete
+/// end to end, not just the one with the fewest hops.
+fn exploit_cost(exploitability: f64) -> f64 {
+    -(exploitability.clamp(0.0, 1.0).max(EXPLOIT_COST_EPSILON)).ln()
+}
+
+/// Dijkstra over `graph` using `edge_cost` to weight each edge, skipping any
+/// edge in `removed_edges` (identified the same way as `RawPath::edges`:
+/// `(from_node_index, edge_position_in_adjacency_list)`) and any node in
+/// `removed_nodes` other than `source`/`target`. Shared by
+/// [`k_shortest_weighted_paths`] and [`k_shortest_paths`]'s spur-path search
+/// (see [`yen_k_shortest`]) so both only differ in which cost function they
+/// plug in.
+fn dijkstra_with_removals(
+    graph: &InMemoryGraph,
+    source: usize,
+    target: usize,
+    removed_edges: &HashSet<(usize, usize)>,
+    removed_nodes: &HashSet<usize>,
+    edge_cost: impl Fn(f64) -> f64,
+) -> Option<RawPath> {
+    let n = graph.node_count();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut prev: Vec<Option<(usize, usize)>> = vec![None; n];
+    let mut visited = vec![false; n];
+
+    dist[source] = 0.0;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(DijkstraState { cost: 0.0, node: source });
+
+    while let Some(DijkstraState { cost, node }) = heap.pop() {
+        if node == target {
+            break;
+        }
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        if cost > dist[node] {
+            continue;
+        }
+
+        for (edge_pos, edge) in graph.adjacency[node].iter().enumerate() {
+            if removed_edges.contains(&(node, edge_pos)) {
+                continue;
+            }
+            if edge.target_index != target && removed_nodes.contains(&edge.target_index) {
+                continue;
+            }
+
+            let new_dist = dist[node] + edge_cost(edge.exploitability);
+            if new_dist < dist[edge.target_index] {
+                dist[edge.target_index] = new_dist;
+                prev[edge.target_index] = Some((node, edge_pos));
+                heap.push(DijkstraState {
+                    cost: new_dist,
+                    node: edge.target_index,
+                });
+            }
+        }
+    }
+
+    if dist[target].is_infinite() {
+        return None;
+    }
+
+    let mut node_indices = Vec::new();
+    let mut edges = Vec::new();
+    let mut current = target;
+
+    while let Some((parent, edge_pos)) = prev[current] {
+        node_indices.push(current);
+        edges.push((parent, edge_pos));
+        current = parent;
+    }
+    node_indices.push(source);
+
+    node_indices.reverse();
+    edges.reverse();
+
+    Some(RawPath {
+        node_indices,
+        edges,
+        total_weight: dist[target],
+    })
+}
+
+/// Yen's algorithm for the `k` lowest-cost loopless paths from `source` to
+/// `target`: compute the shortest path with Dijkstra, then repeatedly treat
+/// each node along the best-so-far path as a "spur node", remove the edges
+/// and interior nodes that would retrace an already-found path's root, and
+/// re-run Dijkstra from the spur node to find a new candidate. The cheapest
+/// unseen candidate across all spur nodes becomes the next accepted path.
+/// `edge_cost` is applied to each edge's exploitability to get its weight --
+/// [`k_shortest_weighted_paths`] and [`k_shortest_paths`] differ only in
+/// which cost function they pass in here. Paths longer than `max_hops`
+/// edges are discarded; pass `usize::MAX` for no cap.
+fn yen_k_shortest(
+    graph: &InMemoryGraph,
+    source: usize,
+    target: usize,
+    k: usize,
+    max_hops: usize,
+    edge_cost: impl Fn(f64) -> f64 + Copy,
+) -> Vec<RawPath> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let Some(first) =
+        dijkstra_with_removals(graph, source, target, &HashSet::new(), &HashSet::new(), edge_cost)
+    else {
+        return Vec::new();
+    };
+    if first.edges.len() > max_hops {
+        return Vec::new();
+    }
+
+    let mut a: Vec<RawPath> = vec![first];
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    seen.insert(a[0].node_indices.clone());
+    let mut candidates: BinaryHeap<YenCandidate> = BinaryHeap::new();
+    // Tracks node_indices currently sitting in `candidates`, so a path
+    // regenerated from a different spur root in a later outer iteration
+    // isn't pushed twice while still unaccepted (and thus absent from
+    // `seen`).
+    let mut in_heap: HashSet<Vec<usize>> = HashSet::new();
+
+    while a.len() < k {
+        let prev_path = a.last().expect("a is never empty").clone();
+
+        for i in 0..prev_path.node_indices.len().saturating_sub(1) {
+            let spur_node = prev_path.node_indices[i];
+            let root_nodes = &prev_path.node_indices[..=i];
+            let root_edges = &prev_path.edges[..i];
+
+            let removed_edges: HashSet<(usize, usize)> = a
+                .iter()
+                .filter(|p| p.node_indices.len() > i && p.node_indices[..=i] == *root_nodes)
+                .map(|p| p.edges[i])
+                .collect();
+            let removed_nodes: HashSet<usize> = root_nodes[..i].iter().copied().collect();
+
+            let Some(spur_path) = dijkstra_with_removals(
+                graph,
+                spur_node,
+                target,
+                &removed_edges,
+                &removed_nodes,
+                edge_cost,
+            ) else {
+                continue;
+            };
+
+            let total_hops = root_edges.len() + spur_path.edges.len();
+            if total_hops > max_hops {
+                continue;
+            }
+
+            let root_cost: f64 = root_edges
+                .iter()
+                .map(|&(n, pos)| edge_cost(graph.adjacency[n][pos].exploitability))
+                .sum();
+
+            let mut node_indices = root_nodes.to_vec();
+            node_indices.extend_from_slice(&spur_path.node_indices[1..]);
+            let mut edges = root_edges.to_vec();
+            edges.extend_from_slice(&spur_path.edges);
+
+            if seen.contains(&node_indices) || in_heap.contains(&node_indices) {
+                continue;
+            }
+
+            in_heap.insert(node_indices.clone());
+            candidates.push(YenCandidate(RawPath {
+                node_indices,
+                edges,
+                total_weight: root_cost + spur_path.total_weight,
+            }));
+        }
+
+        // Pop the cheapest candidate, skipping any that (despite the
+        // push-time check above) turn out to already be accepted, so a
+        // path can never repeat in the output.
+        let next = loop {
+            let Some(YenCandidate(candidate)) = candidates.pop() else {
+                break None;
+            };
+            in_heap.remove(&candidate.node_indices);
+            if seen.contains(&candidate.node_indices) {
+                continue;
+            }
+            break Some(candidate);
+        };
+        let Some(next) = next else {
+            break;
+        };
+        seen.insert(next.node_indices.clone());
+        a.push(next);
+    }
+
+    a
+}
+
+/// The `k` lowest-cost loopless paths from `source` to `target`, ranked by
+/// cumulative [`exploit_cost`] (lower is more plausible end-to-end) rather
+/// than the `1.0 - exploitability` weight the rest of this module uses --
+/// the log cost is what makes path costs additive across probabilities,
+/// which is what makes Yen's spur-path removal well-defined here. See
+/// [`yen_k_shortest`] for the algorithm. Paths longer than `max_hops` edges
+/// are discarded.
+pub fn k_shortest_weighted_paths(
+    graph: &InMemoryGraph,
+    source: usize,
+    target: usize,
+    k: usize,
+    max_hops: usize,
+) -> Vec<RawPath> {
+    yen_k_shortest(graph, source, target, k, max_hops, exploit_cost)
+}
+
+/// The `k` lowest-weight loopless paths from `source` to `target`, using the
+/// same `1.0 - exploitability` edge weight as [`shortest_weighted_path`] and
+/// [`enumerate_all_paths`], found via Yen's algorithm (see
+/// [`yen_k_shortest`]) instead of `enumerate_all_paths`'s exponential DFS --
+/// useful when a large graph's true top-K shortest paths would otherwise get
+/// arbitrarily truncated by `enumerate_all_paths`'s `max_paths` cap. Returned
+/// paths are in ranked order and strictly non-decreasing in weight.
+pub fn k_shortest_paths(
+    graph: &InMemoryGraph,
+    source: usize,
+    target: usize,
+    k: usize,
+) -> Vec<RawPath> {
+    yen_k_shortest(graph, source, target, k, usize::MAX, |e| 1.0 - e.clamp(0.0, 1.0))
+}
+
+/// Wraps a `RawPath` candidate so `BinaryHeap` (a max-heap) pops the
+/// lowest-cost candidate first, matching [`DijkstraState`]'s reversed
+/// ordering.
+#[derive(Debug, Clone)]
+struct YenCandidate(RawPath);
+
+impl PartialEq for YenCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_weight == other.0.total_weight
+    }
+}
+
+impl Eq for YenCandidate {}
+
+impl Ord for YenCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .total_weight
+            .partial_cmp(&self.0.total_weight)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for YenCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 /// State for Dijkstra's priority queue (min-heap by cost).
@@ -375,6 +1005,39 @@ mod tests {
         assert!(path.is_none());
     }
 
+    #[test]
+    fn test_shortest_weighted_path_astar_matches_dijkstra() {
+        let graph = build_test_graph();
+        let landmarks = LandmarkTables::build(&graph);
+
+        let dijkstra_path = shortest_weighted_path(&graph, 0, 3).unwrap();
+        let astar_path = shortest_weighted_path_astar(&graph, 0, 3, &landmarks).unwrap();
+
+        assert_eq!(astar_path.node_indices, dijkstra_path.node_indices);
+        assert!((astar_path.total_weight - dijkstra_path.total_weight).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_shortest_weighted_path_astar_unreachable_returns_none() {
+        let graph = build_test_graph();
+        let landmarks = LandmarkTables::build(&graph);
+        assert!(shortest_weighted_path_astar(&graph, 3, 0, &landmarks).is_none());
+    }
+
+    #[test]
+    fn test_landmark_heuristic_never_overestimates() {
+        let graph = build_test_graph();
+        let landmarks = LandmarkTables::build(&graph);
+
+        for source in 0..graph.node_count() {
+            for target in 0..graph.node_count() {
+                if let Some(path) = shortest_weighted_path(&graph, source, target) {
+                    assert!(landmarks.heuristic(source, target) <= path.total_weight + 1e-9);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_shortest_path_same_node() {
         let graph = build_test_graph();
@@ -446,4 +1109,213 @@ mod tests {
         assert_eq!(paths.len(), 1);
         assert_eq!(paths[0].node_indices, vec![0, 1, 2, 3]);
     }
+
+    #[test]
+    fn test_sample_paths_all_reach_target() {
+        let graph = build_test_graph();
+        let paths = sample_paths(&graph, &[0], &[3], 5, 10, 42);
+
+        assert!(!paths.is_empty());
+        for path in &paths {
+            assert_eq!(*path.node_indices.last().unwrap(), 3);
+            assert_eq!(path.node_indices[0], 0);
+        }
+    }
+
+    #[test]
+    fn test_sample_paths_deduplicates_by_edge_sequence() {
+        let graph = build_test_graph();
+        let paths = sample_paths(&graph, &[0], &[3], 10, 10, 7);
+
+        // Only two distinct paths exist in this graph (0→1→3 and 0→2→3).
+        assert!(paths.len() <= 2);
+
+        let mut seen = HashSet::new();
+        for path in &paths {
+            assert!(seen.insert(path.node_indices.clone()), "duplicate path returned");
+        }
+    }
+
+    #[test]
+    fn test_sample_paths_is_reproducible_for_a_fixed_seed() {
+        let graph = build_test_graph();
+        let first = sample_paths(&graph, &[0], &[3], 5, 10, 123);
+        let second = sample_paths(&graph, &[0], &[3], 5, 10, 123);
+
+        assert_eq!(first.len(), second.len());
+        for (a, b) in first.iter().zip(second.iter()) {
+            assert_eq!(a.node_indices, b.node_indices);
+        }
+    }
+
+    #[test]
+    fn test_sample_paths_empty_sources_returns_empty() {
+        let graph = build_test_graph();
+        let paths = sample_paths(&graph, &[], &[3], 5, 10, 1);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_orders_by_exploit_cost() {
+        let graph = build_test_graph();
+        let paths = k_shortest_weighted_paths(&graph, 0, 3, 2, 10);
+
+        assert_eq!(paths.len(), 2);
+        // Path 0->1->3 (exploitability 0.8, 0.9) is far cheaper than
+        // 0->2->3 (0.3, 0.4), so it must be ranked first.
+        assert_eq!(paths[0].node_indices, vec![0, 1, 3]);
+        assert_eq!(paths[1].node_indices, vec![0, 2, 3]);
+        assert!(paths[0].total_weight < paths[1].total_weight);
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_caps_at_available_paths() {
+        let graph = build_test_graph();
+        // Only two loopless paths exist from 0 to 3.
+        let paths = k_shortest_weighted_paths(&graph, 0, 3, 10, 10);
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_respects_max_hops() {
+        let graph = build_test_graph();
+        // Every path from 0 to 3 needs 2 hops.
+        let paths = k_shortest_weighted_paths(&graph, 0, 3, 5, 1);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_unreachable_returns_empty() {
+        let graph = build_test_graph();
+        let paths = k_shortest_weighted_paths(&graph, 3, 0, 3, 10);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_zero_k_returns_empty() {
+        let graph = build_test_graph();
+        let paths = k_shortest_weighted_paths(&graph, 0, 3, 0, 10);
+        assert!(paths.is_empty());
+    }
+
+    /// Layered, densely-connected graph: source (0) -> layer1 {1,2,3} ->
+    /// layer2 {4,5,6} -> target (7), fully connected between adjacent
+    /// layers. This gives 9 distinct loopless paths with many overlapping
+    /// root prefixes and suffixes across spur nodes -- exactly the shape
+    /// that let Yen's algorithm regenerate (and, before the `in_heap`/`seen`
+    /// fix, double-accept) the same candidate path from two different spur
+    /// roots.
+    fn build_layered_mesh_graph() -> InMemoryGraph {
+        let mut nodes = Vec::new();
+        for i in 0..8 {
+            nodes.push(GraphNode {
+                index: i,
+                id: format!("n{i}"),
+                label: "Host".to_string(),
+                criticality: 0.5,
+                is_internet_facing: i == 0,
+                is_crown_jewel: i == 7,
+                properties: serde_json::json!({}),
+            });
+        }
+
+        let mut adjacency: Vec<Vec<GraphEdge>> = vec![Vec::new(); 8];
+        // source (0) -> layer1 (1,2,3)
+        for (j, &layer1_node) in [1usize, 2, 3].iter().enumerate() {
+            adjacency[0].push(GraphEdge {
+                id: format!("e0_{layer1_node}"),
+                edge_type: "CONNECTS_TO".to_string(),
+                exploitability: 0.9 - 0.05 * j as f64,
+                target_index: layer1_node,
+            });
+        }
+        // layer1 (1,2,3) -> layer2 (4,5,6), fully connected
+        for (i, &l1) in [1usize, 2, 3].iter().enumerate() {
+            for (j, &l2) in [4usize, 5, 6].iter().enumerate() {
+                adjacency[l1].push(GraphEdge {
+                    id: format!("e{l1}_{l2}"),
+                    edge_type: "CONNECTS_TO".to_string(),
+                    exploitability: 0.9 - 0.05 * (i + j) as f64,
+                    target_index: l2,
+                });
+            }
+        }
+        // layer2 (4,5,6) -> target (7)
+        for (j, &l2) in [4usize, 5, 6].iter().enumerate() {
+            adjacency[l2].push(GraphEdge {
+                id: format!("e{l2}_7"),
+                edge_type: "CONNECTS_TO".to_string(),
+                exploitability: 0.9 - 0.03 * j as f64,
+                target_index: 7,
+            });
+        }
+
+        let mut node_index = HashMap::new();
+        for n in &nodes {
+            node_index.insert(n.id.clone(), n.index);
+        }
+
+        InMemoryGraph { nodes, adjacency, node_index }
+    }
+
+    #[test]
+    fn test_k_shortest_weighted_paths_never_repeats_a_path() {
+        let graph = build_layered_mesh_graph();
+
+        // 9 distinct loopless paths exist (3 layer1 choices x 3 layer2
+        // choices); ask for more than that to also exercise the
+        // cap-once-exhausted case.
+        let paths = k_shortest_weighted_paths(&graph, 0, 7, 20, 10);
+        assert_eq!(paths.len(), 9);
+
+        let mut seen = HashSet::new();
+        for path in &paths {
+            assert!(seen.insert(path.node_indices.clone()), "duplicate path returned: {:?}", path.node_indices);
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_orders_like_shortest_weighted_path() {
+        let graph = build_test_graph();
+        let paths = k_shortest_paths(&graph, 0, 3, 2);
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].node_indices, vec![0, 1, 3]);
+        assert!((paths[0].total_weight - 0.3).abs() < 0.01);
+        assert_eq!(paths[1].node_indices, vec![0, 2, 3]);
+        assert!((paths[1].total_weight - 1.3).abs() < 0.01);
+        assert!(paths[0].total_weight <= paths[1].total_weight);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_caps_at_available_paths() {
+        let graph = build_test_graph();
+        let paths = k_shortest_paths(&graph, 0, 3, 10);
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_is_loopless() {
+        let graph = build_test_graph();
+        let paths = k_shortest_paths(&graph, 0, 3, 10);
+
+        for path in &paths {
+            let unique: HashSet<usize> = path.node_indices.iter().copied().collect();
+            assert_eq!(unique.len(), path.node_indices.len(), "path revisits a node");
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_unreachable_returns_empty() {
+        let graph = build_test_graph();
+        let paths = k_shortest_paths(&graph, 3, 0, 3);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_zero_k_returns_empty() {
+        let graph = build_test_graph();
+        let paths = k_shortest_paths(&graph, 0, 3, 0);
+        assert!(paths.is_empty());
+    }
 }
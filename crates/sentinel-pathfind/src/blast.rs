@@ -2,11 +2,17 @@
 //!
 //! BFS from a compromised node, following edges whose exploitability exceeds
 //! a threshold. Tracks hop distance and cumulative exploitability.
+//!
+//! [`compute_most_probable_paths`] offers a second mode: rather than
+//! whichever path BFS happens to discover first, it finds the path to each
+//! reachable node that maximizes the product of edge exploitabilities —
+//! the single most realistic route a defender should actually worry about.
 
+use std::cmp::Ordering;
 use std::collections::{HashSet, VecDeque};
 
 use crate::graph::InMemoryGraph;
-use crate::types::{BlastRadiusResult, ReachableNode};
+use crate::types::{BlastRadiusResult, MostProbablePath, ReachableNode};
 
 /// Compute the blast radius from a compromised node.
 ///
@@ -95,6 +101,107 @@ pub fn compute_blast_radius(
     }
 }
 
+/// For every node reachable from `compromised_node` within `max_hops`, find
+/// the single path that maximizes the product of edge exploitabilities
+/// (rather than whichever path BFS discovers first).
+///
+/// Implemented as a hop-bounded label-setting search over the transformed
+/// weight `w(e) = -ln(exploitability)`: minimizing summed `w` along a path
+/// is exactly maximizing the exploitability product, recovered as
+/// `exp(-dist)`. Edges with exploitability `<= 0.0` or below
+/// `min_exploitability` have effectively infinite weight and are skipped.
+///
+/// `dist`/`prev` are kept per `(node, hops)` state — one layer per hop
+/// count, `0..=max_hops` — rather than a single best-cost-so-far per node.
+/// A single per-node entry would let a cheaper, higher-hop route "settle"
+/// a node and block a costlier, lower-hop route through it from ever
+/// expanding further, even though that lower-hop route might be the only
+/// way to reach some other node within `max_hops`. Keeping every hop count
+/// as its own layer means a node is relaxed independently at each hop
+/// count it's reachable at; the final answer per node is the minimum-cost
+/// layer across `0..=max_hops`.
+pub fn compute_most_probable_paths(
+    graph: &InMemoryGraph,
+    compromised_node: usize,
+    max_hops: usize,
+    min_exploitability: f64,
+) -> Vec<MostProbablePath> {
+    let n = graph.node_count();
+
+    // dist[h][node] / prev[h][node]: best cost (and predecessor edge) to
+    // reach `node` in exactly `h` hops.
+    let mut dist: Vec<Vec<f64>> = vec![vec![f64::INFINITY; n]; max_hops + 1];
+    let mut prev: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; n]; max_hops + 1];
+    dist[0][compromised_node] = 0.0;
+
+    for hops in 0..max_hops {
+        for node in 0..n {
+            let cost = dist[hops][node];
+            if cost.is_infinite() {
+                continue;
+            }
+
+            for (edge_pos, edge) in graph.adjacency[node].iter().enumerate() {
+                if edge.exploitability <= 0.0 || edge.exploitability < min_exploitability {
+                    continue;
+                }
+
+                let next_cost = cost - edge.exploitability.ln();
+                if next_cost < dist[hops + 1][edge.target_index] {
+                    dist[hops + 1][edge.target_index] = next_cost;
+                    prev[hops + 1][edge.target_index] = Some((node, edge_pos));
+                }
+            }
+        }
+    }
+
+    // Pick, for each node, the cheapest layer it was reached at.
+    let mut best_cost = vec![f64::INFINITY; n];
+    let mut best_hops = vec![usize::MAX; n];
+    for hops in 0..=max_hops {
+        for node in 0..n {
+            if dist[hops][node] < best_cost[node] {
+                best_cost[node] = dist[hops][node];
+                best_hops[node] = hops;
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for node in 0..n {
+        if node == compromised_node || best_cost[node].is_infinite() {
+            continue;
+        }
+
+        let mut path_indices = vec![node];
+        let mut current = node;
+        let mut hops = best_hops[node];
+        while let Some((parent, _)) = prev[hops][current] {
+            path_indices.push(parent);
+            current = parent;
+            hops -= 1;
+        }
+        path_indices.reverse();
+
+        let graph_node = &graph.nodes[node];
+        results.push(MostProbablePath {
+            node_id: graph_node.id.clone(),
+            label: graph_node.label.clone(),
+            probability: (-best_cost[node]).exp(),
+            hops: best_hops[node],
+            path: path_indices.into_iter().map(|idx| graph.nodes[idx].id.clone()).collect(),
+        });
+    }
+
+    results.sort_by(|a, b| {
+        a.hops
+            .cmp(&b.hops)
+            .then_with(|| b.probability.partial_cmp(&a.probability).unwrap_or(Ordering::Equal))
+    });
+
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,4 +323,122 @@ mod tests {
             );
         }
     }
+
+    /// Two routes from node 0 to node 3: via node 1 (0.9 * 0.9 = 0.81) and
+    /// via node 2 (0.95 * 0.5 = 0.475). The high-probability route is worse
+    /// at its first hop but better overall, so a greedy/BFS-first choice
+    /// would pick the wrong one.
+    fn build_diamond_graph() -> InMemoryGraph {
+        let nodes: Vec<GraphNode> = (0..4)
+            .map(|i| GraphNode {
+                index: i,
+                id: format!("n{i}"),
+                label: "Host".to_string(),
+                criticality: 0.5,
+                is_internet_facing: false,
+                is_crown_jewel: false,
+                properties: serde_json::json!({}),
+            })
+            .collect();
+
+        let adjacency = vec![
+            vec![
+                GraphEdge { id: "e01".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.9, target_index: 1 },
+                GraphEdge { id: "e02".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.95, target_index: 2 },
+            ],
+            vec![GraphEdge { id: "e13".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.9, target_index: 3 }],
+            vec![GraphEdge { id: "e23".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.5, target_index: 3 }],
+            vec![],
+        ];
+
+        let node_index = nodes.iter().map(|n| (n.id.clone(), n.index)).collect();
+        InMemoryGraph { nodes, adjacency, node_index }
+    }
+
+    #[test]
+    fn test_most_probable_path_picks_higher_product_over_greedy_first_hop() {
+        let graph = build_diamond_graph();
+        let results = compute_most_probable_paths(&graph, 0, 5, 0.0);
+
+        let target = results.iter().find(|r| r.node_id == "n3").unwrap();
+        assert!((target.probability - 0.81).abs() < 1e-9);
+        assert_eq!(target.path, vec!["n0".to_string(), "n1".to_string(), "n3".to_string()]);
+        assert_eq!(target.hops, 2);
+    }
+
+    #[test]
+    fn test_most_probable_path_respects_max_hops() {
+        let graph = build_diamond_graph();
+        let results = compute_most_probable_paths(&graph, 0, 1, 0.0);
+
+        // Node 3 is 2 hops away either route, so it's unreachable within 1 hop.
+        assert!(!results.iter().any(|r| r.node_id == "n3"));
+        assert!(results.iter().any(|r| r.node_id == "n1" || r.node_id == "n2"));
+    }
+
+    /// A shortcut edge makes the min-cost route to an intermediate node (`A`)
+    /// exceed the hop budget, while a costlier, fewer-hop route to `A` stays
+    /// within it and is the only way to reach `T`. `A` must not be "settled"
+    /// by the cheaper 2-hop route in a way that blocks expanding the costlier
+    /// 1-hop route through it.
+    ///
+    /// Edges: `0->B` (0.9, 1 hop), `B->A` (0.9, so `0->B->A` costs less at
+    /// 2 hops), `0->A` direct (0.3, costlier but only 1 hop), `A->T` (0.9).
+    /// With `max_hops=2`, `T` is only reachable via `0->A->T`.
+    fn build_shortcut_graph() -> InMemoryGraph {
+        let ids = ["n0", "B", "A", "T"];
+        let nodes: Vec<GraphNode> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| GraphNode {
+                index: i,
+                id: id.to_string(),
+                label: "Host".to_string(),
+                criticality: 0.5,
+                is_internet_facing: false,
+                is_crown_jewel: false,
+                properties: serde_json::json!({}),
+            })
+            .collect();
+
+        let adjacency = vec![
+            vec![
+                GraphEdge { id: "e0B".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.9, target_index: 1 },
+                GraphEdge { id: "e0A".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.3, target_index: 2 },
+            ],
+            vec![GraphEdge { id: "eBA".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.9, target_index: 2 }],
+            vec![GraphEdge { id: "eAT".to_string(), edge_type: "CONNECTS_TO".to_string(), exploitability: 0.9, target_index: 3 }],
+            vec![],
+        ];
+
+        let node_index = nodes.iter().map(|n| (n.id.clone(), n.index)).collect();
+        InMemoryGraph { nodes, adjacency, node_index }
+    }
+
+    #[test]
+    fn test_most_probable_path_reaches_target_via_costlier_lower_hop_route() {
+        let graph = build_shortcut_graph();
+        let results = compute_most_probable_paths(&graph, 0, 2, 0.0);
+
+        let target = results
+            .iter()
+            .find(|r| r.node_id == "T")
+            .expect("T should be reachable within 2 hops via the costlier 0->A->T route");
+        assert_eq!(target.hops, 2);
+        assert_eq!(target.path, vec!["n0".to_string(), "A".to_string(), "T".to_string()]);
+        assert!((target.probability - 0.3 * 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_most_probable_path_skips_zero_exploitability_edges() {
+        let mut graph = build_diamond_graph();
+        graph.adjacency[0][0].exploitability = 0.0;
+
+        let results = compute_most_probable_paths(&graph, 0, 5, 0.0);
+        let target = results.iter().find(|r| r.node_id == "n3").unwrap();
+
+        // Only the node-2 route remains viable now.
+        assert!((target.probability - 0.475).abs() < 1e-9);
+        assert_eq!(target.path, vec!["n0".to_string(), "n2".to_string(), "n3".to_string()]);
+    }
 }
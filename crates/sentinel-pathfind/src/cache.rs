@@ -0,0 +1,101 @@
+//! Shared in-memory subgraph cache for [`PathfindEngine`](crate::PathfindEngine).
+//!
+//! `compute_attack_paths`, `compute_blast_radius`, and `shortest_path` each
+//! independently fetch a tenant's subgraph from Neo4j and rebuild an
+//! `InMemoryGraph` from it, so back-to-back calls for the same tenant (e.g.
+//! an interactive blast-radius drill-down following a shortest-path query)
+//! redo the same fetch and parse. This cache keys a built
+//! `Arc<InMemoryGraph>` by `(TenantId, node_limit)` so a hit within its TTL
+//! skips both steps entirely.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use sentinel_core::types::TenantId;
+
+use crate::graph::InMemoryGraph;
+
+struct CacheEntry {
+    graph: Arc<InMemoryGraph>,
+    inserted_at: DateTime<Utc>,
+    last_used: DateTime<Utc>,
+}
+
+/// Cache of built `InMemoryGraph`s, keyed by `(TenantId, node_limit)`, with
+/// TTL-based expiry and capacity-based LRU eviction.
+///
+/// Cloning shares the same underlying map, the same way cloning a
+/// [`PathfindEngine`](crate::PathfindEngine) shares the same `GraphClient`.
+#[derive(Clone)]
+pub struct SubgraphCache {
+    entries: Arc<DashMap<(Uuid, u32), CacheEntry>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl SubgraphCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(DashMap::new()),
+            ttl,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// A cached subgraph for `(tenant_id, node_limit)`, if present and
+    /// still within TTL. Bumps its recency so it survives the next LRU
+    /// eviction.
+    pub fn get(&self, tenant_id: &TenantId, node_limit: u32) -> Option<Arc<InMemoryGraph>> {
+        let key = (tenant_id.0, node_limit);
+        let mut entry = self.entries.get_mut(&key)?;
+        let age = Utc::now() - entry.inserted_at;
+        if age > chrono::Duration::seconds(self.ttl.as_secs() as i64) {
+            drop(entry);
+            self.entries.remove(&key);
+            return None;
+        }
+        entry.last_used = Utc::now();
+        Some(entry.graph.clone())
+    }
+
+    /// Insert a freshly built subgraph, evicting the least-recently-used
+    /// entry first if the cache is already at capacity.
+    pub fn insert(&self, tenant_id: &TenantId, node_limit: u32, graph: Arc<InMemoryGraph>) {
+        let key = (tenant_id.0, node_limit);
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.evict_lru();
+        }
+        let now = Utc::now();
+        self.entries.insert(
+            key,
+            CacheEntry {
+                graph,
+                inserted_at: now,
+                last_used: now,
+            },
+        );
+    }
+
+    fn evict_lru(&self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|entry| entry.last_used)
+            .map(|entry| *entry.key());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+
+    /// Drop every cached subgraph for `tenant_id`, across all node limits,
+    /// so the next lookup re-fetches from Neo4j. Ingestion pipelines should
+    /// call this after writing changes for a tenant into the graph.
+    pub fn invalidate_tenant(&self, tenant_id: &TenantId) {
+        let tenant = tenant_id.0;
+        self.entries.retain(|(id, _), _| *id != tenant);
+    }
+}
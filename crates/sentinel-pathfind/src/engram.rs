@@ -72,13 +72,12 @@ pub fn record_pathfind_results(
     );
 }
 
-/// Finalize the session and store the engram.
+/// Finalize the session and store the engram, chained onto the tenant's
+/// ledger so tampering with the pathfinding audit trail is detectable.
 pub fn finalize_and_store(session: EngramSession, engram_dir: &str) -> Option<Engram> {
-    let engram = session.finalize();
-
     match GitEngramStore::new(engram_dir) {
-        Ok(store) => match store.save(&engram) {
-            Ok(()) => {
+        Ok(store) => match store.append(session) {
+            Ok(engram) => {
                 tracing::info!(
                     engram_id = %engram.id,
                     "Engram recorded for pathfind session"
@@ -87,12 +86,12 @@ pub fn finalize_and_store(session: EngramSession, engram_dir: &str) -> Option<En
             }
             Err(e) => {
                 tracing::warn!(error = %e, "Failed to store engram");
-                Some(engram)
+                None
             }
         },
         Err(e) => {
             tracing::warn!(error = %e, "Failed to initialize engram store");
-            Some(engram)
+            None
         }
     }
 }
@@ -27,6 +27,16 @@ pub enum PathfindError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Snapshot format version mismatch: expected {expected}, found {found}")]
+    UnsupportedSnapshotVersion { expected: u32, found: u32 },
+
+    #[error("Stale snapshot at {path}: expected source hash {expected}, found {found}")]
+    StaleSnapshot {
+        path: String,
+        expected: String,
+        found: String,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, PathfindError>;
@@ -4,11 +4,22 @@
 //! optimized for cache-friendly traversal during pathfinding.
 
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
 
+use ipnet::IpNet;
 use sentinel_graph::queries::{EdgeRecord, NodeRecord};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PathfindError, Result};
+
+/// Snapshot format version for [`InMemoryGraph::save_to_path`]/
+/// [`InMemoryGraph::load_from_path`]. Bump whenever the on-disk shape of
+/// [`GraphSnapshot`] changes incompatibly.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
 
 /// Compact node metadata stored in the in-memory graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphNode {
     /// Dense index (0..N-1) for O(1) lookup.
     pub index: usize,
@@ -27,7 +38,7 @@ pub struct GraphNode {
 }
 
 /// Compact edge metadata for the adjacency list.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphEdge {
     /// Original edge ID.
     pub id: String,
@@ -50,6 +61,27 @@ pub struct InMemoryGraph {
 }
 
 impl InMemoryGraph {
+    /// Build from fetched subgraph data, scoped to a subset of IP address
+    /// space. Nodes whose `ip` property falls outside `filter` — and their
+    /// incident edges — are dropped before the dense index is assigned.
+    /// Nodes without a parseable `ip` (services, unaddressed subnets, …)
+    /// are never filtered out, since the filter only scopes address space.
+    pub fn from_subgraph_filtered(
+        nodes: Vec<NodeRecord>,
+        edges: Vec<EdgeRecord>,
+        filter: &IpFilter,
+    ) -> Self {
+        let nodes: Vec<NodeRecord> = nodes
+            .into_iter()
+            .filter(|record| match extract_ip(&record.properties) {
+                Some(ip) => filter.allows(ip),
+                None => true,
+            })
+            .collect();
+
+        Self::from_subgraph(nodes, edges)
+    }
+
     /// Build from fetched subgraph data.
     pub fn from_subgraph(nodes: Vec<NodeRecord>, edges: Vec<EdgeRecord>) -> Self {
         let mut node_index = HashMap::with_capacity(nodes.len());
@@ -59,7 +91,8 @@ impl InMemoryGraph {
             node_index.insert(record.id.clone(), i);
 
             let criticality = extract_criticality(&record.properties);
-            let is_internet_facing = detect_internet_facing(&record.label, &record.properties);
+            let ip = extract_ip(&record.properties);
+            let is_internet_facing = detect_internet_facing(&record.label, &record.properties, ip);
             let is_crown_jewel = detect_crown_jewel(criticality, &record.properties);
 
             graph_nodes.push(GraphNode {
@@ -124,6 +157,176 @@ impl InMemoryGraph {
     pub fn edge_count(&self) -> usize {
         self.adjacency.iter().map(|edges| edges.len()).sum()
     }
+
+    /// Compute a stable content hash over the source node/edge IDs fetched
+    /// from Neo4j. Compare against a loaded snapshot's stored hash to
+    /// detect staleness before trusting the cache.
+    pub fn source_hash(nodes: &[NodeRecord], edges: &[EdgeRecord]) -> String {
+        let mut node_ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        node_ids.sort_unstable();
+        let mut edge_ids: Vec<&str> = edges.iter().map(|e| e.id.as_str()).collect();
+        edge_ids.sort_unstable();
+
+        let json = serde_json::to_vec(&(node_ids, edge_ids))
+            .expect("id list serialization should not fail");
+        blake3::hash(&json).to_hex().to_string()
+    }
+
+    /// Serialize this graph to `path` as a cacheable snapshot, tagged with
+    /// `source_hash` (see [`Self::source_hash`]) so a later
+    /// [`Self::load_from_path`] can detect whether the source subgraph has
+    /// changed since the snapshot was taken.
+    pub fn save_to_path(&self, path: &Path, source_hash: &str) -> Result<()> {
+        let snapshot = GraphSnapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            source_hash: source_hash.to_string(),
+            nodes: self.nodes.clone(),
+            adjacency: self.adjacency.clone(),
+            node_index: self.node_index.clone(),
+        };
+
+        let json = serde_json::to_vec(&snapshot)
+            .map_err(|e| PathfindError::Serialization(e.to_string()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a snapshot written by [`Self::save_to_path`], rejecting it if
+    /// the format version or source hash no longer matches
+    /// `expected_source_hash` — the caller should treat either error as
+    /// "rebuild via `from_subgraph` and re-save" rather than a hard failure.
+    pub fn load_from_path(path: &Path, expected_source_hash: &str) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let snapshot: GraphSnapshot =
+            serde_json::from_slice(&bytes).map_err(|e| PathfindError::Serialization(e.to_string()))?;
+
+        if snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(PathfindError::UnsupportedSnapshotVersion {
+                expected: SNAPSHOT_FORMAT_VERSION,
+                found: snapshot.format_version,
+            });
+        }
+        if snapshot.source_hash != expected_source_hash {
+            return Err(PathfindError::StaleSnapshot {
+                path: path.display().to_string(),
+                expected: expected_source_hash.to_string(),
+                found: snapshot.source_hash,
+            });
+        }
+
+        Ok(Self {
+            nodes: snapshot.nodes,
+            adjacency: snapshot.adjacency,
+            node_index: snapshot.node_index,
+        })
+    }
+
+    /// Default max hops for a sampled walk before it's given up on.
+    const SAMPLE_MAX_DEPTH: usize = 10;
+
+    /// Sample up to `k` distinct, diverse attack paths from internet-facing
+    /// nodes to crown jewels, weighted toward easy/high-value routes but
+    /// not limited to the single best one. See
+    /// [`crate::algorithms::sample_paths`] for the weighting scheme; `seed`
+    /// makes the sampling reproducible.
+    pub fn sample_paths(&self, k: usize, seed: u64) -> Vec<crate::algorithms::RawPath> {
+        crate::algorithms::sample_paths(
+            self,
+            &self.internet_facing_nodes(),
+            &self.crown_jewel_nodes(),
+            k,
+            Self::SAMPLE_MAX_DEPTH,
+            seed,
+        )
+    }
+}
+
+/// On-disk representation of a cached [`InMemoryGraph`]. See
+/// [`InMemoryGraph::save_to_path`].
+#[derive(Debug, Serialize, Deserialize)]
+struct GraphSnapshot {
+    format_version: u32,
+    source_hash: String,
+    nodes: Vec<GraphNode>,
+    adjacency: Vec<Vec<GraphEdge>>,
+    node_index: HashMap<String, usize>,
+}
+
+/// Which class of IP address space a node's address must fall within to
+/// be kept by an [`IpFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AllowIp {
+    /// Keep every node, regardless of address (the default).
+    #[default]
+    All,
+    /// Keep only private / non-routable addresses (RFC1918, loopback,
+    /// link-local, …).
+    Private,
+    /// Keep only routable public addresses.
+    Public,
+    /// Keep nothing by address class alone; only the explicit
+    /// `allow_cidrs` list (if any) can admit a node.
+    None,
+}
+
+/// Scopes a graph to a subset of IP address space — only RFC1918 ranges,
+/// only routable public space, or a specific set of CIDRs. Use with
+/// [`InMemoryGraph::from_subgraph_filtered`].
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    /// Broad address-space class to admit.
+    pub allow: AllowIp,
+    /// Additional CIDRs to admit even if `allow` would otherwise exclude them.
+    pub allow_cidrs: Vec<IpNet>,
+    /// CIDRs to exclude even if `allow` or `allow_cidrs` would admit them.
+    /// Checked before `allow_cidrs`, so deny always wins.
+    pub deny_cidrs: Vec<IpNet>,
+}
+
+impl IpFilter {
+    /// The default, no-op filter: every address is admitted.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Whether `ip` is admitted by this filter.
+    pub fn allows(&self, ip: IpAddr) -> bool {
+        if self.deny_cidrs.iter().any(|net| net.contains(&ip)) {
+            return false;
+        }
+        if self.allow_cidrs.iter().any(|net| net.contains(&ip)) {
+            return true;
+        }
+        match self.allow {
+            AllowIp::All => true,
+            AllowIp::Private => !is_public_ip(ip),
+            AllowIp::Public => is_public_ip(ip),
+            AllowIp::None => false,
+        }
+    }
+}
+
+/// Parse a node's `ip` property, if present and valid.
+fn extract_ip(properties: &serde_json::Value) -> Option<IpAddr> {
+    properties.get("ip")?.as_str()?.parse().ok()
+}
+
+/// Whether an address is genuinely routable public space, as opposed to
+/// private, loopback, link-local, or otherwise non-routable.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_unspecified())
+        }
+        IpAddr::V6(v6) => {
+            !(v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xfe00) == 0xfc00)
+        }
+    }
 }
 
 /// Map criticality string to a numeric weight.
@@ -148,7 +351,14 @@ fn extract_criticality(properties: &serde_json::Value) -> f64 {
 }
 
 /// Detect if a node is internet-facing.
-fn detect_internet_facing(label: &str, properties: &serde_json::Value) -> bool {
+fn detect_internet_facing(label: &str, properties: &serde_json::Value, ip: Option<IpAddr>) -> bool {
+    // A genuinely public IP is authoritative, regardless of label or tags.
+    if let Some(ip) = ip {
+        if is_public_ip(ip) {
+            return true;
+        }
+    }
+
     // Subnets with is_public = true.
     if label == "Subnet" {
         if let Some(is_public) = properties.get("is_public").and_then(|v| v.as_bool()) {
@@ -345,4 +555,162 @@ mod tests {
         assert_eq!(graph.node_index.get("beta"), Some(&1));
         assert_eq!(graph.node_index.get("gamma"), None);
     }
+
+    #[test]
+    fn test_public_ip_is_authoritative_for_internet_facing() {
+        // No "public"/"dmz" tag and no is_public flag, but a routable IP.
+        let nodes = vec![make_node(
+            "h1",
+            "Host",
+            serde_json::json!({"ip": "8.8.8.8", "criticality": "low"}),
+        )];
+
+        let graph = InMemoryGraph::from_subgraph(nodes, vec![]);
+        assert!(graph.internet_facing_nodes().contains(&0));
+    }
+
+    #[test]
+    fn test_private_ip_does_not_force_internet_facing() {
+        let nodes = vec![make_node(
+            "h1",
+            "Host",
+            serde_json::json!({"ip": "10.0.0.5", "criticality": "low"}),
+        )];
+
+        let graph = InMemoryGraph::from_subgraph(nodes, vec![]);
+        assert!(graph.internet_facing_nodes().is_empty());
+    }
+
+    #[test]
+    fn test_ip_filter_private_only() {
+        let filter = IpFilter {
+            allow: AllowIp::Private,
+            ..IpFilter::all()
+        };
+
+        assert!(filter.allows("10.0.0.5".parse().unwrap()));
+        assert!(filter.allows("192.168.1.1".parse().unwrap()));
+        assert!(!filter.allows("8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_filter_public_only() {
+        let filter = IpFilter {
+            allow: AllowIp::Public,
+            ..IpFilter::all()
+        };
+
+        assert!(filter.allows("8.8.8.8".parse().unwrap()));
+        assert!(!filter.allows("10.0.0.5".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_filter_none_with_explicit_allow_cidr() {
+        let filter = IpFilter {
+            allow: AllowIp::None,
+            allow_cidrs: vec!["203.0.113.0/24".parse().unwrap()],
+            deny_cidrs: vec![],
+        };
+
+        assert!(filter.allows("203.0.113.42".parse().unwrap()));
+        assert!(!filter.allows("8.8.8.8".parse().unwrap()));
+        assert!(!filter.allows("10.0.0.5".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_filter_deny_wins_over_allow() {
+        let filter = IpFilter {
+            allow: AllowIp::All,
+            allow_cidrs: vec![],
+            deny_cidrs: vec!["10.0.0.0/8".parse().unwrap()],
+        };
+
+        assert!(!filter.allows("10.1.2.3".parse().unwrap()));
+        assert!(filter.allows("8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_from_subgraph_filtered_drops_node_and_incident_edges() {
+        let nodes = vec![
+            make_node("n1", "Host", serde_json::json!({"ip": "10.0.0.1"})),
+            make_node("n2", "Host", serde_json::json!({"ip": "8.8.8.8"})),
+        ];
+        let edges = vec![make_edge("e1", "CONNECTS_TO", "n1", "n2", 0.5)];
+
+        let filter = IpFilter {
+            allow: AllowIp::Private,
+            ..IpFilter::all()
+        };
+        let graph = InMemoryGraph::from_subgraph_filtered(nodes, edges, &filter);
+
+        assert_eq!(graph.node_count(), 1);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.node_index.get("n1"), Some(&0));
+        assert_eq!(graph.node_index.get("n2"), None);
+    }
+
+    #[test]
+    fn test_save_and_load_snapshot_roundtrip() {
+        let nodes = vec![
+            make_node("n1", "Host", serde_json::json!({"criticality": "high"})),
+            make_node("n2", "Service", serde_json::json!({})),
+        ];
+        let edges = vec![make_edge("e1", "CONNECTS_TO", "n1", "n2", 0.7)];
+
+        let hash = InMemoryGraph::source_hash(&nodes, &edges);
+        let graph = InMemoryGraph::from_subgraph(nodes, edges);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        graph.save_to_path(&path, &hash).unwrap();
+
+        let loaded = InMemoryGraph::load_from_path(&path, &hash).unwrap();
+        assert_eq!(loaded.node_count(), graph.node_count());
+        assert_eq!(loaded.edge_count(), graph.edge_count());
+        assert_eq!(loaded.node_index, graph.node_index);
+    }
+
+    #[test]
+    fn test_load_snapshot_detects_stale_hash() {
+        let nodes = vec![make_node("n1", "Host", serde_json::json!({}))];
+        let hash = InMemoryGraph::source_hash(&nodes, &[]);
+        let graph = InMemoryGraph::from_subgraph(nodes, vec![]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        graph.save_to_path(&path, &hash).unwrap();
+
+        let err = InMemoryGraph::load_from_path(&path, "different-hash").unwrap_err();
+        assert!(matches!(err, PathfindError::StaleSnapshot { .. }));
+    }
+
+    #[test]
+    fn test_source_hash_stable_under_reordering() {
+        let nodes_a = vec![
+            make_node("n1", "Host", serde_json::json!({})),
+            make_node("n2", "Host", serde_json::json!({})),
+        ];
+        let nodes_b = vec![
+            make_node("n2", "Host", serde_json::json!({})),
+            make_node("n1", "Host", serde_json::json!({})),
+        ];
+
+        assert_eq!(
+            InMemoryGraph::source_hash(&nodes_a, &[]),
+            InMemoryGraph::source_hash(&nodes_b, &[])
+        );
+    }
+
+    #[test]
+    fn test_from_subgraph_filtered_keeps_nodes_without_ip() {
+        let nodes = vec![make_node("svc1", "Service", serde_json::json!({}))];
+
+        let filter = IpFilter {
+            allow: AllowIp::None,
+            ..IpFilter::all()
+        };
+        let graph = InMemoryGraph::from_subgraph_filtered(nodes, vec![], &filter);
+
+        assert_eq!(graph.node_count(), 1);
+    }
 }
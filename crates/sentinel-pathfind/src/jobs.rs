@@ -0,0 +1,192 @@
+//! Background job submission and polling for long-running attack path
+//! computations.
+//!
+//! [`PathfindEngine::compute_attack_paths`] runs fetch -> build -> enumerate
+//! -> score synchronously inside one `await`, which can take many seconds
+//! against a large subgraph and forces callers to block. This module adds
+//! [`PathfindEngine::submit_attack_paths`], which spawns the same
+//! computation on a background task and returns a [`JobId`] immediately,
+//! and [`PathfindEngine::poll_job`], which reads back its progress -- the
+//! same poll-ID shape used for long-running RPC calls elsewhere (e.g.
+//! openethereum's `rpc_poll_ids`).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::PathfindResult;
+use crate::{PathfindEngine, PathfindRequest};
+
+/// How long a finished job's status is kept around for polling before
+/// [`JobStore`]'s background eviction task reclaims it.
+const JOB_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// How often the eviction task sweeps for expired jobs.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Identifier for a job submitted via
+/// [`PathfindEngine::submit_attack_paths`], passed back into
+/// [`PathfindEngine::poll_job`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct JobId(pub Uuid);
+
+impl JobId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for JobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Snapshot of a submitted job's progress, returned by
+/// [`PathfindEngine::poll_job`].
+///
+/// `Failed` carries the rendered error message rather than `PathfindError`
+/// itself: the error may wrap a non-`Clone`, non-serializable
+/// `neo4rs::Error`, and a status polled over an RPC boundary needs to be
+/// both anyway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JobStatus {
+    Running { phase: String, paths_found: usize },
+    Done(PathfindResult),
+    Failed(String),
+}
+
+impl JobStatus {
+    fn is_finished(&self) -> bool {
+        !matches!(self, JobStatus::Running { .. })
+    }
+}
+
+struct JobEntry {
+    status: JobStatus,
+    updated_at: DateTime<Utc>,
+}
+
+/// Shared store of in-flight and recently-finished jobs, keyed by [`JobId`].
+///
+/// Cloning a `JobStore` shares the same underlying map, the same way
+/// cloning a [`PathfindEngine`] shares the same `GraphClient` connection
+/// pool. Construction spawns a background task that evicts finished jobs
+/// older than [`JOB_TTL`] so the map doesn't grow unbounded across a
+/// long-running process.
+#[derive(Clone)]
+pub struct JobStore {
+    jobs: Arc<DashMap<Uuid, JobEntry>>,
+}
+
+impl JobStore {
+    pub fn new() -> Self {
+        let jobs: Arc<DashMap<Uuid, JobEntry>> = Arc::new(DashMap::new());
+        let store = Self { jobs };
+        store.spawn_eviction_task();
+        store
+    }
+
+    fn spawn_eviction_task(&self) {
+        let jobs = self.jobs.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EVICTION_INTERVAL);
+            loop {
+                interval.tick().await;
+                let cutoff = Utc::now() - chrono::Duration::seconds(JOB_TTL.as_secs() as i64);
+                jobs.retain(|_, entry| !(entry.status.is_finished() && entry.updated_at < cutoff));
+            }
+        });
+    }
+
+    fn set(&self, job_id: JobId, status: JobStatus) {
+        self.jobs.insert(
+            job_id.0,
+            JobEntry {
+                status,
+                updated_at: Utc::now(),
+            },
+        );
+    }
+
+    fn set_running(&self, job_id: JobId, phase: &str, paths_found: usize) {
+        self.set(
+            job_id,
+            JobStatus::Running {
+                phase: phase.to_string(),
+                paths_found,
+            },
+        );
+    }
+
+    fn set_done(&self, job_id: JobId, result: PathfindResult) {
+        self.set(job_id, JobStatus::Done(result));
+    }
+
+    fn set_failed(&self, job_id: JobId, error: &crate::error::PathfindError) {
+        self.set(job_id, JobStatus::Failed(error.to_string()));
+    }
+
+    /// Current status of `job_id`, or `None` if it was never submitted or
+    /// has since been evicted.
+    pub fn get(&self, job_id: JobId) -> Option<JobStatus> {
+        self.jobs.get(&job_id.0).map(|entry| entry.status.clone())
+    }
+}
+
+impl Default for JobStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reports progress for a job-tracked computation. `None` for the plain
+/// synchronous [`PathfindEngine::compute_attack_paths`] call, `Some` when
+/// driven from [`PathfindEngine::submit_attack_paths`]'s background task.
+pub(crate) type JobProgress<'a> = Option<(&'a JobStore, JobId)>;
+
+pub(crate) fn report_phase(progress: JobProgress<'_>, phase: &str, paths_found: usize) {
+    if let Some((store, job_id)) = progress {
+        store.set_running(job_id, phase, paths_found);
+    }
+}
+
+impl PathfindEngine {
+    /// Submit an attack path computation to run on a background task,
+    /// returning a [`JobId`] immediately instead of blocking until it's
+    /// done. Poll progress and the eventual result with [`Self::poll_job`].
+    pub fn submit_attack_paths(&self, request: PathfindRequest) -> JobId {
+        let job_id = JobId::new();
+        self.job_store.set_running(job_id, "queued", 0);
+
+        let engine = self.clone();
+        tokio::spawn(async move {
+            match engine
+                .compute_attack_paths_tracked(request, Some((&engine.job_store, job_id)))
+                .await
+            {
+                Ok(result) => engine.job_store.set_done(job_id, result),
+                Err(e) => engine.job_store.set_failed(job_id, &e),
+            }
+        });
+
+        job_id
+    }
+
+    /// Current status of a job submitted via [`Self::submit_attack_paths`],
+    /// or `None` if `job_id` is unknown or its finished status has since
+    /// been evicted (see [`JobStore`]).
+    pub fn poll_job(&self, job_id: JobId) -> Option<JobStatus> {
+        self.job_store.get(job_id)
+    }
+}
@@ -7,31 +7,44 @@
 
 pub mod algorithms;
 pub mod blast;
+pub mod cache;
 pub mod engram;
 pub mod error;
 pub mod fetch;
 pub mod graph;
+pub mod jobs;
 pub mod lateral;
+pub mod scc;
 pub mod scoring;
 pub mod types;
 
+pub use cache::SubgraphCache;
 pub use error::PathfindError;
+pub use jobs::{JobId, JobStatus};
 pub use types::{BlastRadiusRequest, BlastRadiusResult, PathfindRequest, PathfindResult};
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use chrono::Utc;
+use rayon::prelude::*;
 use sentinel_core::types::{AttackPath, AttackStep, EdgeId, NodeId, TenantId};
 use sentinel_graph::GraphClient;
 use uuid::Uuid;
 
 use crate::algorithms::RawPath;
 use crate::graph::InMemoryGraph;
+use crate::jobs::{report_phase, JobProgress, JobStore};
 use crate::scoring::ScoringConfig;
 
 /// The main attack path computation engine.
+#[derive(Clone)]
 pub struct PathfindEngine {
     graph_client: GraphClient,
     scoring_config: ScoringConfig,
     engram_dir: Option<String>,
+    job_store: JobStore,
+    subgraph_cache: Option<SubgraphCache>,
 }
 
 impl PathfindEngine {
@@ -41,6 +54,8 @@ impl PathfindEngine {
             graph_client,
             scoring_config: ScoringConfig::default(),
             engram_dir: None,
+            job_store: JobStore::new(),
+            subgraph_cache: None,
         }
     }
 
@@ -56,6 +71,50 @@ impl PathfindEngine {
         self
     }
 
+    /// Enable the shared subgraph cache (see [`cache`]): subsequent
+    /// `compute_attack_paths`/`compute_blast_radius`/`shortest_path`/
+    /// `k_shortest_attack_paths` calls for the same `(tenant_id, node_limit)`
+    /// reuse a cached `InMemoryGraph` instead of refetching from Neo4j, as
+    /// long as the entry is within `ttl` and hasn't been evicted to stay
+    /// under `capacity`. Off by default.
+    pub fn with_subgraph_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.subgraph_cache = Some(SubgraphCache::new(ttl, capacity));
+        self
+    }
+
+    /// Drop any cached subgraph for `tenant_id`, forcing the next lookup to
+    /// refetch from Neo4j. Call after ingestion writes new data for the
+    /// tenant into the graph. A no-op if the subgraph cache isn't enabled.
+    pub fn invalidate_tenant(&self, tenant_id: &TenantId) {
+        if let Some(cache) = &self.subgraph_cache {
+            cache.invalidate_tenant(tenant_id);
+        }
+    }
+
+    /// Fetch and build (or, on a cache hit, reuse) the `InMemoryGraph` for
+    /// `tenant_id` capped at `node_limit` nodes.
+    async fn get_subgraph(
+        &self,
+        tenant_id: &TenantId,
+        node_limit: u32,
+    ) -> error::Result<Arc<InMemoryGraph>> {
+        if let Some(cache) = &self.subgraph_cache {
+            if let Some(graph) = cache.get(tenant_id, node_limit) {
+                return Ok(graph);
+            }
+        }
+
+        let subgraph =
+            fetch::fetch_tenant_subgraph(&self.graph_client, tenant_id, node_limit).await?;
+        let graph = Arc::new(InMemoryGraph::from_subgraph(subgraph.nodes, subgraph.edges));
+
+        if let Some(cache) = &self.subgraph_cache {
+            cache.insert(tenant_id, node_limit, graph.clone());
+        }
+
+        Ok(graph)
+    }
+
     /// Compute attack paths for a tenant.
     ///
     /// Orchestrates: fetch subgraph → build in-memory graph → identify sources/targets →
@@ -63,6 +122,18 @@ impl PathfindEngine {
     pub async fn compute_attack_paths(
         &self,
         request: PathfindRequest,
+    ) -> error::Result<PathfindResult> {
+        self.compute_attack_paths_tracked(request, None).await
+    }
+
+    /// Same computation as [`Self::compute_attack_paths`], reporting phase
+    /// and path-count progress into `progress` (if given) along the way.
+    /// Shared by the plain synchronous call and
+    /// [`Self::submit_attack_paths`]'s background task.
+    async fn compute_attack_paths_tracked(
+        &self,
+        request: PathfindRequest,
+        progress: JobProgress<'_>,
     ) -> error::Result<PathfindResult> {
         let start = std::time::Instant::now();
         let tenant_str = request.tenant_id.0.to_string();
@@ -79,22 +150,20 @@ impl PathfindEngine {
             }),
         );
 
-        // Fetch subgraph from Neo4j.
-        let subgraph = fetch::fetch_tenant_subgraph(
-            &self.graph_client,
-            &request.tenant_id,
-            request.node_limit.unwrap_or(50_000),
-        )
-        .await?;
+        report_phase(progress, "fetch", 0);
 
-        if subgraph.nodes.is_empty() {
+        // Fetch (or reuse a cached) subgraph from Neo4j.
+        let mem_graph = self
+            .get_subgraph(&request.tenant_id, request.node_limit.unwrap_or(50_000))
+            .await?;
+
+        if mem_graph.node_count() == 0 {
             return Err(PathfindError::EmptySubgraph {
                 tenant_id: tenant_str,
             });
         }
 
-        // Build in-memory graph.
-        let mem_graph = InMemoryGraph::from_subgraph(subgraph.nodes, subgraph.edges);
+        report_phase(progress, "build", 0);
         let graph_stats = types::GraphStats {
             total_nodes: mem_graph.node_count(),
             total_edges: mem_graph.edge_count(),
@@ -140,17 +209,33 @@ impl PathfindEngine {
             });
         }
 
+        report_phase(progress, "enumerate", 0);
+
         // Run all-paths enumeration.
         let max_depth = request.max_depth.unwrap_or(10);
         let max_paths = request.max_paths.unwrap_or(100);
         let raw_paths = algorithms::enumerate_all_paths(&mem_graph, &sources, &targets, max_depth, max_paths);
 
-        // Score and convert paths.
+        report_phase(progress, "enumerate", raw_paths.len());
+
+        // Score and convert paths. `InMemoryGraph` is read-only from here on,
+        // so scoring (the other dominant cost alongside enumeration, which
+        // is already parallelized per-source in `enumerate_all_paths`) fans
+        // out across a rayon thread pool too.
         let mut attack_paths: Vec<AttackPath> = raw_paths
-            .iter()
+            .par_iter()
             .map(|rp| self.raw_path_to_attack_path(rp, &mem_graph, &request.tenant_id))
             .collect();
-        attack_paths.sort_by(|a, b| b.risk_score.partial_cmp(&a.risk_score).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Sort by risk descending, tie-broken by source/target node id so
+        // output ordering is stable regardless of thread scheduling.
+        attack_paths.sort_by(|a, b| {
+            b.risk_score
+                .partial_cmp(&a.risk_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.source_node.0.cmp(&b.source_node.0))
+                .then_with(|| a.target_node.0.cmp(&b.target_node.0))
+        });
 
         // Lateral movement detection.
         let lateral_chains = if request.include_lateral.unwrap_or(false) {
@@ -222,14 +307,7 @@ impl PathfindEngine {
         &self,
         request: BlastRadiusRequest,
     ) -> error::Result<BlastRadiusResult> {
-        let subgraph = fetch::fetch_tenant_subgraph(
-            &self.graph_client,
-            &request.tenant_id,
-            50_000,
-        )
-        .await?;
-
-        let mem_graph = InMemoryGraph::from_subgraph(subgraph.nodes, subgraph.edges);
+        let mem_graph = self.get_subgraph(&request.tenant_id, 50_000).await?;
         let node_idx = mem_graph
             .node_index
             .get(&request.compromised_node_id)
@@ -250,14 +328,7 @@ impl PathfindEngine {
         source_id: &str,
         target_id: &str,
     ) -> error::Result<Option<AttackPath>> {
-        let subgraph = fetch::fetch_tenant_subgraph(
-            &self.graph_client,
-            tenant_id,
-            50_000,
-        )
-        .await?;
-
-        let mem_graph = InMemoryGraph::from_subgraph(subgraph.nodes, subgraph.edges);
+        let mem_graph = self.get_subgraph(tenant_id, 50_000).await?;
         let src_idx = mem_graph
             .node_index
             .get(source_id)
@@ -277,6 +348,41 @@ impl PathfindEngine {
         Ok(raw_path.map(|rp| self.raw_path_to_attack_path(&rp, &mem_graph, tenant_id)))
     }
 
+    /// Compute the `k` most plausible attack paths between two specific
+    /// nodes, ranked by cumulative exploit cost rather than hop count (see
+    /// [`algorithms::k_shortest_weighted_paths`]).
+    pub async fn k_shortest_attack_paths(
+        &self,
+        tenant_id: &TenantId,
+        source_id: &str,
+        target_id: &str,
+        k: usize,
+        max_hops: usize,
+    ) -> error::Result<Vec<AttackPath>> {
+        let mem_graph = self.get_subgraph(tenant_id, 50_000).await?;
+        let src_idx = mem_graph
+            .node_index
+            .get(source_id)
+            .copied()
+            .ok_or_else(|| PathfindError::NodeNotFound {
+                node_id: source_id.to_string(),
+            })?;
+        let tgt_idx = mem_graph
+            .node_index
+            .get(target_id)
+            .copied()
+            .ok_or_else(|| PathfindError::NodeNotFound {
+                node_id: target_id.to_string(),
+            })?;
+
+        let raw_paths =
+            algorithms::k_shortest_weighted_paths(&mem_graph, src_idx, tgt_idx, k, max_hops);
+        Ok(raw_paths
+            .iter()
+            .map(|rp| self.raw_path_to_attack_path(rp, &mem_graph, tenant_id))
+            .collect())
+    }
+
     /// Convert a `RawPath` into an `AttackPath` with scoring.
     fn raw_path_to_attack_path(
         &self,
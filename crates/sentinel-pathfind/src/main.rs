@@ -3,6 +3,8 @@
 //! Designed for subprocess invocation from the Python API:
 //! reads a JSON request from stdin, writes a JSON result to stdout.
 
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 use tracing_subscriber::{fmt, EnvFilter};
 
@@ -99,7 +101,7 @@ fn load_graph_config(file_prefix: &str) -> GraphConfig {
         )
         .build();
 
-    match cfg {
+    let mut graph_config = match cfg {
         Ok(c) => GraphConfig {
             uri: c
                 .get_string("neo4j.uri")
@@ -110,8 +112,30 @@ fn load_graph_config(file_prefix: &str) -> GraphConfig {
             password: c
                 .get_string("neo4j.password")
                 .unwrap_or_else(|_| "sentinel-dev".to_string()),
+            tls_ca_cert: c.get_string("neo4j.tls_ca_cert_path").ok().map(PathBuf::from),
+            tls_client_cert: c.get_string("neo4j.tls_client_cert_path").ok().map(PathBuf::from),
+            tls_client_key: c.get_string("neo4j.tls_client_key_path").ok().map(PathBuf::from),
+            tls_verify_hostname: c.get_bool("neo4j.tls_verify_hostname").unwrap_or(true),
             ..Default::default()
         },
         Err(_) => GraphConfig::default(),
+    };
+
+    // Credentials and TLS cert paths may also (or only) come from the
+    // process environment, bypassing the config file entirely, so they
+    // never need to be written to `sentinel.toml`.
+    if let Ok(password) = std::env::var("SENTINEL_NEO4J_PASSWORD") {
+        graph_config.password = password;
+    }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CA_CERT_PATH") {
+        graph_config.tls_ca_cert = Some(PathBuf::from(path));
     }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CLIENT_CERT_PATH") {
+        graph_config.tls_client_cert = Some(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var("SENTINEL_NEO4J_TLS_CLIENT_KEY_PATH") {
+        graph_config.tls_client_key = Some(PathBuf::from(path));
+    }
+
+    graph_config
 }
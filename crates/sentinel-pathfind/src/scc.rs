@@ -0,0 +1,302 @@
+//! Strongly-connected-component detection and condensation.
+//!
+//! Mutually reachable clusters of hosts are a strong lateral-movement
+//! indicator: an attacker who compromises one member can route back and
+//! forth among the rest. Finds those clusters with an iterative Tarjan's
+//! algorithm (iterative to avoid stack overflow on deep graphs) and offers
+//! a condensation that collapses each nontrivial component into a single
+//! super-node so callers can run acyclic algorithms (e.g. Dijkstra) on the
+//! resulting DAG and expand cycles back out in post-processing.
+
+use std::collections::HashMap;
+
+use crate::graph::{GraphEdge, GraphNode, InMemoryGraph};
+
+/// A cluster of mutually-reachable nodes flagged as a "reachability loop".
+#[derive(Debug, Clone)]
+pub struct ReachabilityLoop {
+    /// Original node IDs of every member of the loop.
+    pub node_ids: Vec<String>,
+}
+
+/// The strongly connected components of `graph`, each as a list of node
+/// indices, using an iterative Tarjan's algorithm.
+///
+/// Tarjan assigns each node a discovery `index` and a `lowlink` (the lowest
+/// index reachable from it via tree edges and back edges to nodes still on
+/// the stack); a node roots its own SCC exactly when `lowlink[v] ==
+/// index[v]`, at which point everything above it on the stack is popped off
+/// as that component. The algorithm is run iteratively with an explicit
+/// work stack (rather than recursively) so it doesn't blow the call stack
+/// on a graph with a long dependency chain.
+pub fn strongly_connected_components(graph: &InMemoryGraph) -> Vec<Vec<usize>> {
+    let n = graph.node_count();
+    let mut index: Vec<Option<usize>> = vec![None; n];
+    let mut lowlink: Vec<usize> = vec![0; n];
+    let mut on_stack: Vec<bool> = vec![false; n];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next_index = 0;
+    let mut components: Vec<Vec<usize>> = Vec::new();
+
+    for start in 0..n {
+        if index[start].is_some() {
+            continue;
+        }
+
+        // Explicit work stack of (node, next adjacency position to try).
+        let mut work: Vec<(usize, usize)> = vec![(start, 0)];
+        index[start] = Some(next_index);
+        lowlink[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some(&(v, pos)) = work.last() {
+            if pos < graph.adjacency[v].len() {
+                let w = graph.adjacency[v][pos].target_index;
+                work.last_mut().expect("just matched Some above").1 += 1;
+
+                match index[w] {
+                    None => {
+                        // Tree edge: recurse into w.
+                        index[w] = Some(next_index);
+                        lowlink[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        work.push((w, 0));
+                    }
+                    Some(w_index) if on_stack[w] => {
+                        // Back edge to a node still on the stack.
+                        lowlink[v] = lowlink[v].min(w_index);
+                    }
+                    Some(_) => {
+                        // Cross edge to an already-finished SCC; ignore.
+                    }
+                }
+            } else {
+                // Finished exploring v's neighbors.
+                work.pop();
+
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[v]);
+                }
+
+                if lowlink[v] == index[v].expect("v was indexed on entry") {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = stack.pop().expect("stack non-empty until v is popped");
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Flag every strongly connected component with more than one node as a
+/// reachability loop, carrying its members' original node IDs.
+pub fn detect_reachability_loops(graph: &InMemoryGraph) -> Vec<ReachabilityLoop> {
+    strongly_connected_components(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| ReachabilityLoop {
+            node_ids: component.into_iter().map(|idx| graph.nodes[idx].id.clone()).collect(),
+        })
+        .collect()
+}
+
+/// The DAG formed by collapsing every strongly connected component of
+/// `graph` with more than one node into a single super-node. Edges between
+/// two original nodes that land in the same component are dropped (they'd
+/// be self-loops on the super-node); parallel edges between two distinct
+/// components are kept as-is rather than deduplicated, so downstream
+/// algorithms that care about edge weight still see every option.
+///
+/// `member_of` maps a condensed-graph node index back to the original node
+/// indices that were merged into it, so callers can expand a path through
+/// the condensation back into the original graph.
+pub struct Condensation {
+    pub graph: InMemoryGraph,
+    pub member_of: Vec<Vec<usize>>,
+}
+
+/// Collapse each nontrivial SCC of `graph` into a super-node. See
+/// [`Condensation`].
+pub fn condense(graph: &InMemoryGraph) -> Condensation {
+    let components = strongly_connected_components(graph);
+
+    let mut component_of: Vec<usize> = vec![0; graph.node_count()];
+    for (component_idx, component) in components.iter().enumerate() {
+        for &node in component {
+            component_of[node] = component_idx;
+        }
+    }
+
+    let super_nodes: Vec<GraphNode> = components
+        .iter()
+        .enumerate()
+        .map(|(component_idx, component)| {
+            let representative = &graph.nodes[component[0]];
+            GraphNode {
+                index: component_idx,
+                id: if component.len() > 1 {
+                    format!("scc-{component_idx}")
+                } else {
+                    representative.id.clone()
+                },
+                label: representative.label.clone(),
+                criticality: component
+                    .iter()
+                    .map(|&idx| graph.nodes[idx].criticality)
+                    .fold(0.0, f64::max),
+                is_internet_facing: component.iter().any(|&idx| graph.nodes[idx].is_internet_facing),
+                is_crown_jewel: component.iter().any(|&idx| graph.nodes[idx].is_crown_jewel),
+                properties: representative.properties.clone(),
+            }
+        })
+        .collect();
+
+    let mut super_adjacency: Vec<Vec<GraphEdge>> = vec![Vec::new(); components.len()];
+    for (from, edges) in graph.adjacency.iter().enumerate() {
+        let from_component = component_of[from];
+        for edge in edges {
+            let to_component = component_of[edge.target_index];
+            if to_component == from_component {
+                continue;
+            }
+            super_adjacency[from_component].push(GraphEdge {
+                target_index: to_component,
+                ..edge.clone()
+            });
+        }
+    }
+
+    let node_index: HashMap<String, usize> =
+        super_nodes.iter().map(|n| (n.id.clone(), n.index)).collect();
+
+    Condensation {
+        graph: InMemoryGraph {
+            nodes: super_nodes,
+            adjacency: super_adjacency,
+            node_index,
+        },
+        member_of: components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as TestHashMap;
+
+    fn node(i: usize) -> GraphNode {
+        GraphNode {
+            index: i,
+            id: format!("n{i}"),
+            label: "Host".to_string(),
+            criticality: 0.2,
+            is_internet_facing: false,
+            is_crown_jewel: false,
+            properties: serde_json::json!({}),
+        }
+    }
+
+    fn edge(id: &str, target: usize) -> GraphEdge {
+        GraphEdge {
+            id: id.to_string(),
+            edge_type: "CONNECTS_TO".to_string(),
+            exploitability: 0.5,
+            target_index: target,
+        }
+    }
+
+    fn build_graph(nodes: usize, adjacency: Vec<Vec<GraphEdge>>) -> InMemoryGraph {
+        let nodes: Vec<GraphNode> = (0..nodes).map(node).collect();
+        let node_index: TestHashMap<String, usize> =
+            nodes.iter().map(|n| (n.id.clone(), n.index)).collect();
+        InMemoryGraph { nodes, adjacency, node_index }
+    }
+
+    /// A 3-cycle (0 -> 1 -> 2 -> 0) feeding into a separate tail node 3.
+    fn build_cyclic_graph() -> InMemoryGraph {
+        build_graph(
+            4,
+            vec![
+                vec![edge("e01", 1)],
+                vec![edge("e12", 2)],
+                vec![edge("e20", 0), edge("e23", 3)],
+                vec![],
+            ],
+        )
+    }
+
+    #[test]
+    fn finds_the_3_cycle_as_one_component() {
+        let graph = build_cyclic_graph();
+        let mut components = strongly_connected_components(&graph);
+        components.sort_by_key(|c| c.len());
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], vec![3]);
+
+        let mut cycle = components[1].clone();
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn acyclic_graph_has_only_singleton_components() {
+        let graph = build_graph(3, vec![vec![edge("e01", 1)], vec![edge("e12", 2)], vec![]]);
+        let components = strongly_connected_components(&graph);
+
+        assert_eq!(components.len(), 3);
+        assert!(components.iter().all(|c| c.len() == 1));
+    }
+
+    #[test]
+    fn detect_reachability_loops_only_reports_nontrivial_components() {
+        let graph = build_cyclic_graph();
+        let loops = detect_reachability_loops(&graph);
+
+        assert_eq!(loops.len(), 1);
+        let mut ids = loops[0].node_ids.clone();
+        ids.sort();
+        assert_eq!(ids, vec!["n0".to_string(), "n1".to_string(), "n2".to_string()]);
+    }
+
+    #[test]
+    fn condense_collapses_cycle_into_a_single_super_node_with_one_outgoing_edge() {
+        let graph = build_cyclic_graph();
+        let condensation = condense(&graph);
+
+        // 3 cyclic nodes collapse to 1 super-node; node 3 stays on its own.
+        assert_eq!(condensation.graph.node_count(), 2);
+
+        let cycle_super = condensation
+            .member_of
+            .iter()
+            .position(|members| members.len() == 3)
+            .expect("cyclic component present");
+        // The only edge leaving the cycle is e23 -> node 3's component.
+        assert_eq!(condensation.graph.adjacency[cycle_super].len(), 1);
+    }
+
+    #[test]
+    fn condense_keeps_singleton_components_unmerged_with_original_id() {
+        let graph = build_graph(2, vec![vec![edge("e01", 1)], vec![]]);
+        let condensation = condense(&graph);
+
+        assert_eq!(condensation.graph.node_count(), 2);
+        let ids: Vec<&str> = condensation.graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert!(ids.contains(&"n0"));
+        assert!(ids.contains(&"n1"));
+    }
+}
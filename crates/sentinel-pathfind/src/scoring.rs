@@ -15,6 +15,26 @@ pub struct ScoringConfig {
     pub max_score: f64,
     /// Default exploitability when an edge has no score (default 0.5).
     pub default_exploitability: f64,
+    /// Weight applied to a vulnerability's EPSS score (a 0–1 probability of
+    /// exploitation within 30 days) when deriving exploitability from
+    /// `Vulnerability` nodes (default 0.5).
+    pub w_epss: f64,
+    /// Weight applied to a vulnerability's normalized CVSS base score
+    /// (`cvss_score / 10`) when deriving exploitability from `Vulnerability`
+    /// nodes (default 0.4).
+    pub w_cvss: f64,
+    /// Flat bonus added when a vulnerability is listed in CISA's Known
+    /// Exploited Vulnerabilities catalog (default 0.2).
+    pub kev_bonus: f64,
+    /// How much vulnerability-derived exploitability should override the
+    /// static per-edge `exploitability_score`, in `[0.0, 1.0]`. `0.0`
+    /// (default) preserves the original behavior of scoring purely off the
+    /// edge's own score; `1.0` ignores the static score entirely wherever a
+    /// target node has `HasCve` edges.
+    pub vuln_blend: f64,
+    /// Which formula combines per-hop exploitabilities into a risk score
+    /// (default [`ScoringModel::Additive`]).
+    pub model: ScoringModel,
 }
 
 impl Default for ScoringConfig {
@@ -23,13 +43,145 @@ impl Default for ScoringConfig {
             decay_factor: 0.9,
             max_score: 10.0,
             default_exploitability: 0.5,
+            w_epss: 0.5,
+            w_cvss: 0.4,
+            kev_bonus: 0.2,
+            vuln_blend: 0.0,
+            model: ScoringModel::default(),
         }
     }
 }
 
-/// Compute the risk score for an attack path.
+/// Derive a node's exploitability from the `Vulnerability` nodes attached to
+/// it via `HasCve` edges, falling back to `config.default_exploitability`
+/// when it has none.
 ///
-/// Returns a score in `[0.0, 10.0]` range.
+/// Each vulnerability contributes a weight
+/// `v = clamp(w_epss·epss + w_cvss·(cvss/10) + kev_bonus·[in_cisa_kev], 0, 1)`,
+/// and multiple vulnerabilities are combined with an "at least one
+/// succeeds" rule `e = 1 − Π(1 − v_i)`, so several weak vulnerabilities can
+/// compound into a node that's easy to exploit overall even if none of them
+/// individually is.
+fn vulnerability_derived_exploitability(
+    graph: &InMemoryGraph,
+    node_idx: usize,
+    config: &ScoringConfig,
+) -> f64 {
+    let mut none_succeed = 1.0;
+    let mut found_any = false;
+
+    for vuln in attached_vulnerabilities(graph, node_idx) {
+        let epss = vuln.properties.get("epss_score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let cvss = vuln.properties.get("cvss_score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let in_kev = vuln
+            .properties
+            .get("in_cisa_kev")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let v = (config.w_epss * epss + config.w_cvss * (cvss / 10.0)
+            + if in_kev { config.kev_bonus } else { 0.0 })
+        .clamp(0.0, 1.0);
+
+        none_succeed *= 1.0 - v;
+        found_any = true;
+    }
+
+    if found_any {
+        1.0 - none_succeed
+    } else {
+        config.default_exploitability
+    }
+}
+
+/// `Vulnerability` nodes attached to `node_idx` via `HasCve` edges.
+fn attached_vulnerabilities(
+    graph: &InMemoryGraph,
+    node_idx: usize,
+) -> impl Iterator<Item = &crate::graph::GraphNode> {
+    graph.adjacency[node_idx]
+        .iter()
+        .filter(|edge| edge.edge_type == "HAS_CVE")
+        .filter_map(|edge| graph.nodes.get(edge.target_index))
+}
+
+/// Which formula [`compute_path_risk_score`] uses to turn a path's per-edge
+/// exploitabilities into a single risk score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoringModel {
+    /// `risk = target_criticality · Σ(e_i) · decay^(N-1)`, normalized to
+    /// `[0, max_score]`. The original model; kept as the default so existing
+    /// callers see no behavior change.
+    #[default]
+    Additive,
+    /// `risk = target_criticality · Π(e_i) · max_score`, treating each edge
+    /// exploitability as an independent success probability. Penalizes long
+    /// chains naturally -- each extra hop can only shrink the product -- so
+    /// there's no separate decay constant to tune, and scores stay
+    /// monotonic: adding a hop never raises the score.
+    Probabilistic,
+}
+
+/// Effective exploitability for one hop: the edge's static score, blended
+/// with exploitability derived from the hop's target's attached
+/// vulnerabilities when `config.vuln_blend > 0` (see
+/// [`vulnerability_derived_exploitability`]).
+fn effective_exploitability(
+    graph: &InMemoryGraph,
+    from_idx: usize,
+    edge_pos: usize,
+    config: &ScoringConfig,
+) -> f64 {
+    let edge = &graph.adjacency[from_idx][edge_pos];
+    if config.vuln_blend <= 0.0 {
+        return edge.exploitability;
+    }
+    let vuln_exploit = vulnerability_derived_exploitability(graph, edge.target_index, config);
+    (1.0 - config.vuln_blend) * edge.exploitability + config.vuln_blend * vuln_exploit
+}
+
+/// Combine a path's per-hop exploitabilities into a single score, per
+/// `config.model`. Shared by [`compute_path_risk_score`] and
+/// [`explain_path_risk`], which both need to recompute this for variants of
+/// the same path's exploitability list.
+fn score_from_exploitabilities(
+    target_criticality: f64,
+    exploitabilities: &[f64],
+    config: &ScoringConfig,
+) -> f64 {
+    if exploitabilities.is_empty() {
+        return 0.0;
+    }
+
+    match config.model {
+        ScoringModel::Additive => {
+            let exploit_sum: f64 = exploitabilities.iter().sum();
+
+            // Path probability decay.
+            let hop_count = exploitabilities.len();
+            let path_probability = config.decay_factor.powi((hop_count - 1) as i32);
+
+            // Raw score.
+            let raw = target_criticality * exploit_sum * path_probability;
+
+            // Theoretical maximum: criticality=1.0, all exploitabilities=1.0, decay=1.0
+            let theoretical_max = 1.0 * hop_count as f64;
+
+            // Normalize to 0-10 range.
+            let normalized = (raw / theoretical_max) * config.max_score;
+            normalized.min(config.max_score)
+        }
+        ScoringModel::Probabilistic => {
+            let path_probability: f64 = exploitabilities.iter().product();
+            let raw = target_criticality * path_probability * config.max_score;
+            raw.clamp(0.0, config.max_score)
+        }
+    }
+}
+
+/// Compute the risk score for an attack path, using `config.model`.
+///
+/// Returns a score in `[0.0, max_score]` (`[0.0, 10.0]` by default).
 pub fn compute_path_risk_score(
     graph: &InMemoryGraph,
     path: &RawPath,
@@ -39,34 +191,103 @@ pub fn compute_path_risk_score(
         return 0.0;
     }
 
-    // Target criticality (last node in path).
     let target_idx = *path.node_indices.last().unwrap();
     let target_criticality = graph.nodes[target_idx].criticality;
 
-    // Sum of edge exploitabilities.
-    let exploit_sum: f64 = path
+    let exploitabilities: Vec<f64> = path
         .edges
         .iter()
-        .map(|&(from_idx, edge_pos)| graph.adjacency[from_idx][edge_pos].exploitability)
-        .sum();
+        .map(|&(from_idx, edge_pos)| effective_exploitability(graph, from_idx, edge_pos, config))
+        .collect();
 
-    // Path probability decay.
-    let hop_count = path.edges.len();
-    let path_probability = config.decay_factor.powi((hop_count - 1) as i32);
+    score_from_exploitabilities(target_criticality, &exploitabilities, config)
+}
 
-    // Raw score.
-    let raw = target_criticality * exploit_sum * path_probability;
+/// One step's contribution to a path's risk score, as returned by
+/// [`explain_path_risk`].
+#[derive(Debug, Clone)]
+pub struct StepAttribution {
+    /// Index into `path.edges` for this step.
+    pub step_index: usize,
+    /// The effective exploitability used for this step (after any
+    /// `vuln_blend`).
+    pub exploitability: f64,
+    /// IDs of `Vulnerability` nodes attached to this step's target node
+    /// that fed into its vulnerability-derived exploitability.
+    pub contributing_vulns: Vec<String>,
+    /// How much the path's total score would drop if this step alone were
+    /// replaced with `config.default_exploitability` -- the "remove-one"
+    /// marginal. The step with the largest value is the single cheapest
+    /// edge to cut to most reduce this path's risk.
+    pub marginal_contribution: f64,
+}
 
-    // Theoretical maximum: criticality=1.0, all exploitabilities=1.0, decay=1.0
-    let theoretical_max = 1.0 * hop_count as f64;
+/// Risk score breakdown for a path, returned by [`explain_path_risk`].
+#[derive(Debug, Clone)]
+pub struct PathRiskExplanation {
+    /// Same value [`compute_path_risk_score`] would return for this path.
+    pub score: f64,
+    /// Per-step attribution, sorted by `marginal_contribution` descending.
+    pub steps: Vec<StepAttribution>,
+}
 
-    if theoretical_max == 0.0 {
-        return 0.0;
+/// Like [`compute_path_risk_score`], but also explains *why* the path
+/// scored the way it did: for each step, which vulnerabilities drove its
+/// exploitability and its "remove-one" marginal contribution to the total
+/// score -- giving defenders a ranked, per-path choke-point report of the
+/// cheapest edges to cut.
+pub fn explain_path_risk(
+    graph: &InMemoryGraph,
+    path: &RawPath,
+    config: &ScoringConfig,
+) -> PathRiskExplanation {
+    if path.node_indices.is_empty() || path.edges.is_empty() {
+        return PathRiskExplanation {
+            score: 0.0,
+            steps: Vec::new(),
+        };
     }
 
-    // Normalize to 0-10 range.
-    let normalized = (raw / theoretical_max) * config.max_score;
-    normalized.min(config.max_score)
+    let target_idx = *path.node_indices.last().unwrap();
+    let target_criticality = graph.nodes[target_idx].criticality;
+
+    let exploitabilities: Vec<f64> = path
+        .edges
+        .iter()
+        .map(|&(from_idx, edge_pos)| effective_exploitability(graph, from_idx, edge_pos, config))
+        .collect();
+    let score = score_from_exploitabilities(target_criticality, &exploitabilities, config);
+
+    let mut steps: Vec<StepAttribution> = path
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(step_index, &(from_idx, edge_pos))| {
+            let target_node_idx = graph.adjacency[from_idx][edge_pos].target_index;
+            let contributing_vulns = attached_vulnerabilities(graph, target_node_idx)
+                .map(|vuln| vuln.id.clone())
+                .collect();
+
+            let mut without_step = exploitabilities.clone();
+            without_step[step_index] = config.default_exploitability;
+            let score_without = score_from_exploitabilities(target_criticality, &without_step, config);
+
+            StepAttribution {
+                step_index,
+                exploitability: exploitabilities[step_index],
+                contributing_vulns,
+                marginal_contribution: (score - score_without).max(0.0),
+            }
+        })
+        .collect();
+
+    steps.sort_by(|a, b| {
+        b.marginal_contribution
+            .partial_cmp(&a.marginal_contribution)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    PathRiskExplanation { score, steps }
 }
 
 #[cfg(test)]
@@ -134,6 +355,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_probabilistic_model_scores_lower_than_additive_for_same_path() {
+        let graph = build_scored_graph();
+        let additive_config = ScoringConfig {
+            model: ScoringModel::Additive,
+            ..ScoringConfig::default()
+        };
+        let probabilistic_config = ScoringConfig {
+            model: ScoringModel::Probabilistic,
+            ..ScoringConfig::default()
+        };
+
+        // Same path as test_scoring_known_path: 0 → 1 → 2 (2 hops).
+        let path = RawPath {
+            node_indices: vec![0, 1, 2],
+            edges: vec![(0, 0), (1, 0)],
+            total_weight: 0.3,
+        };
+
+        let additive_score = compute_path_risk_score(&graph, &path, &additive_config);
+        let probabilistic_score = compute_path_risk_score(&graph, &path, &probabilistic_config);
+
+        // target_criticality = 1.0, exploitabilities = 0.8, 0.9
+        // probabilistic raw = 1.0 * (0.8 * 0.9) * 10.0 = 7.2
+        assert!((probabilistic_score - 7.2).abs() < 0.01);
+        assert!(probabilistic_score < additive_score);
+    }
+
+    #[test]
+    fn test_probabilistic_model_empty_path_is_zero() {
+        let graph = build_scored_graph();
+        let config = ScoringConfig {
+            model: ScoringModel::Probabilistic,
+            ..ScoringConfig::default()
+        };
+
+        let path = RawPath {
+            node_indices: vec![],
+            edges: vec![],
+            total_weight: 0.0,
+        };
+
+        assert!((compute_path_risk_score(&graph, &path, &config) - 0.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_scoring_known_path() {
         let graph = build_scored_graph();
@@ -201,6 +467,7 @@ mod tests {
             max_score: 10.0,
             decay_factor: 1.0, // No decay
             default_exploitability: 0.5,
+            ..ScoringConfig::default()
         };
 
         // All exploitabilities at 1.0, criticality at 1.0 → should not exceed 10.0
@@ -213,4 +480,251 @@ mod tests {
         let score = compute_path_risk_score(&graph, &path, &config);
         assert!(score <= 10.0);
     }
+
+    /// Graph where node 1's only outgoing edge targets node 2, a
+    /// `Vulnerability` with the given CVSS/EPSS/KEV properties, so
+    /// `vulnerability_derived_exploitability(graph, 1, ..)` reflects exactly
+    /// that one vuln.
+    fn build_graph_with_vuln(cvss: f64, epss: f64, in_kev: bool) -> InMemoryGraph {
+        let nodes = vec![
+            GraphNode {
+                index: 0,
+                id: "host".to_string(),
+                label: "Host".to_string(),
+                criticality: 1.0,
+                is_internet_facing: true,
+                is_crown_jewel: true,
+                properties: serde_json::json!({}),
+            },
+            GraphNode {
+                index: 1,
+                id: "vuln".to_string(),
+                label: "Vulnerability".to_string(),
+                criticality: 0.0,
+                is_internet_facing: false,
+                is_crown_jewel: false,
+                properties: serde_json::json!({
+                    "cvss_score": cvss,
+                    "epss_score": epss,
+                    "in_cisa_kev": in_kev,
+                }),
+            },
+        ];
+
+        let adjacency = vec![
+            vec![GraphEdge {
+                id: "e01".to_string(),
+                edge_type: "HAS_CVE".to_string(),
+                exploitability: 0.1,
+                target_index: 1,
+            }],
+            vec![],
+        ];
+
+        let mut node_index = HashMap::new();
+        for n in &nodes {
+            node_index.insert(n.id.clone(), n.index);
+        }
+
+        InMemoryGraph {
+            nodes,
+            adjacency,
+            node_index,
+        }
+    }
+
+    #[test]
+    fn test_vulnerability_derived_exploitability_matches_formula() {
+        let graph = build_graph_with_vuln(9.0, 0.8, true);
+        let config = ScoringConfig::default();
+
+        // v = clamp(0.5*0.8 + 0.4*(9.0/10.0) + 0.2*1, 0, 1) = clamp(0.4 + 0.36 + 0.2, 0, 1) = 0.96
+        let exploit = vulnerability_derived_exploitability(&graph, 0, &config);
+        assert!((exploit - 0.96).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vulnerability_derived_exploitability_falls_back_without_cve() {
+        let graph = build_scored_graph();
+        let config = ScoringConfig::default();
+
+        // Node 2 has no HAS_CVE edges (no outgoing edges at all).
+        let exploit = vulnerability_derived_exploitability(&graph, 2, &config);
+        assert!((exploit - config.default_exploitability).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_vulnerability_derived_exploitability_compounds_multiple_vulns() {
+        let nodes = vec![
+            GraphNode {
+                index: 0,
+                id: "host".to_string(),
+                label: "Host".to_string(),
+                criticality: 1.0,
+                is_internet_facing: true,
+                is_crown_jewel: true,
+                properties: serde_json::json!({}),
+            },
+            GraphNode {
+                index: 1,
+                id: "vuln-a".to_string(),
+                label: "Vulnerability".to_string(),
+                criticality: 0.0,
+                is_internet_facing: false,
+                is_crown_jewel: false,
+                properties: serde_json::json!({"cvss_score": 3.0, "epss_score": 0.1, "in_cisa_kev": false}),
+            },
+            GraphNode {
+                index: 2,
+                id: "vuln-b".to_string(),
+                label: "Vulnerability".to_string(),
+                criticality: 0.0,
+                is_internet_facing: false,
+                is_crown_jewel: false,
+                properties: serde_json::json!({"cvss_score": 3.0, "epss_score": 0.1, "in_cisa_kev": false}),
+            },
+        ];
+        let adjacency = vec![
+            vec![
+                GraphEdge {
+                    id: "e01".to_string(),
+                    edge_type: "HAS_CVE".to_string(),
+                    exploitability: 0.1,
+                    target_index: 1,
+                },
+                GraphEdge {
+                    id: "e02".to_string(),
+                    edge_type: "HAS_CVE".to_string(),
+                    exploitability: 0.1,
+                    target_index: 2,
+                },
+            ],
+            vec![],
+            vec![],
+        ];
+        let mut node_index = HashMap::new();
+        for n in &nodes {
+            node_index.insert(n.id.clone(), n.index);
+        }
+        let graph = InMemoryGraph { nodes, adjacency, node_index };
+        let config = ScoringConfig::default();
+
+        // Each vuln: v = 0.5*0.1 + 0.4*0.3 = 0.05 + 0.12 = 0.17
+        // Combined: 1 - (1-0.17)^2 = 1 - 0.6889 = 0.3111
+        let exploit = vulnerability_derived_exploitability(&graph, 0, &config);
+        assert!((exploit - 0.3111).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_vuln_blend_overrides_static_exploitability_at_full_blend() {
+        let graph = build_graph_with_vuln(9.0, 0.8, true);
+        let config = ScoringConfig {
+            vuln_blend: 1.0,
+            decay_factor: 1.0,
+            ..ScoringConfig::default()
+        };
+
+        let path = RawPath {
+            node_indices: vec![0, 1],
+            edges: vec![(0, 0)],
+            total_weight: 0.0,
+        };
+
+        // target_criticality = 0.0 (the vuln node itself), so the score is
+        // zero regardless -- assert on the ingredient instead via the edge's
+        // blended exploitability through a non-zero-criticality variant.
+        let _ = compute_path_risk_score(&graph, &path, &config);
+
+        let static_only = ScoringConfig {
+            vuln_blend: 0.0,
+            decay_factor: 1.0,
+            ..ScoringConfig::default()
+        };
+        // Static edge exploitability is 0.1; blended-in vuln exploitability
+        // is 0.96 (see test_vulnerability_derived_exploitability_matches_formula),
+        // so full blend should score higher on a path with positive target
+        // criticality.
+        let graph_crit = {
+            let mut g = build_graph_with_vuln(9.0, 0.8, true);
+            g.nodes[1].criticality = 1.0;
+            g
+        };
+        let blended_score = compute_path_risk_score(&graph_crit, &path, &config);
+        let static_score = compute_path_risk_score(&graph_crit, &path, &static_only);
+        assert!(blended_score > static_score);
+    }
+
+    #[test]
+    fn test_explain_path_risk_matches_compute_path_risk_score() {
+        let graph = build_scored_graph();
+        let config = ScoringConfig::default();
+        let path = RawPath {
+            node_indices: vec![0, 1, 2],
+            edges: vec![(0, 0), (1, 0)],
+            total_weight: 0.3,
+        };
+
+        let score = compute_path_risk_score(&graph, &path, &config);
+        let explanation = explain_path_risk(&graph, &path, &config);
+
+        assert!((explanation.score - score).abs() < f64::EPSILON);
+        assert_eq!(explanation.steps.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_path_risk_ranks_higher_exploitability_step_first() {
+        let graph = build_scored_graph();
+        let config = ScoringConfig::default();
+
+        // Path: 0 → 1 (exploitability 0.8) → 2 (exploitability 0.9).
+        let path = RawPath {
+            node_indices: vec![0, 1, 2],
+            edges: vec![(0, 0), (1, 0)],
+            total_weight: 0.3,
+        };
+
+        let explanation = explain_path_risk(&graph, &path, &config);
+
+        // The step with exploitability 0.9 contributes more to the total
+        // than the step with exploitability 0.8, so it should rank first.
+        assert_eq!(explanation.steps[0].step_index, 1);
+        assert!(
+            explanation.steps[0].marginal_contribution
+                >= explanation.steps[1].marginal_contribution
+        );
+    }
+
+    #[test]
+    fn test_explain_path_risk_surfaces_contributing_vulns() {
+        let graph = build_graph_with_vuln(9.0, 0.8, true);
+        let config = ScoringConfig {
+            vuln_blend: 1.0,
+            ..ScoringConfig::default()
+        };
+        let path = RawPath {
+            node_indices: vec![0, 1],
+            edges: vec![(0, 0)],
+            total_weight: 0.0,
+        };
+
+        let explanation = explain_path_risk(&graph, &path, &config);
+
+        assert_eq!(explanation.steps.len(), 1);
+        assert_eq!(explanation.steps[0].contributing_vulns, vec!["vuln".to_string()]);
+    }
+
+    #[test]
+    fn test_explain_path_risk_empty_path() {
+        let graph = build_scored_graph();
+        let config = ScoringConfig::default();
+        let path = RawPath {
+            node_indices: vec![],
+            edges: vec![],
+            total_weight: 0.0,
+        };
+
+        let explanation = explain_path_risk(&graph, &path, &config);
+        assert_eq!(explanation.score, 0.0);
+        assert!(explanation.steps.is_empty());
+    }
 }
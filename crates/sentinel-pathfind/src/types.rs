@@ -74,6 +74,20 @@ pub struct ReachableNode {
     pub cumulative_exploitability: f64,
 }
 
+/// The single most likely compromise path to a node, found by maximizing
+/// the product of edge exploitabilities along the way (as opposed to
+/// [`ReachableNode::cumulative_exploitability`], which is just whatever
+/// path BFS happened to discover first).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MostProbablePath {
+    pub node_id: String,
+    pub label: String,
+    pub probability: f64,
+    pub hops: usize,
+    /// Node IDs from the compromised node to this one, inclusive of both ends.
+    pub path: Vec<String>,
+}
+
 /// Statistics about the in-memory graph.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphStats {